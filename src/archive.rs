@@ -0,0 +1,404 @@
+//! Read-only scanning of project archives (`archive` feature)
+//!
+//! Supported formats: `.zip`, `.tar`, and gzipped tarballs (`.tar.gz`, `.tgz`).
+//! Parsers read from disk, so the config files in the archive (plus the few
+//! sibling files parsers look at, like lockfiles and build tool wrappers) are
+//! extracted to a private temporary folder that's removed afterwards; nothing
+//! else is written. Lookups above a config file stop at the archive root, and
+//! extraction is capped per file and in total so a zip or tar bomb can't fill
+//! the disk. Reported `config_path`s are relative to the archive root.
+
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use flate2::read::GzDecoder;
+
+use crate::parsers;
+use crate::scanner::parsers_for;
+use crate::{ScanError, ScanResult, TaskRunner};
+
+/// Files parsers only check exist (`.mvn/maven.config` marks the Maven
+/// project root), staged empty. Matched by trailing path.
+const MARKER_FILES: &[&str] = &[
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lock",
+    "bun.lockb",
+    "uv.lock",
+    ".yarnrc.yml",
+    "gradlew",
+    "mvnw",
+    ".mvn/maven.config",
+];
+
+/// Files parsers read next to or above a config file, matched by trailing path
+const SIBLING_FILES: &[&str] = &[
+    "pnpm-workspace.yaml",
+    ".prototools",
+    ".cargo/config.toml",
+    ".cargo/config",
+];
+
+/// Largest file extracted; bigger config files are skipped
+const MAX_ENTRY_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Most bytes extracted from one archive before giving up
+const MAX_TOTAL_BYTES: u64 = 64 * 1024 * 1024;
+
+/// What to do with an archive entry worth keeping
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Config,
+    Sibling,
+    Marker,
+}
+
+/// Scan an archive for task runners. Wanted entries are extracted to a
+/// temporary folder and parsed in archive order; files that fail to parse
+/// are skipped, like [`crate::scan`].
+pub fn scan_archive(path: impl AsRef<Path>) -> ScanResult<Vec<TaskRunner>> {
+    let path = path.as_ref();
+    let staging = tempfile::tempdir()?;
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let configs = if name.ends_with(".zip") {
+        stage_zip(path, staging.path())?
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        stage_tar(path, GzDecoder::new(File::open(path)?), staging.path())?
+    } else if name.ends_with(".tar") {
+        stage_tar(path, File::open(path)?, staging.path())?
+    } else {
+        return Err(ScanError::ParseError {
+            path: path.to_path_buf(),
+            message: "unsupported archive format (expected .zip, .tar, .tar.gz or .tgz)"
+                .to_string(),
+        });
+    };
+
+    let mut runners = Vec::new();
+    parsers::within(staging.path(), || {
+        for relative in configs {
+            let staged = staging.path().join(&relative);
+            for parser in parsers_for(&relative) {
+                if let Ok(Some(mut runner)) = parser.parse(&staged) {
+                    runner.config_path = relative.clone();
+                    runners.push(runner);
+                }
+            }
+        }
+    });
+    Ok(runners)
+}
+
+/// Where an entry should be staged and how, if it's a file worth keeping
+fn wanted(entry_path: &Path) -> Option<(PathBuf, Kind)> {
+    // Never write outside the staging folder
+    let relative: PathBuf = entry_path
+        .components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .map(|c| match c {
+            Component::Normal(part) => Some(part),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+
+    let kind = if !parsers_for(&relative).is_empty() {
+        Kind::Config
+    } else if SIBLING_FILES
+        .iter()
+        .any(|sibling| relative.ends_with(sibling))
+    {
+        Kind::Sibling
+    } else if MARKER_FILES.iter().any(|marker| relative.ends_with(marker)) {
+        Kind::Marker
+    } else {
+        return None;
+    };
+    Some((relative, kind))
+}
+
+/// Bytes extracted so far, shared by every entry of one archive
+struct Budget {
+    archive: PathBuf,
+    used: u64,
+}
+
+impl Budget {
+    fn new(archive: &Path) -> Self {
+        Self {
+            archive: archive.to_path_buf(),
+            used: 0,
+        }
+    }
+
+    /// Write an entry to `staging/relative`: markers empty, other files in
+    /// full. Returns false (writing nothing) if the entry is over
+    /// [`MAX_ENTRY_BYTES`], and errors once the archive is over [`MAX_TOTAL_BYTES`].
+    fn stage(
+        &mut self,
+        staging: &Path,
+        relative: &Path,
+        kind: Kind,
+        contents: &mut impl Read,
+    ) -> ScanResult<bool> {
+        let mut data = Vec::new();
+        if kind != Kind::Marker {
+            // Declared sizes can lie, so count what is actually read
+            contents.take(MAX_ENTRY_BYTES + 1).read_to_end(&mut data)?;
+            if data.len() as u64 > MAX_ENTRY_BYTES {
+                return Ok(false);
+            }
+            self.used += data.len() as u64;
+            if self.used > MAX_TOTAL_BYTES {
+                return Err(ScanError::ParseError {
+                    path: self.archive.clone(),
+                    message: format!(
+                        "more than {} MiB of config files",
+                        MAX_TOTAL_BYTES / 1024 / 1024
+                    ),
+                });
+            }
+        }
+
+        let target = staging.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, data)?;
+        Ok(true)
+    }
+}
+
+/// Stage the wanted files of a tarball, returning the config files in order
+fn stage_tar(path: &Path, reader: impl Read, staging: &Path) -> ScanResult<Vec<PathBuf>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut budget = Budget::new(path);
+    let mut configs = Vec::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let Some((relative, kind)) = wanted(&entry.path()?) else {
+            continue;
+        };
+        if budget.stage(staging, &relative, kind, &mut entry)? && kind == Kind::Config {
+            configs.push(relative);
+        }
+    }
+    Ok(configs)
+}
+
+/// Stage the wanted files of a zip archive, returning the config files in order
+fn stage_zip(path: &Path, staging: &Path) -> ScanResult<Vec<PathBuf>> {
+    let zip_error = |e: zip::result::ZipError| ScanError::ParseError {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    };
+    let mut archive = zip::ZipArchive::new(File::open(path)?).map_err(zip_error)?;
+    let mut budget = Budget::new(path);
+    let mut configs = Vec::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(zip_error)?;
+        if !entry.is_file() {
+            continue;
+        }
+        let Some((relative, kind)) = entry.enclosed_name().as_deref().and_then(wanted) else {
+            continue;
+        };
+        if budget.stage(staging, &relative, kind, &mut entry)? && kind == Kind::Config {
+            configs.push(relative);
+        }
+    }
+    Ok(configs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RunnerType;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    const FILES: &[(&str, &str)] = &[
+        ("project/Makefile", "build:\n\tcc main.c\n"),
+        (
+            "project/web/package.json",
+            r#"{"scripts": {"dev": "vite"}}"#,
+        ),
+        ("project/web/pnpm-lock.yaml", "lockfileVersion: '9.0'\n"),
+        ("project/README.md", "# Project\n"),
+    ];
+
+    fn summary(runners: &[TaskRunner]) -> Vec<(PathBuf, RunnerType, String)> {
+        runners
+            .iter()
+            .map(|r| {
+                (
+                    r.config_path.clone(),
+                    r.runner_type,
+                    r.tasks[0].command.clone(),
+                )
+            })
+            .collect()
+    }
+
+    fn expected() -> Vec<(PathBuf, RunnerType, String)> {
+        vec![
+            (
+                PathBuf::from("project/Makefile"),
+                RunnerType::Make,
+                "make build".to_string(),
+            ),
+            (
+                PathBuf::from("project/web/package.json"),
+                RunnerType::Pnpm,
+                "pnpm run dev".to_string(),
+            ),
+        ]
+    }
+
+    fn write_tar_gz(path: &Path, files: &[(&str, &[u8])]) {
+        let encoder = flate2::write::GzEncoder::new(
+            File::create(path).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        for (name, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *contents).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn test_scan_tar_gz() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.tar.gz");
+        let files: Vec<(&str, &[u8])> = FILES
+            .iter()
+            .map(|(name, contents)| (*name, contents.as_bytes()))
+            .collect();
+        write_tar_gz(&path, &files);
+
+        assert_eq!(summary(&scan_archive(&path).unwrap()), expected());
+    }
+
+    #[test]
+    fn test_siblings_above_config() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.tar.gz");
+        write_tar_gz(
+            &path,
+            &[
+                ("project/pnpm-workspace.yaml", b"packages:\n  - 'apps/*'\n"),
+                (
+                    "project/apps/web/package.json",
+                    br#"{"name": "web", "scripts": {"dev": "vite"}}"#,
+                ),
+                ("project/.cargo/config.toml", b"[alias]\nb = \"build\"\n"),
+                ("project/Cargo.toml", b"[package]\nname = \"app\"\n"),
+            ],
+        );
+
+        let runners = scan_archive(&path).unwrap();
+        let web = runners
+            .iter()
+            .find(|r| r.config_path == Path::new("project/apps/web/package.json"))
+            .unwrap();
+        assert_eq!(web.workspace.as_deref(), Some("project"));
+        let cargo = runners
+            .iter()
+            .find(|r| r.runner_type == RunnerType::Cargo)
+            .unwrap();
+        assert!(cargo.tasks.iter().any(|t| t.command == "cargo b"));
+    }
+
+    #[test]
+    fn test_lookups_stop_at_archive_root() {
+        let seen: Vec<PathBuf> = parsers::within(Path::new("/staging"), || {
+            parsers::ancestors(Path::new("/staging/project/web"))
+                .map(Path::to_path_buf)
+                .collect()
+        });
+        assert_eq!(
+            seen,
+            [
+                PathBuf::from("/staging/project/web"),
+                PathBuf::from("/staging/project"),
+                PathBuf::from("/staging"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_oversized_entry_is_skipped() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.tar.gz");
+        let mut huge = b"build:\n\tcc main.c\n".to_vec();
+        huge.resize(MAX_ENTRY_BYTES as usize + 1, b'\n');
+        write_tar_gz(
+            &path,
+            &[
+                ("project/Makefile", &huge),
+                ("project/web/package.json", FILES[1].1.as_bytes()),
+            ],
+        );
+
+        let runners = scan_archive(&path).unwrap();
+        assert_eq!(runners.len(), 1);
+        assert_eq!(runners[0].runner_type, RunnerType::Npm);
+    }
+
+    #[test]
+    fn test_total_size_is_capped() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.tar.gz");
+        let contents = vec![b'\n'; MAX_ENTRY_BYTES as usize];
+        let names: Vec<String> = (0..=MAX_TOTAL_BYTES / MAX_ENTRY_BYTES)
+            .map(|i| format!("project/{i}/Makefile"))
+            .collect();
+        let files: Vec<(&str, &[u8])> = names
+            .iter()
+            .map(|name| (name.as_str(), contents.as_slice()))
+            .collect();
+        write_tar_gz(&path, &files);
+
+        assert!(matches!(
+            scan_archive(&path),
+            Err(ScanError::ParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_scan_zip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&path).unwrap());
+        for (name, contents) in FILES {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+
+        assert_eq!(summary(&scan_archive(&path).unwrap()), expected());
+    }
+
+    #[test]
+    fn test_unsupported_format() {
+        assert!(matches!(
+            scan_archive("project.rar"),
+            Err(ScanError::ParseError { .. })
+        ));
+    }
+}
@@ -0,0 +1,89 @@
+//! Opt-in runner availability checks for JSON output (`--check-availability`)
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+use task_runner_detector::{is_installed, Task, TaskRunner};
+
+/// Cached PATH lookups for the programs tasks invoke
+#[derive(Default)]
+pub struct Availability {
+    cache: HashMap<String, bool>,
+}
+
+impl Availability {
+    /// Check whether the program a task invokes can be run. Relative paths
+    /// (`./mvnw`, `./run.sh`) are resolved against the config file's folder.
+    pub fn check(&mut self, runner: &TaskRunner, task: &Task) -> bool {
+        let Some(program) = task.command.split_whitespace().next() else {
+            return false;
+        };
+
+        if program.contains('/') {
+            let dir = runner
+                .config_path
+                .parent()
+                .unwrap_or(runner.config_path.as_path());
+            return dir.join(program).is_file();
+        }
+
+        *self
+            .cache
+            .entry(program.to_string())
+            .or_insert_with(|| is_installed(program))
+    }
+
+    /// Serialize a runner, adding `available` to each task
+    pub fn runner_json(&mut self, runner: &TaskRunner) -> Value {
+        let mut value = serde_json::to_value(runner).unwrap_or(Value::Null);
+        if let Some(tasks) = value.get_mut("tasks").and_then(Value::as_array_mut) {
+            for (json, task) in tasks.iter_mut().zip(&runner.tasks) {
+                if let Some(object) = json.as_object_mut() {
+                    object.insert("available".to_string(), self.check(runner, task).into());
+                }
+            }
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use task_runner_detector::RunnerType;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_runner_json_adds_availability() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("mvnw"), "#!/bin/sh\n").unwrap();
+
+        let task = |name: &str, command: &str| Task {
+            name: name.to_string(),
+            command: command.to_string(),
+            description: None,
+            script: None,
+            ..Default::default()
+        };
+        let runner = TaskRunner {
+            config_path: dir.path().join("pom.xml"),
+            runner_type: RunnerType::Maven,
+            tasks: vec![
+                task("test", "./mvnw test"),
+                task("missing", "./gradlew build"),
+                task("other", "definitely-not-a-real-binary-xyz run"),
+            ],
+            ..Default::default()
+        };
+
+        let json = Availability::default().runner_json(&runner);
+        let available: Vec<_> = json["tasks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["available"].as_bool().unwrap())
+            .collect();
+        assert_eq!(available, vec![true, false, false]);
+    }
+}
@@ -1,12 +1,16 @@
 //! Backend thread for task registry, fuzzy search, and scanner integration
 
-use crate::messages::{SearchRequest, SearchResponse, TaskItem};
+use crate::availability::Availability;
+use crate::matching::MatchOptions;
+use crate::messages::{FolderState, SearchRequest, SearchResponse, TaskItem};
 use crate::registry::{Registry, Task};
-use crate::{scan_streaming, ScanOptions, TaskRunner};
+use crate::{RunnerType, ScanOptions, TaskRunner};
 use nucleo::{Config, Nucleo, Utf32String};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::mpsc::{Receiver, Sender, TryRecvError};
 use std::sync::{Arc, RwLock};
+use task_runner_detector::{scan_streaming_cancellable, ScanHandle};
 
 /// Shared task storage type
 pub type SharedTasks = Arc<RwLock<Vec<TaskItem>>>;
@@ -29,12 +33,32 @@ pub struct Backend {
     root: PathBuf,
     /// Current query
     current_query: String,
+    /// Case/normalization settings for the query pattern
+    match_options: MatchOptions,
+    /// How tasks are listed when there's no query
+    order: OrderOptions,
     /// Whether scanning is complete
     scanning_done: bool,
+    /// Looks up whether task commands can run (None: assume they can)
+    availability: Option<Availability>,
+}
+
+/// How tasks are ordered when listed without a query
+#[derive(Debug, Clone, Default)]
+pub struct OrderOptions {
+    /// Keep each runner's declaration order instead of sorting by name
+    pub preserve_order: bool,
+    /// Runners listed first within a folder, highest priority first
+    pub runner_priority: Vec<RunnerType>,
 }
 
 impl Backend {
-    pub fn new(root: PathBuf, tasks: SharedTasks) -> Self {
+    pub fn new(
+        root: PathBuf,
+        tasks: SharedTasks,
+        match_options: MatchOptions,
+        order: OrderOptions,
+    ) -> Self {
         // Use multiple threads for parallel fuzzy matching
         let num_threads = std::thread::available_parallelism()
             .map(|n| n.get())
@@ -44,10 +68,13 @@ impl Backend {
         Self {
             nucleo,
             tasks,
-            registry: Registry::new(),
+            registry: Registry::with_runner_priority(order.runner_priority.clone()),
             root,
             current_query: String::new(),
+            match_options,
+            order,
             scanning_done: false,
+            availability: Some(Availability::default()),
         }
     }
 
@@ -101,7 +128,7 @@ impl Backend {
     fn add_runner(&mut self, runner: TaskRunner) {
         let injector = self.nucleo.injector();
 
-        for task in runner.tasks {
+        for task in &runner.tasks {
             let registry_task = Task {
                 name: task.name.clone(),
                 runner_type: runner.runner_type,
@@ -114,13 +141,19 @@ impl Backend {
             // Only add if new (registry grew)
             if self.registry.len() > len_before {
                 let folder = registry_task.folder_display(&self.root);
+                let available = self
+                    .availability
+                    .as_mut()
+                    .map_or(true, |availability| availability.check(&runner, task));
 
                 let item = TaskItem {
                     folder: folder.clone(),
                     command: task.command.clone(),
                     script: task.script.clone(),
+                    description: task.description.clone(),
                     runner_type: runner.runner_type,
                     config_path: runner.config_path.clone(),
+                    available,
                 };
 
                 // Add to shared tasks
@@ -132,7 +165,8 @@ impl Backend {
                 };
 
                 // Add to nucleo
-                let search_text = format!("{} {}", folder, task.command);
+                let search_text =
+                    crate::matching::search_text(&folder, &task.command, runner.runner_type);
                 injector.push(TaskRef { index }, |_, cols| {
                     cols[0] = Utf32String::from(search_text.as_str());
                 });
@@ -140,10 +174,63 @@ impl Backend {
         }
     }
 
+    /// Hide tasks in collapsed folders. The first task of each collapsed
+    /// folder is kept as a stand-in for the folder's row; the returned map
+    /// holds the folder each stand-in represents.
+    fn collapse_folders(
+        &self,
+        indices: Vec<u32>,
+        folders: &FolderState,
+    ) -> (Vec<u32>, HashMap<u32, String>) {
+        let tasks = self.tasks.read().unwrap();
+        let mut visible = Vec::with_capacity(indices.len());
+        let mut collapsed = HashMap::new();
+        let mut last_collapsed: Option<&str> = None;
+
+        for idx in indices {
+            match folders.collapsed_ancestor(&tasks[idx as usize].folder) {
+                None => {
+                    last_collapsed = None;
+                    visible.push(idx);
+                }
+                Some(folder) if last_collapsed == Some(folder) => {}
+                Some(folder) => {
+                    last_collapsed = Some(folder);
+                    collapsed.insert(idx, folder.to_string());
+                    visible.push(idx);
+                }
+            }
+        }
+        (visible, collapsed)
+    }
+
+    /// Position of a task in the list, or of the collapsed folder hiding it
+    fn position_of(
+        &self,
+        indices: &[u32],
+        collapsed: &HashMap<u32, String>,
+        task: u32,
+    ) -> Option<usize> {
+        if let Some(pos) = indices.iter().position(|&idx| idx == task) {
+            return Some(pos);
+        }
+        let tasks = self.tasks.read().unwrap();
+        let folder = &tasks.get(task as usize)?.folder;
+        indices.iter().position(|idx| {
+            collapsed.get(idx).is_some_and(|hidden| {
+                folder == hidden
+                    || folder
+                        .strip_prefix(hidden.as_str())
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+        })
+    }
+
     /// Calculate the correct scroll offset to make selected_index visible
     fn calculate_scroll_for_selected(
         &self,
         all_indices: &[u32],
+        collapsed: &HashMap<u32, String>,
         requested_offset: usize,
         selected_index: usize,
         viewport_lines: usize,
@@ -155,11 +242,24 @@ impl Backend {
         let selected_index = selected_index.min(all_indices.len().saturating_sub(1));
         let tasks = self.tasks.read().unwrap();
 
+        // A collapsed folder's row sits where its header would be
+        let folder_of = |idx: usize| -> &str {
+            let task = all_indices[idx];
+            collapsed.get(&task).unwrap_or(&tasks[task as usize].folder)
+        };
+        let row_lines = |idx: usize| -> usize {
+            if collapsed.contains_key(&all_indices[idx]) {
+                0
+            } else {
+                1
+            }
+        };
+
         // Helper to count headers for a task when it's at a given position in viewport
         let headers_for_task = |idx: usize, prev_idx: Option<usize>| -> usize {
-            let folder = &tasks[all_indices[idx] as usize].folder;
+            let folder = folder_of(idx);
             if let Some(prev) = prev_idx {
-                let prev_folder = &tasks[all_indices[prev] as usize].folder;
+                let prev_folder = folder_of(prev);
                 if prev_folder == folder {
                     0
                 } else {
@@ -199,7 +299,7 @@ impl Backend {
                 Some(i - 1)
             };
             let headers = headers_for_task(i, prev);
-            let task_lines = headers + 1;
+            let task_lines = headers + row_lines(i);
 
             if lines_used + task_lines > viewport_lines {
                 break;
@@ -227,7 +327,7 @@ impl Backend {
             for i in try_scroll..=selected_index {
                 let prev = if i == try_scroll { None } else { Some(i - 1) };
                 let headers = headers_for_task(i, prev);
-                let task_lines = headers + 1;
+                let task_lines = headers + row_lines(i);
 
                 if lines_used + task_lines > viewport_lines {
                     break;
@@ -255,8 +355,8 @@ impl Backend {
             self.nucleo.pattern.reparse(
                 0,
                 &req.query,
-                nucleo::pattern::CaseMatching::Ignore,
-                nucleo::pattern::Normalization::Smart,
+                self.match_options.case_matching(),
+                self.match_options.normalization(),
                 false,
             );
             self.current_query = req.query.clone();
@@ -274,26 +374,38 @@ impl Backend {
         let snapshot = self.nucleo.snapshot();
         let matched_count = snapshot.matched_item_count();
 
-        let matched_indices: Vec<u32> = if req.query.is_empty() {
+        let (matched_indices, collapsed) = if req.query.is_empty() {
             // No query - show all tasks sorted by folder/name
-            self.registry
-                .sorted_ids()
-                .into_iter()
-                .map(|id| id.0 as u32)
-                .collect()
+            let ids = if self.order.preserve_order {
+                self.registry.declaration_ordered_ids()
+            } else {
+                self.registry.sorted_ids()
+            };
+            let ids = ids.into_iter().map(|id| id.0 as u32).collect();
+            self.collapse_folders(ids, &req.folders)
         } else {
             // With query - nucleo returns items sorted by score (best first)
-            snapshot
+            let ids = snapshot
                 .matched_items(0..matched_count)
                 .map(|item| item.data.index)
-                .collect()
+                .collect();
+            (ids, HashMap::new())
+        };
+
+        // Keep following a task across query changes if it still matches
+        let selected_index = match req.follow_task {
+            Some(task) => self
+                .position_of(&matched_indices, &collapsed, task)
+                .unwrap_or(0),
+            None => req.selected_index,
         };
 
         // Calculate corrected scroll offset
         let corrected_offset = self.calculate_scroll_for_selected(
             &matched_indices,
+            &collapsed,
             req.offset,
-            req.selected_index,
+            selected_index,
             req.viewport_lines,
         );
 
@@ -303,6 +415,10 @@ impl Backend {
         let start = corrected_offset.min(matched_tasks);
         let end = (corrected_offset + req.limit).min(matched_tasks);
         let sliced = matched_indices[start..end].to_vec();
+        let collapsed = collapsed
+            .into_iter()
+            .filter(|(idx, _)| sliced.contains(idx))
+            .collect();
 
         SearchResponse {
             matched_indices: sliced,
@@ -310,9 +426,17 @@ impl Backend {
             total_tasks,
             matched_tasks,
             scanning_done: self.scanning_done,
+            selected_index,
+            collapsed,
         }
     }
 
+    /// Treat every task as runnable so rendering doesn't depend on PATH
+    #[cfg(test)]
+    pub fn skip_availability_for_test(&mut self) {
+        self.availability = None;
+    }
+
     /// Add a runner for testing (bypasses channel)
     #[cfg(test)]
     pub fn add_runner_for_test(&mut self, runner: TaskRunner) {
@@ -331,21 +455,26 @@ impl Backend {
     }
 }
 
-/// Spawn the backend thread
+/// Spawn the backend thread. The returned [`ScanHandle`] stops the
+/// background scan once its results are no longer needed.
 pub fn spawn_backend(
     root: PathBuf,
     options: ScanOptions,
+    match_options: MatchOptions,
+    order: OrderOptions,
     tasks: SharedTasks,
     request_rx: Receiver<SearchRequest>,
     response_tx: Sender<SearchResponse>,
-) -> std::thread::JoinHandle<()> {
+) -> (std::thread::JoinHandle<()>, ScanHandle) {
     let (scanner_tx, scanner_rx) = std::sync::mpsc::channel();
-    let _scanner_handle = scan_streaming(root.clone(), options, scanner_tx);
+    let (_scanner_thread, scan_handle) =
+        scan_streaming_cancellable(root.clone(), options, scanner_tx);
 
-    std::thread::spawn(move || {
-        let backend = Backend::new(root, tasks);
+    let backend_thread = std::thread::spawn(move || {
+        let backend = Backend::new(root, tasks, match_options, order);
         backend.run(scanner_rx, request_rx, response_tx);
-    })
+    });
+    (backend_thread, scan_handle)
 }
 
 #[cfg(test)]
@@ -355,7 +484,12 @@ mod tests {
 
     fn create_test_backend() -> (Backend, SharedTasks) {
         let tasks = Arc::new(RwLock::new(Vec::new()));
-        let backend = Backend::new(PathBuf::from("/test"), tasks.clone());
+        let backend = Backend::new(
+            PathBuf::from("/test"),
+            tasks.clone(),
+            MatchOptions::default(),
+            OrderOptions::default(),
+        );
         (backend, tasks)
     }
 
@@ -371,7 +505,9 @@ mod tests {
                 command: "npm run build".to_string(),
                 description: None,
                 script: None,
+                ..Default::default()
             }],
+            ..Default::default()
         });
 
         let tasks = tasks.read().unwrap();
@@ -394,7 +530,9 @@ mod tests {
                     command: "npm run build".to_string(),
                     description: None,
                     script: None,
+                    ..Default::default()
                 }],
+                ..Default::default()
             });
         }
 
@@ -402,6 +540,59 @@ mod tests {
         assert_eq!(tasks.len(), 1); // Should be deduplicated
     }
 
+    #[test]
+    fn test_backend_follows_task_across_query_change() {
+        let (mut backend, tasks) = create_test_backend();
+
+        backend.add_runner(TaskRunner {
+            config_path: PathBuf::from("/test/package.json"),
+            runner_type: RunnerType::Npm,
+            tasks: ["build", "build:prod", "test"]
+                .iter()
+                .map(|name| crate::Task {
+                    name: name.to_string(),
+                    command: format!("npm run {}", name),
+                    description: None,
+                    script: None,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        });
+        for _ in 0..10 {
+            backend.nucleo.tick(10);
+        }
+        backend.scanning_done = true;
+
+        let prod = tasks
+            .read()
+            .unwrap()
+            .iter()
+            .position(|t| t.command == "npm run build:prod")
+            .unwrap() as u32;
+
+        let request = |query: &str, follow_task| SearchRequest {
+            query: query.to_string(),
+            offset: 0,
+            limit: 100,
+            viewport_lines: 30,
+            selected_index: 0,
+            follow_task,
+            folders: FolderState::default(),
+        };
+
+        // Still matches: selection resolves to its new position
+        let response = backend.handle_search(request("bu", Some(prod)));
+        assert_eq!(
+            response.matched_indices[response.selected_index - response.offset],
+            prod
+        );
+
+        // No longer matches: fall back to the top
+        let response = backend.handle_search(request("test", Some(prod)));
+        assert_eq!(response.selected_index, 0);
+    }
+
     #[test]
     fn test_backend_search_returns_sorted_indices() {
         let (mut backend, tasks) = create_test_backend();
@@ -414,7 +605,9 @@ mod tests {
                 command: "npm test".to_string(),
                 description: None,
                 script: None,
+                ..Default::default()
             }],
+            ..Default::default()
         });
 
         backend.add_runner(TaskRunner {
@@ -425,7 +618,9 @@ mod tests {
                 command: "npm run build".to_string(),
                 description: None,
                 script: None,
+                ..Default::default()
             }],
+            ..Default::default()
         });
 
         // Let nucleo process
@@ -440,6 +635,8 @@ mod tests {
             limit: 100,
             viewport_lines: 30,
             selected_index: 0,
+            follow_task: None,
+            folders: FolderState::default(),
         });
 
         // Should be sorted by folder: a before b
@@ -449,4 +646,75 @@ mod tests {
         let second_folder = &tasks[response.matched_indices[1] as usize].folder;
         assert!(first_folder < second_folder);
     }
+
+    #[test]
+    fn test_backend_collapses_folders() {
+        let (mut backend, tasks) = create_test_backend();
+
+        for (path, name) in [
+            ("/test/package.json", "dev"),
+            ("/test/apps/api/package.json", "serve"),
+            ("/test/apps/web/package.json", "build"),
+            ("/test/apps/web/package.json", "test"),
+        ] {
+            backend.add_runner(TaskRunner {
+                config_path: PathBuf::from(path),
+                runner_type: RunnerType::Npm,
+                tasks: vec![crate::Task {
+                    name: name.to_string(),
+                    command: format!("npm run {}", name),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            });
+        }
+        backend.scanning_done = true;
+
+        let index_of = |command: &str| {
+            tasks
+                .read()
+                .unwrap()
+                .iter()
+                .position(|t| t.command == command)
+                .unwrap() as u32
+        };
+        let mut folders = FolderState {
+            collapsed_by_default: true,
+            ..Default::default()
+        };
+        let request = |folders: &FolderState, follow_task| SearchRequest {
+            query: String::new(),
+            offset: 0,
+            limit: 100,
+            viewport_lines: 30,
+            selected_index: 0,
+            follow_task,
+            folders: folders.clone(),
+        };
+
+        // Everything below the root is one "apps" row
+        let response = backend.handle_search(request(&folders, None));
+        assert_eq!(
+            response.matched_indices,
+            vec![index_of("npm run dev"), index_of("npm run serve")]
+        );
+        assert_eq!(response.collapsed[&index_of("npm run serve")], "apps");
+
+        // Expanding "apps" shows its subfolders, still collapsed
+        folders.set_collapsed("apps", false);
+        let response = backend.handle_search(request(&folders, None));
+        assert_eq!(response.matched_indices.len(), 3);
+        assert_eq!(response.collapsed[&index_of("npm run build")], "apps/web");
+
+        // A task hidden in a collapsed folder selects that folder's row
+        let response = backend.handle_search(request(&folders, Some(index_of("npm run test"))));
+        assert_eq!(response.selected_index, 2);
+
+        // Search ignores collapse state
+        let mut searching = request(&folders, None);
+        searching.query = "test".to_string();
+        let response = backend.handle_search(searching);
+        assert!(response.collapsed.is_empty());
+        assert!(response.matched_indices.contains(&index_of("npm run test")));
+    }
 }
@@ -0,0 +1,38 @@
+//! Copy text to the clipboard
+//!
+//! With the `clipboard` feature the system clipboard is used. Without it, or
+//! when there's no system clipboard to reach (e.g. over SSH), the text is
+//! sent to the terminal as an OSC 52 escape, which most terminals put on
+//! their clipboard.
+
+use std::io::stdout;
+
+use crossterm::clipboard::CopyToClipboard;
+use crossterm::execute;
+
+/// Copy `text`, preferring the system clipboard
+pub fn copy(text: &str) {
+    if !copy_system(text) {
+        execute!(stdout(), CopyToClipboard::to_clipboard_from(text)).ok();
+    }
+}
+
+#[cfg(feature = "clipboard")]
+fn copy_system(text: &str) -> bool {
+    use std::sync::{Mutex, OnceLock};
+
+    // On X11/Wayland the copied text is served by the process that owns the
+    // clipboard handle, so keep it alive until `task` exits
+    static CLIPBOARD: OnceLock<Option<Mutex<arboard::Clipboard>>> = OnceLock::new();
+    let clipboard = CLIPBOARD.get_or_init(|| arboard::Clipboard::new().ok().map(Mutex::new));
+    clipboard.as_ref().is_some_and(|clipboard| {
+        clipboard
+            .lock()
+            .is_ok_and(|mut clipboard| clipboard.set_text(text).is_ok())
+    })
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn copy_system(_text: &str) -> bool {
+    false
+}
@@ -0,0 +1,131 @@
+//! Shell completion scripts for task names (`--complete`)
+//!
+//! Task names come from the scanned files, so the script is generated for
+//! the folder it's run in: the names found there are baked into the script
+//! as the candidates for `--exec`. Regenerate it after adding tasks. A name
+//! shared by several tasks is offered with its folder (and runner) instead,
+//! as `--exec` resolves it (see `exec_completions` in main.rs).
+
+use std::collections::BTreeSet;
+
+/// Shells `--complete` can write a script for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// A completion script offering `names` (sorted, without duplicates) after `--exec`
+pub fn script<'a>(shell: Shell, names: impl IntoIterator<Item = &'a str>) -> String {
+    let names: BTreeSet<&str> = names.into_iter().collect();
+    match shell {
+        Shell::Bash => bash(&names),
+        Shell::Zsh => zsh(&names),
+        Shell::Fish => fish(&names),
+    }
+}
+
+/// Single-quote a word for bash and zsh
+fn posix_quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', r"'\''"))
+}
+
+/// Single-quote a word for fish
+fn fish_quote(word: &str) -> String {
+    format!("'{}'", word.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+fn bash(names: &BTreeSet<&str>) -> String {
+    let words: Vec<_> = names.iter().map(|name| posix_quote(name)).collect();
+    format!(
+        r#"# bash completion for task names (source this file)
+_task_names=({})
+
+_task_complete() {{
+    local cur prev
+    # Keep `build:prod` in one word when bash-completion is available
+    if declare -F _get_comp_words_by_ref >/dev/null; then
+        _get_comp_words_by_ref -n : cur prev
+    else
+        cur="${{COMP_WORDS[COMP_CWORD]}}"
+        prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    fi
+    if [[ "$prev" == "--exec" ]]; then
+        local IFS=$'\n'
+        COMPREPLY=($(compgen -W "$(printf '%s\n' "${{_task_names[@]}}")" -- "$cur"))
+        if declare -F __ltrim_colon_completions >/dev/null; then
+            __ltrim_colon_completions "$cur"
+        fi
+    fi
+}}
+
+complete -o default -F _task_complete task
+"#,
+        words.join(" ")
+    )
+}
+
+fn zsh(names: &BTreeSet<&str>) -> String {
+    let words: Vec<_> = names.iter().map(|name| posix_quote(name)).collect();
+    format!(
+        r#"#compdef task
+# zsh completion for task names (source this file after compinit)
+
+_task() {{
+    local state
+    local -a names
+    names=({})
+    _arguments \
+        '--exec[run the task named NAME]:task name:->names' \
+        '*:path:_files'
+    if [[ "$state" == names ]]; then
+        compadd -a names
+    fi
+}}
+
+compdef _task task
+"#,
+        words.join(" ")
+    )
+}
+
+fn fish(names: &BTreeSet<&str>) -> String {
+    let mut script = String::from("# fish completion for task names (source this file)\n");
+    script.push_str("complete -c task -l exec -x\n");
+    for name in names {
+        script.push_str(&format!(
+            "complete -c task -l exec -x -a {}\n",
+            fish_quote(name)
+        ));
+    }
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_deduped_and_quoted() {
+        let names = ["test", "build", "it's", "build"];
+
+        let bash = script(Shell::Bash, names);
+        assert!(bash.contains(r"_task_names=('build' 'it'\''s' 'test')"));
+        assert!(bash.contains("complete -o default -F _task_complete task"));
+
+        let zsh = script(Shell::Zsh, names);
+        assert!(zsh.contains(r"names=('build' 'it'\''s' 'test')"));
+
+        let fish = script(Shell::Fish, names);
+        let candidates: Vec<_> = fish.lines().filter(|l| l.contains(" -a ")).collect();
+        assert_eq!(
+            candidates,
+            vec![
+                "complete -c task -l exec -x -a 'build'",
+                r"complete -c task -l exec -x -a 'it\'s'",
+                "complete -c task -l exec -x -a 'test'",
+            ]
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! Code generation from scan results (`--generate`, also `--export`)
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde_json::{json, Value};
+use task_runner_detector::TaskRunner;
+
+use crate::work_dir;
+use task_runner_detector::{folder_display, ordered_tasks};
+
+/// Output formats for `--generate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GenerateFormat {
+    /// A Makefile with one phony target per task
+    Makefile,
+    /// A VS Code `.vscode/tasks.json` with one shell task per task
+    Vscode,
+}
+
+/// Generate output for all runners in the given format
+pub fn generate(format: GenerateFormat, runners: &[TaskRunner], root: &Path) -> String {
+    match format {
+        GenerateFormat::Makefile => makefile(runners, root),
+        GenerateFormat::Vscode => vscode_tasks(runners, root),
+    }
+}
+
+/// Make a task name safe to use as a make target
+fn sanitize_target(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Generate a Makefile exposing every task as a phony target.
+///
+/// Targets are named after the task; names that occur in more than one place
+/// are prefixed with their folder (`apps/web/build`), and with the runner if
+/// that still isn't unique (then numbered). Output is ordered by folder then task name.
+fn makefile(runners: &[TaskRunner], root: &Path) -> String {
+    let tasks = ordered_tasks(runners, root);
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for (_, task) in &tasks {
+        *counts.entry(sanitize_target(&task.name)).or_default() += 1;
+    }
+
+    let mut folder_counts: HashMap<(String, String), usize> = HashMap::new();
+    for (runner, task) in &tasks {
+        let key = (
+            folder_display(&runner.config_path, root),
+            sanitize_target(&task.name),
+        );
+        *folder_counts.entry(key).or_default() += 1;
+    }
+
+    let mut used = HashSet::new();
+    let mut targets = Vec::new();
+    for (runner, task) in &tasks {
+        let folder = folder_display(&runner.config_path, root);
+        let base = sanitize_target(&task.name);
+
+        let mut target = base.clone();
+        if counts[&base] > 1 && folder != "." {
+            target = format!("{}/{}", sanitize_target(&folder), base);
+        }
+        if folder_counts[&(folder.clone(), base)] > 1 {
+            target = format!("{}-{}", target, runner.runner_type.display_name());
+        }
+        // Same name from the same runner in one folder: number the repeats
+        if used.contains(&target) {
+            let mut n = 2;
+            while used.contains(&format!("{}-{}", target, n)) {
+                n += 1;
+            }
+            target = format!("{}-{}", target, n);
+        }
+        used.insert(target.clone());
+
+        let command = task.command.replace('$', "$$");
+        let recipe = if folder == "." {
+            command
+        } else {
+            format!("cd {} && {}", folder, command)
+        };
+        targets.push((target, recipe));
+    }
+
+    let mut output = String::from("# Generated by `task --generate makefile`. Do not edit.\n\n");
+    if targets.is_empty() {
+        return output;
+    }
+
+    let names: Vec<&str> = targets.iter().map(|(t, _)| t.as_str()).collect();
+    output.push_str(&format!(".PHONY: {}\n", names.join(" ")));
+    for (target, recipe) in &targets {
+        output.push_str(&format!("\n{}:\n\t{}\n", target, recipe));
+    }
+    output
+}
+
+/// Generate a VS Code `tasks.json` document.
+///
+/// Tasks are labelled `<runner>: <name>`; VS Code needs unique labels, so
+/// repeated labels get the folder appended (then a number). Each task runs
+/// in its config file's folder via `options.cwd`, left out for tasks that run
+/// at the root so the workspace folder is used.
+fn vscode_tasks(runners: &[TaskRunner], root: &Path) -> String {
+    let mut used = HashSet::new();
+    let tasks: Vec<Value> = ordered_tasks(runners, root)
+        .into_iter()
+        .map(|(runner, task)| {
+            let mut label = format!("{}: {}", runner.runner_type.display_name(), task.name);
+            if used.contains(&label) {
+                label = format!("{} ({})", label, folder_display(&runner.config_path, root));
+            }
+            if used.contains(&label) {
+                let mut n = 2;
+                while used.contains(&format!("{} {}", label, n)) {
+                    n += 1;
+                }
+                label = format!("{} {}", label, n);
+            }
+            used.insert(label.clone());
+
+            let mut entry = json!({
+                "label": label,
+                "type": "shell",
+                "command": task.command,
+                "problemMatcher": [],
+            });
+            let dir = work_dir(&runner.config_path, root);
+            if let Some(relative) = dir
+                .strip_prefix(root)
+                .ok()
+                .filter(|r| !r.as_os_str().is_empty())
+            {
+                let cwd = format!("${{workspaceFolder}}/{}", relative.to_string_lossy());
+                entry["options"] = json!({ "cwd": cwd });
+            }
+            entry
+        })
+        .collect();
+
+    let document = json!({ "version": "2.0.0", "tasks": tasks });
+    let mut output = serde_json::to_string_pretty(&document).unwrap_or_default();
+    output.push('\n');
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use task_runner_detector::{RunnerType, Task};
+
+    fn runner(config_path: &str, runner_type: RunnerType, tasks: &[(&str, &str)]) -> TaskRunner {
+        TaskRunner {
+            config_path: PathBuf::from(config_path),
+            runner_type,
+            tasks: tasks
+                .iter()
+                .map(|(name, command)| Task {
+                    name: name.to_string(),
+                    command: command.to_string(),
+                    description: None,
+                    script: None,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_makefile_disambiguates_collisions() {
+        let root = Path::new("/repo");
+        let runners = vec![
+            runner(
+                "/repo/apps/web/package.json",
+                RunnerType::Npm,
+                &[
+                    ("build", "npm run build"),
+                    ("build:prod", "npm run build:prod"),
+                ],
+            ),
+            runner(
+                "/repo/Makefile",
+                RunnerType::Make,
+                &[("build", "make build")],
+            ),
+            runner(
+                "/repo/package.json",
+                RunnerType::Npm,
+                &[("build", "npm run build")],
+            ),
+            runner(
+                "/repo/justfile",
+                RunnerType::Just,
+                &[("deploy", "just deploy $ENV")],
+            ),
+        ];
+
+        let output = generate(GenerateFormat::Makefile, &runners, root);
+        let expected = "\
+# Generated by `task --generate makefile`. Do not edit.
+
+.PHONY: build-make build-npm deploy apps/web/build build-prod
+
+build-make:
+\tmake build
+
+build-npm:
+\tnpm run build
+
+deploy:
+\tjust deploy $$ENV
+
+apps/web/build:
+\tcd apps/web && npm run build
+
+build-prod:
+\tcd apps/web && npm run build:prod
+";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_vscode_tasks() {
+        let root = Path::new("/repo");
+        let runners = vec![
+            runner(
+                "/repo/apps/web/package.json",
+                RunnerType::Npm,
+                &[("build", "npm run build")],
+            ),
+            runner(
+                "/repo/package.json",
+                RunnerType::Npm,
+                &[("build", "npm run build")],
+            ),
+        ];
+
+        let output = generate(GenerateFormat::Vscode, &runners, root);
+        let document: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(document["version"], "2.0.0");
+        assert_eq!(
+            document["tasks"],
+            json!([
+                {
+                    "label": "npm: build",
+                    "type": "shell",
+                    "command": "npm run build",
+                    "problemMatcher": [],
+                },
+                {
+                    "label": "npm: build (apps/web)",
+                    "type": "shell",
+                    "command": "npm run build",
+                    "problemMatcher": [],
+                    "options": { "cwd": "${workspaceFolder}/apps/web" },
+                },
+            ])
+        );
+    }
+}
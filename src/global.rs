@@ -0,0 +1,108 @@
+//! User-level global tasks from `~/.config/task-runner/tasks.toml`
+//!
+//! The file holds ad-hoc `name = "command"` entries that are available in
+//! every project:
+//!
+//! ```toml
+//! sync = "git pull --rebase"
+//! serve = "python3 -m http.server"
+//! ```
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+/// Folder global tasks are listed under
+pub const GLOBAL_FOLDER: &str = "global";
+
+/// Location of the global tasks file: `$XDG_CONFIG_HOME/task-runner/tasks.toml`,
+/// falling back to `~/.config/task-runner/tasks.toml`
+pub fn global_tasks_path() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("task-runner").join("tasks.toml"))
+}
+
+/// Load global tasks from a tasks file. A missing file has no tasks;
+/// entries whose value isn't a string are skipped.
+pub fn load_global_tasks(path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let entries: IndexMap<String, toml::Value> =
+        toml::from_str(&content).map_err(|e| ScanError::ParseError {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+
+    let tasks: Vec<Task> = entries
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let command = value.as_str()?.trim().to_string();
+            Some(Task {
+                name,
+                command,
+                description: Some("Global task".to_string()),
+                script: None,
+                ..Default::default()
+            })
+        })
+        .collect();
+
+    if tasks.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(TaskRunner {
+        config_path: path.to_path_buf(),
+        runner_type: RunnerType::Script,
+        tasks,
+        ..Default::default()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_global_tasks() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("tasks.toml");
+        assert!(load_global_tasks(&path).unwrap().is_none());
+
+        fs::write(
+            &path,
+            r#"
+sync = "git pull --rebase"
+serve = "python3 -m http.server"
+ignored = 3
+"#,
+        )
+        .unwrap();
+
+        let runner = load_global_tasks(&path).unwrap().unwrap();
+        let tasks: Vec<_> = runner
+            .tasks
+            .iter()
+            .map(|t| (t.name.as_str(), t.command.as_str()))
+            .collect();
+        assert_eq!(
+            tasks,
+            vec![
+                ("sync", "git pull --rebase"),
+                ("serve", "python3 -m http.server")
+            ]
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Grouping of scan results for JSON output (`--group-by`)
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use task_runner_detector::TaskRunner;
+
+use task_runner_detector::folder_display;
+
+/// What to group runners by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GroupBy {
+    /// The folder containing the config file
+    Folder,
+    /// The runner's display name
+    Runner,
+    /// The runner's language ecosystem (js, python, jvm, ...)
+    Ecosystem,
+    /// The git submodule the config file belongs to ("." for the superproject)
+    Submodule,
+}
+
+/// Group runners by key. Groups are sorted by key; runners keep their order.
+fn group_runners<'a>(
+    by: GroupBy,
+    runners: &'a [TaskRunner],
+    root: &Path,
+) -> BTreeMap<String, Vec<&'a TaskRunner>> {
+    let submodules = match by {
+        GroupBy::Submodule => submodule_paths(root),
+        _ => Vec::new(),
+    };
+
+    let mut groups: BTreeMap<String, Vec<&TaskRunner>> = BTreeMap::new();
+    for runner in runners {
+        let key = match by {
+            GroupBy::Folder => folder_display(&runner.config_path, root),
+            GroupBy::Runner => runner.runner_type.display_name().to_string(),
+            GroupBy::Ecosystem => runner.runner_type.category().display_name().to_string(),
+            GroupBy::Submodule => submodule_for(&runner.config_path, root, &submodules),
+        };
+        groups.entry(key).or_default().push(runner);
+    }
+    groups
+}
+
+/// Build `[{ "group": ..., "runners": [...] }]` JSON, serializing each runner
+/// with `runner_json`
+pub fn grouped_json(
+    by: GroupBy,
+    runners: &[TaskRunner],
+    root: &Path,
+    mut runner_json: impl FnMut(&TaskRunner) -> Value,
+) -> Value {
+    group_runners(by, runners, root)
+        .into_iter()
+        .map(|(group, runners)| {
+            let runners: Vec<Value> = runners.into_iter().map(&mut runner_json).collect();
+            json!({ "group": group, "runners": runners })
+        })
+        .collect()
+}
+
+/// Read submodule paths (relative to root) from `.gitmodules`
+fn submodule_paths(root: &Path) -> Vec<PathBuf> {
+    let Ok(content) = fs::read_to_string(root.join(".gitmodules")) else {
+        return Vec::new();
+    };
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "path").then(|| PathBuf::from(value.trim()))
+        })
+        .collect()
+}
+
+/// The submodule containing a config file, or "." for the superproject.
+/// The longest matching path wins so nested submodules are attributed correctly.
+fn submodule_for(config_path: &Path, root: &Path, submodules: &[PathBuf]) -> String {
+    let relative = config_path.strip_prefix(root).unwrap_or(config_path);
+    submodules
+        .iter()
+        .filter(|submodule| relative.starts_with(submodule))
+        .max_by_key(|submodule| submodule.components().count())
+        .map(|submodule| submodule.to_string_lossy().to_string())
+        .unwrap_or_else(|| ".".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use task_runner_detector::RunnerType;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_group_by_submodule() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(
+            root.join(".gitmodules"),
+            r#"[submodule "ui"]
+	path = libs/ui
+	url = https://example.com/ui.git
+[submodule "ui-icons"]
+	path = libs/ui/icons
+	url = https://example.com/icons.git
+"#,
+        )
+        .unwrap();
+
+        let runner = |path: &str| TaskRunner {
+            config_path: root.join(path),
+            runner_type: RunnerType::Npm,
+            tasks: Vec::new(),
+            ..Default::default()
+        };
+        let runners = vec![
+            runner("package.json"),
+            runner("libs/ui/package.json"),
+            runner("libs/ui/icons/package.json"),
+            runner("libs/uikit/package.json"),
+        ];
+
+        let groups = group_runners(GroupBy::Submodule, &runners, root);
+        let paths = |key: &str| -> Vec<PathBuf> {
+            groups[key]
+                .iter()
+                .map(|r| r.config_path.strip_prefix(root).unwrap().to_path_buf())
+                .collect()
+        };
+
+        assert_eq!(
+            groups.keys().collect::<Vec<_>>(),
+            vec![".", "libs/ui", "libs/ui/icons"]
+        );
+        assert_eq!(
+            paths("."),
+            vec![
+                PathBuf::from("package.json"),
+                PathBuf::from("libs/uikit/package.json")
+            ]
+        );
+        assert_eq!(
+            paths("libs/ui"),
+            vec![PathBuf::from("libs/ui/package.json")]
+        );
+    }
+
+    #[test]
+    fn test_group_by_ecosystem() {
+        let root = Path::new("/repo");
+        let runner = |path: &str, runner_type| TaskRunner {
+            config_path: root.join(path),
+            runner_type,
+            tasks: Vec::new(),
+            ..Default::default()
+        };
+        let runners = vec![
+            runner("web/package.json", RunnerType::Pnpm),
+            runner("Makefile", RunnerType::Make),
+            runner("api/pom.xml", RunnerType::Maven),
+            runner("deno.json", RunnerType::Deno),
+        ];
+
+        let groups = group_runners(GroupBy::Ecosystem, &runners, root);
+        let sizes: Vec<_> = groups.iter().map(|(k, v)| (k.as_str(), v.len())).collect();
+        assert_eq!(sizes, vec![("general", 1), ("js", 2), ("jvm", 1)]);
+    }
+}
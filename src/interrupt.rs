@@ -0,0 +1,39 @@
+//! Ctrl+C while a task runs
+//!
+//! The terminal delivers SIGINT to the whole foreground process group, so
+//! the running task gets it either way. Once a task has been started, `task`
+//! itself only records the interrupt instead of dying, so it can wait for the
+//! child, report it and exit with the conventional 130.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+
+/// Exit code for a run cut short by Ctrl+C (128 + SIGINT)
+pub const EXIT_CODE: i32 = 130;
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static INSTALL: Once = Once::new();
+
+/// Record Ctrl+C instead of exiting on it. Called before spawning tasks; the
+/// picker reads Ctrl+C as a key in raw mode, so it isn't affected.
+pub fn install() {
+    INSTALL.call_once(|| {
+        // Without the handler Ctrl+C still ends the task, just not as cleanly
+        ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::SeqCst)).ok();
+    });
+}
+
+/// Whether Ctrl+C was pressed since a task was first started
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Exit code to report for a finished task: 130 after Ctrl+C, whatever the
+/// task itself exited with, else its own code (1 if a signal ended it)
+pub fn exit_code(status: std::process::ExitStatus) -> i32 {
+    if interrupted() {
+        EXIT_CODE
+    } else {
+        status.code().unwrap_or(1)
+    }
+}
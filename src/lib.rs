@@ -16,17 +16,45 @@
 //!     }
 //! }
 //! ```
+//!
+//! Frontends can pull in everything commonly needed (task types, scan
+//! functions and options, folder labels and ordering) with
+//! `use task_runner_detector::prelude::*;`.
 
+#[cfg(feature = "archive")]
+mod archive;
+mod global;
+mod order;
 mod parsers;
+pub mod prelude;
+mod proto;
+mod readme;
 mod scanner;
+mod services;
+mod summary;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use thiserror::Error;
 
-pub use scanner::{scan, scan_streaming, scan_with_options, ScanOptions};
+#[cfg(feature = "archive")]
+pub use archive::scan_archive;
+pub use global::{global_tasks_path, load_global_tasks, GLOBAL_FOLDER};
+/// Re-exported so callers of [`scan_with_walker`] use a matching `ignore` version
+pub use ignore::WalkBuilder;
+pub use order::{folder_display, ordered_tasks};
+pub use scanner::{
+    parse_file, scan, scan_report, scan_streaming, scan_streaming_cancellable,
+    scan_streaming_items, scan_with_options, scan_with_progress, scan_with_walker, ScanHandle,
+    ScanItem, ScanOptions, ScanReport, ScanStats, DEFAULT_PARSE_TIMEOUT, DEFAULT_SCRIPT_PATTERNS,
+};
+pub use summary::{summarize, ScanSummary};
 
-/// The type of task runner detected
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+/// The type of task runner detected. Defaults to [`RunnerType::Script`],
+/// the least specific one.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum RunnerType {
     Npm,
@@ -44,9 +72,65 @@ pub enum RunnerType {
     Deno,
     Maven,
     DotNet,
+    Snakemake,
+    Nu,
+    PreCommit,
+    Brew,
+    Devcontainer,
+    Rake,
+    Gradle,
+    Composer,
+    Foreman,
+    GithubActions,
+    Rebar3,
+    Mask,
+    Dune,
+    Hatch,
+    Nx,
+    Melos,
+    #[default]
+    Script,
 }
 
 impl RunnerType {
+    /// Every runner type, in declaration order
+    pub fn all() -> &'static [RunnerType] {
+        &[
+            RunnerType::Npm,
+            RunnerType::Bun,
+            RunnerType::Yarn,
+            RunnerType::Pnpm,
+            RunnerType::Make,
+            RunnerType::Cargo,
+            RunnerType::Flutter,
+            RunnerType::Dart,
+            RunnerType::Turbo,
+            RunnerType::Poetry,
+            RunnerType::Pdm,
+            RunnerType::Just,
+            RunnerType::Deno,
+            RunnerType::Maven,
+            RunnerType::DotNet,
+            RunnerType::Snakemake,
+            RunnerType::Nu,
+            RunnerType::PreCommit,
+            RunnerType::Brew,
+            RunnerType::Devcontainer,
+            RunnerType::Rake,
+            RunnerType::Gradle,
+            RunnerType::Composer,
+            RunnerType::Foreman,
+            RunnerType::GithubActions,
+            RunnerType::Rebar3,
+            RunnerType::Mask,
+            RunnerType::Dune,
+            RunnerType::Hatch,
+            RunnerType::Nx,
+            RunnerType::Melos,
+            RunnerType::Script,
+        ]
+    }
+
     /// Returns a human-readable display name for the runner type
     pub fn display_name(&self) -> &'static str {
         match self {
@@ -65,6 +149,23 @@ impl RunnerType {
             RunnerType::Deno => "deno",
             RunnerType::Maven => "mvn",
             RunnerType::DotNet => "dotnet",
+            RunnerType::Snakemake => "snakemake",
+            RunnerType::Nu => "nu",
+            RunnerType::PreCommit => "pre-commit",
+            RunnerType::Brew => "brew",
+            RunnerType::Devcontainer => "devcontainer",
+            RunnerType::Rake => "rake",
+            RunnerType::Gradle => "gradle",
+            RunnerType::Composer => "composer",
+            RunnerType::Foreman => "foreman",
+            RunnerType::GithubActions => "github-actions",
+            RunnerType::Rebar3 => "rebar3",
+            RunnerType::Mask => "mask",
+            RunnerType::Dune => "dune",
+            RunnerType::Hatch => "hatch",
+            RunnerType::Nx => "nx",
+            RunnerType::Melos => "melos",
+            RunnerType::Script => "script",
         }
     }
 
@@ -86,27 +187,139 @@ impl RunnerType {
             RunnerType::Deno => "🦕",
             RunnerType::Maven => "🪶",
             RunnerType::DotNet => "🟣",
+            RunnerType::Snakemake => "🐍",
+            RunnerType::Nu => "🐚",
+            RunnerType::PreCommit => "🪝",
+            RunnerType::Brew => "🍺",
+            RunnerType::Devcontainer => "🐳",
+            RunnerType::Rake => "💎",
+            RunnerType::Gradle => "🐘",
+            RunnerType::Composer => "🎼",
+            RunnerType::Foreman => "🏭",
+            RunnerType::GithubActions => "🎬",
+            RunnerType::Rebar3 => "🦬",
+            RunnerType::Mask => "🎭",
+            RunnerType::Dune => "🐪",
+            RunnerType::Hatch => "🐣",
+            RunnerType::Nx => "🔷",
+            RunnerType::Melos => "🧰",
+            RunnerType::Script => "📄",
         }
     }
 
+    /// The executable that runs tasks of this runner type
+    pub fn binary(&self) -> &'static str {
+        match self {
+            RunnerType::Npm => "npm",
+            RunnerType::Bun => "bun",
+            RunnerType::Yarn => "yarn",
+            RunnerType::Pnpm => "pnpm",
+            RunnerType::Make => "make",
+            RunnerType::Cargo => "cargo",
+            RunnerType::Flutter => "flutter",
+            RunnerType::Dart => "dart",
+            RunnerType::Turbo => "turbo",
+            RunnerType::Poetry => "poetry",
+            RunnerType::Pdm => "pdm",
+            RunnerType::Just => "just",
+            RunnerType::Deno => "deno",
+            RunnerType::Maven => "mvn",
+            RunnerType::DotNet => "dotnet",
+            RunnerType::Snakemake => "snakemake",
+            RunnerType::Nu => "nu",
+            RunnerType::PreCommit => "pre-commit",
+            RunnerType::Brew => "brew",
+            RunnerType::Devcontainer => "sh",
+            RunnerType::Rake => "rake",
+            RunnerType::Gradle => "gradle",
+            RunnerType::Composer => "composer",
+            RunnerType::Foreman => "foreman",
+            RunnerType::GithubActions => "act",
+            RunnerType::Rebar3 => "rebar3",
+            RunnerType::Mask => "mask",
+            RunnerType::Dune => "dune",
+            RunnerType::Hatch => "hatch",
+            RunnerType::Nx => "nx",
+            RunnerType::Melos => "melos",
+            RunnerType::Script => "sh",
+        }
+    }
+
+    /// Check whether this runner's executable is on PATH
+    pub fn is_installed(&self) -> bool {
+        is_installed(self.binary())
+    }
+
     /// Get a suggested terminal color for this runner type
     pub fn color_code(&self) -> u8 {
         match self {
-            RunnerType::Npm => 1,     // Red
-            RunnerType::Bun => 3,     // Yellow
-            RunnerType::Yarn => 4,    // Blue
-            RunnerType::Pnpm => 3,    // Yellow
-            RunnerType::Make => 2,    // Green
-            RunnerType::Cargo => 1,   // Red
-            RunnerType::Flutter => 6, // Cyan
-            RunnerType::Dart => 6,    // Cyan
-            RunnerType::Turbo => 5,   // Magenta
-            RunnerType::Poetry => 2,  // Green
-            RunnerType::Pdm => 2,     // Green
-            RunnerType::Just => 3,    // Yellow
-            RunnerType::Deno => 2,    // Green
-            RunnerType::Maven => 1,   // Red
-            RunnerType::DotNet => 5,  // Magenta
+            RunnerType::Npm => 1,           // Red
+            RunnerType::Bun => 3,           // Yellow
+            RunnerType::Yarn => 4,          // Blue
+            RunnerType::Pnpm => 3,          // Yellow
+            RunnerType::Make => 2,          // Green
+            RunnerType::Cargo => 1,         // Red
+            RunnerType::Flutter => 6,       // Cyan
+            RunnerType::Dart => 6,          // Cyan
+            RunnerType::Turbo => 5,         // Magenta
+            RunnerType::Poetry => 2,        // Green
+            RunnerType::Pdm => 2,           // Green
+            RunnerType::Just => 3,          // Yellow
+            RunnerType::Deno => 2,          // Green
+            RunnerType::Maven => 1,         // Red
+            RunnerType::DotNet => 5,        // Magenta
+            RunnerType::Snakemake => 2,     // Green
+            RunnerType::Nu => 2,            // Green
+            RunnerType::PreCommit => 3,     // Yellow
+            RunnerType::Brew => 3,          // Yellow
+            RunnerType::Devcontainer => 4,  // Blue
+            RunnerType::Rake => 1,          // Red
+            RunnerType::Gradle => 6,        // Cyan
+            RunnerType::Composer => 5,      // Magenta
+            RunnerType::Foreman => 4,       // Blue
+            RunnerType::GithubActions => 5, // Magenta
+            RunnerType::Rebar3 => 1,        // Red
+            RunnerType::Mask => 6,          // Cyan
+            RunnerType::Dune => 3,          // Yellow
+            RunnerType::Hatch => 3,         // Yellow
+            RunnerType::Nx => 4,            // Blue
+            RunnerType::Melos => 6,         // Cyan
+            RunnerType::Script => 7,        // White
+        }
+    }
+
+    /// The language ecosystem this runner belongs to. Runners that work with
+    /// any language (make, just, pre-commit, ...) are [`Ecosystem::General`].
+    pub fn category(&self) -> Ecosystem {
+        match self {
+            RunnerType::Npm
+            | RunnerType::Bun
+            | RunnerType::Yarn
+            | RunnerType::Pnpm
+            | RunnerType::Turbo
+            | RunnerType::Nx
+            | RunnerType::Deno => Ecosystem::JavaScript,
+            RunnerType::Poetry | RunnerType::Pdm | RunnerType::Hatch | RunnerType::Snakemake => {
+                Ecosystem::Python
+            }
+            RunnerType::Maven | RunnerType::Gradle => Ecosystem::Jvm,
+            RunnerType::Cargo => Ecosystem::Rust,
+            RunnerType::Flutter | RunnerType::Dart | RunnerType::Melos => Ecosystem::Dart,
+            RunnerType::DotNet => Ecosystem::DotNet,
+            RunnerType::Rake => Ecosystem::Ruby,
+            RunnerType::Composer => Ecosystem::Php,
+            RunnerType::Rebar3 => Ecosystem::Erlang,
+            RunnerType::Dune => Ecosystem::OCaml,
+            RunnerType::Make
+            | RunnerType::Just
+            | RunnerType::Nu
+            | RunnerType::PreCommit
+            | RunnerType::Brew
+            | RunnerType::Devcontainer
+            | RunnerType::Foreman
+            | RunnerType::GithubActions
+            | RunnerType::Mask
+            | RunnerType::Script => Ecosystem::General,
         }
     }
 }
@@ -117,32 +330,230 @@ impl std::fmt::Display for RunnerType {
     }
 }
 
+/// Error returned when a string names no runner type
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("unknown runner type: {0}")]
+pub struct RunnerTypeParseError(pub String);
+
+impl std::str::FromStr for RunnerType {
+    type Err = RunnerTypeParseError;
+
+    /// Parse a runner from its display name (`cargo`, `mvn`, `pre-commit`),
+    /// ignoring case. A few common aliases are accepted too (`maven`, `.net`,
+    /// `precommit`, `homebrew`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase();
+        let alias = match name.as_str() {
+            "maven" => Some(RunnerType::Maven),
+            ".net" => Some(RunnerType::DotNet),
+            "precommit" => Some(RunnerType::PreCommit),
+            "homebrew" => Some(RunnerType::Brew),
+            _ => None,
+        };
+        alias
+            .or_else(|| {
+                RunnerType::all()
+                    .iter()
+                    .copied()
+                    .find(|runner_type| runner_type.display_name() == name)
+            })
+            .ok_or_else(|| RunnerTypeParseError(s.to_string()))
+    }
+}
+
+/// A language ecosystem, grouping related runner types (see [`RunnerType::category`])
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Ecosystem {
+    #[serde(rename = "js")]
+    JavaScript,
+    Python,
+    Jvm,
+    Rust,
+    Dart,
+    DotNet,
+    Ruby,
+    Php,
+    Erlang,
+    OCaml,
+    /// Language-agnostic runners
+    General,
+}
+
+impl Ecosystem {
+    /// Every ecosystem, in declaration order
+    pub fn all() -> &'static [Ecosystem] {
+        &[
+            Ecosystem::JavaScript,
+            Ecosystem::Python,
+            Ecosystem::Jvm,
+            Ecosystem::Rust,
+            Ecosystem::Dart,
+            Ecosystem::DotNet,
+            Ecosystem::Ruby,
+            Ecosystem::Php,
+            Ecosystem::Erlang,
+            Ecosystem::OCaml,
+            Ecosystem::General,
+        ]
+    }
+
+    /// Returns a short display name for the ecosystem
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Ecosystem::JavaScript => "js",
+            Ecosystem::Python => "python",
+            Ecosystem::Jvm => "jvm",
+            Ecosystem::Rust => "rust",
+            Ecosystem::Dart => "dart",
+            Ecosystem::DotNet => "dotnet",
+            Ecosystem::Ruby => "ruby",
+            Ecosystem::Php => "php",
+            Ecosystem::Erlang => "erlang",
+            Ecosystem::OCaml => "ocaml",
+            Ecosystem::General => "general",
+        }
+    }
+
+    /// The runner types in this ecosystem, in declaration order
+    pub fn runner_types(&self) -> impl Iterator<Item = RunnerType> + '_ {
+        RunnerType::all()
+            .iter()
+            .copied()
+            .filter(move |runner_type| runner_type.category() == *self)
+    }
+}
+
+impl std::fmt::Display for Ecosystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+/// Error returned when a string names no ecosystem
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("unknown ecosystem: {0}")]
+pub struct EcosystemParseError(pub String);
+
+impl std::str::FromStr for Ecosystem {
+    type Err = EcosystemParseError;
+
+    /// Parse an ecosystem from its display name (`js`, `jvm`), ignoring case.
+    /// A few common aliases are accepted too (`javascript`, `node`, `java`,
+    /// `.net`, `beam`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase();
+        let alias = match name.as_str() {
+            "javascript" | "node" | "typescript" | "ts" => Some(Ecosystem::JavaScript),
+            "java" | "kotlin" => Some(Ecosystem::Jvm),
+            ".net" => Some(Ecosystem::DotNet),
+            "beam" | "elixir" => Some(Ecosystem::Erlang),
+            _ => None,
+        };
+        alias
+            .or_else(|| {
+                Ecosystem::all()
+                    .iter()
+                    .copied()
+                    .find(|ecosystem| ecosystem.display_name() == name)
+            })
+            .ok_or_else(|| EcosystemParseError(s.to_string()))
+    }
+}
+
 /// A single task that can be run
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+///
+/// `name` identifies the task, `command` is how to invoke it and `script` is
+/// what it runs: for a package.json script `"build": "tsc"` they are `build`,
+/// `npm run build` and `tsc`. Tasks a runner provides without declaring a
+/// body (Maven phases, cargo defaults) have no `script`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Task {
-    /// The name of the task (e.g., "build", "test", "dev")
+    /// The task identifier as declared or known to the runner (e.g., "build", "test", "dev")
     pub name: String,
-    /// The full command to run (e.g., "npm run build", "make test")
+    /// The full invocation, run from the config file's folder (e.g., "npm run build", "make test")
     pub command: String,
     /// Optional description of what the task does
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    /// The actual script content (e.g., the shell command in package.json scripts)
+    /// The body the config file declares for the task, if any (e.g., the shell
+    /// command of a package.json script, a Makefile recipe, a Python entry point)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub script: Option<String>,
+    /// Extra metadata declared by richer config formats
+    #[serde(default, skip_serializing_if = "TaskMetadata::is_empty")]
+    pub metadata: TaskMetadata,
+}
+
+/// Per-task metadata that only some config formats declare
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TaskMetadata {
+    /// Runs across all workspaces of a monorepo from its root
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub workspace: bool,
+    /// Tasks that run first, as the config declares them (Turborepo
+    /// `dependsOn`, Makefile prerequisites that are themselves targets)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// An event hook the tool runs on its own (Composer `post-install-cmd`)
+    /// rather than a task meant to be run by hand
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hook: bool,
+}
+
+impl TaskMetadata {
+    /// Returns true if no metadata was declared
+    pub fn is_empty(&self) -> bool {
+        !self.workspace && self.depends_on.is_empty() && !self.hook
+    }
 }
 
 /// A task runner configuration file with its discovered tasks
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct TaskRunner {
     /// Path to the config file (e.g., "apps/mobile/pubspec.yaml")
     pub config_path: PathBuf,
     /// The type of task runner
     pub runner_type: RunnerType,
+    /// One-line summary of the project, if the config file declares one
+    /// (e.g. package.json or Cargo.toml `description`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The npm/yarn/pnpm workspace this package is a member of: the workspace
+    /// root's package name, or its folder name if it has none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
     /// List of tasks discovered in the config file
     pub tasks: Vec<Task>,
 }
 
+/// Check whether an executable is available on PATH
+pub fn is_installed(program: &str) -> bool {
+    let Some(paths) = std::env::var_os("PATH") else {
+        return false;
+    };
+
+    // On Windows, executables are found through their PATHEXT extensions
+    let extensions: Vec<String> = if cfg!(windows) {
+        std::env::var("PATHEXT")
+            .unwrap_or_else(|_| ".EXE;.CMD;.BAT;.COM".to_string())
+            .split(';')
+            .map(|ext| ext.to_string())
+            .chain(std::iter::once(String::new()))
+            .collect()
+    } else {
+        vec![String::new()]
+    };
+
+    std::env::split_paths(&paths).any(|dir| {
+        extensions
+            .iter()
+            .any(|ext| dir.join(format!("{}{}", program, ext)).is_file())
+    })
+}
+
 /// Errors that can occur during scanning
 #[derive(Error, Debug)]
 pub enum ScanError {
@@ -154,7 +565,127 @@ pub enum ScanError {
 
     #[error("Walk error: {0}")]
     WalkError(#[from] ignore::Error),
+
+    #[error("Parsing {path} took longer than {timeout:?}")]
+    ParseTimeout { path: PathBuf, timeout: Duration },
+}
+
+impl ScanError {
+    /// The config file the error is about, if it's about one
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            ScanError::ParseError { path, .. } | ScanError::ParseTimeout { path, .. } => Some(path),
+            ScanError::Io(_) | ScanError::WalkError(_) => None,
+        }
+    }
 }
 
 /// Result type for scan operations
 pub type ScanResult<T> = Result<T, ScanError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runner_type_from_str_round_trips() {
+        for &runner_type in RunnerType::all() {
+            assert_eq!(runner_type.display_name().parse(), Ok(runner_type));
+        }
+        assert_eq!("Maven".parse(), Ok(RunnerType::Maven));
+        assert_eq!("dotnet".parse(), Ok(RunnerType::DotNet));
+        assert_eq!(
+            "ant".parse::<RunnerType>(),
+            Err(RunnerTypeParseError("ant".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_runner_categories() {
+        assert_eq!(RunnerType::Pnpm.category(), Ecosystem::JavaScript);
+        assert_eq!(RunnerType::Poetry.category(), Ecosystem::Python);
+        assert_eq!(RunnerType::Gradle.category(), Ecosystem::Jvm);
+        assert_eq!(RunnerType::Make.category(), Ecosystem::General);
+
+        // Every runner type is in exactly one ecosystem
+        let count: usize = Ecosystem::all()
+            .iter()
+            .map(|e| e.runner_types().count())
+            .sum();
+        assert_eq!(count, RunnerType::all().len());
+
+        for &ecosystem in Ecosystem::all() {
+            assert_eq!(ecosystem.display_name().parse(), Ok(ecosystem));
+        }
+        assert_eq!("JavaScript".parse(), Ok(Ecosystem::JavaScript));
+        assert_eq!(
+            serde_json::to_value(Ecosystem::JavaScript).unwrap(),
+            serde_json::json!("js")
+        );
+        assert!("cobol".parse::<Ecosystem>().is_err());
+    }
+
+    #[test]
+    fn test_task_json_contract() {
+        let dir = tempfile::TempDir::new().unwrap();
+        // (config file, contents, task, expected name/command/script)
+        let cases = [
+            (
+                "package.json",
+                r#"{"scripts": {"build": "tsc"}}"#,
+                "build",
+                serde_json::json!({"name": "build", "command": "npm run build", "script": "tsc"}),
+            ),
+            (
+                "Makefile",
+                "test:\n\tcargo test\n",
+                "test",
+                serde_json::json!({"name": "test", "command": "make test", "script": "cargo test"}),
+            ),
+            (
+                "justfile",
+                "deploy env:\n    ./deploy.sh {{env}}\n",
+                "deploy",
+                serde_json::json!({
+                    "name": "deploy",
+                    "command": "just deploy <env>",
+                    "script": "./deploy.sh {{env}}"
+                }),
+            ),
+            (
+                "Cargo.toml",
+                "[package]\nname = \"app\"\n",
+                "build",
+                serde_json::json!({"name": "build", "command": "cargo build"}),
+            ),
+            (
+                "pom.xml",
+                "<project><artifactId>app</artifactId></project>",
+                "package",
+                serde_json::json!({"name": "package", "command": "mvn package"}),
+            ),
+            (
+                "pyproject.toml",
+                "[project.scripts]\nserve = \"app.cli:main\"\n",
+                "serve",
+                serde_json::json!({
+                    "name": "serve",
+                    "command": "serve",
+                    "script": "app.cli:main"
+                }),
+            ),
+        ];
+
+        for (file, contents, task_name, expected) in cases {
+            let path = dir.path().join(file);
+            std::fs::write(&path, contents).unwrap();
+            let runner = parse_file(&path).unwrap().unwrap();
+            let task = runner.tasks.iter().find(|t| t.name == task_name).unwrap();
+
+            let mut json = serde_json::to_value(task).unwrap();
+            let fields = json.as_object_mut().unwrap();
+            fields.retain(|key, _| matches!(key.as_str(), "name" | "command" | "script"));
+            assert_eq!(json, expected, "{}", file);
+        }
+    }
+}
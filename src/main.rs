@@ -6,28 +6,47 @@
 //!   task -j                 # JSON output
 //!   task -s                 # Streaming NDJSON output
 //!   task -j -q "query"      # Filter JSON output with fuzzy search
+//!   task -e "query"         # Open the best match directly in Edit mode
+//!   task run-all "pattern"  # Run every task named like the pattern, in order
+//!   task --generate makefile  # Emit a Makefile wrapping every task
+//!   task --first "query"    # Print the best match's command
+//!   task --watch-run "query"  # Run the best match, re-running on file changes
 
+use std::collections::HashMap;
 use std::env;
-use std::io::{stdout, Write};
+use std::io::{stderr, stdout, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::mpsc;
 use std::sync::{Arc, RwLock};
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use console::style;
-use nucleo::pattern::{CaseMatching, Normalization, Pattern};
 use nucleo::{Config, Matcher, Utf32Str};
+use serde_json::json;
 
 use task_runner_detector::{
-    scan_streaming, scan_with_options, RunnerType, ScanOptions, Task, TaskRunner,
+    folder_display, ordered_tasks, scan_report, scan_streaming, scan_streaming_items,
+    scan_with_options, Ecosystem, RunnerType, ScanError, ScanItem, ScanOptions, Task, TaskRunner,
 };
 
+mod availability;
 mod backend;
+mod clipboard;
+mod completions;
+mod generate;
+mod grouping;
+mod interrupt;
+mod matching;
 mod messages;
+mod parallel;
+mod pipeline;
+mod plan;
 mod registry;
 mod render;
 mod ui;
+mod versions;
+mod watch;
 
 #[derive(Parser)]
 #[command(name = "task")]
@@ -42,139 +61,805 @@ struct Cli {
     #[arg(short = 's', long)]
     json_stream: bool,
 
-    /// Filter tasks using fuzzy search (works with --json and --json-stream)
+    /// Include config files that failed to parse in JSON output: --json wraps
+    /// its output as {"runners", "errors"}, --json-stream adds {"error"} lines
+    #[arg(long)]
+    report_errors: bool,
+
+    /// Output a flat JSON array with one object per task, carrying its runner
+    /// type, config path and working directory
+    #[arg(long, conflicts_with_all = ["json", "json_stream"])]
+    json_flat: bool,
+
+    /// Filter tasks using fuzzy search (works with --json, --json-stream, --json-flat and --generate)
     #[arg(short = 'q', long)]
     query: Option<String>,
 
+    /// Only list tasks of this runner type (repeatable, e.g. npm, cargo)
+    #[arg(long = "runner", value_name = "TYPE")]
+    runners: Vec<RunnerType>,
+
+    /// Only list tasks of runners in this ecosystem (repeatable, e.g. js, python, jvm)
+    #[arg(long = "ecosystem", value_name = "NAME")]
+    ecosystems: Vec<Ecosystem>,
+
+    /// Only look this many folders deep (0 = just the config files in the root)
+    #[arg(long, value_name = "N")]
+    depth: Option<usize>,
+
+    /// Stream tasks from the root and its immediate subfolders before deeper
+    /// ones (slightly slower overall)
+    #[arg(long)]
+    shallow_first: bool,
+
+    /// Order non-interactive output by task name, folder or runner (default: scan order)
+    #[arg(long, value_name = "ORDER")]
+    sort: Option<pipeline::SortOrder>,
+
+    /// Group --json output by folder, runner or git submodule
+    #[arg(long, value_name = "GROUP", requires = "json")]
+    group_by: Option<grouping::GroupBy>,
+
+    /// Add `available: bool` to each task in JSON output (looks up runners on PATH)
+    #[arg(long)]
+    check_availability: bool,
+
+    /// Add `package_manager_version` to npm/yarn/pnpm/bun runners in JSON output,
+    /// from package.json's `packageManager` field
+    #[arg(long)]
+    package_manager_version: bool,
+
+    /// With --package-manager-version, run `<manager> --version` when none is pinned
+    #[arg(long, requires = "package_manager_version")]
+    probe_version: bool,
+
+    /// Print the discovered tasks in another format (a Makefile or a VS Code
+    /// tasks.json) and exit
+    #[arg(long, visible_alias = "export", value_name = "FORMAT", conflicts_with_all = ["json", "json_stream", "json_flat", "edit"])]
+    generate: Option<generate::GenerateFormat>,
+
+    /// List each discovered config (runner, folder and task count) instead of its tasks
+    #[arg(long, conflicts_with_all = ["json", "json_stream", "json_flat", "generate"])]
+    projects: bool,
+
+    /// Open the picker with the best match for QUERY already in Edit mode
+    #[arg(short = 'e', long, value_name = "QUERY", conflicts_with_all = ["json", "json_stream", "json_flat"])]
+    edit: Option<String>,
+
+    /// Print the command of the best match for QUERY and exit (exit code 1 if nothing matches)
+    #[arg(long, value_name = "QUERY", conflicts_with_all = ["json", "json_stream", "json_flat", "generate", "edit"])]
+    first: Option<String>,
+
+    /// Print a completion script offering this folder's task names for --exec
+    /// (e.g. `source <(task --complete bash)`)
+    #[arg(long, value_name = "SHELL", conflicts_with_all = ["json", "json_stream", "json_flat", "generate", "edit", "first", "projects"])]
+    complete: Option<completions::Shell>,
+
+    /// Print tab-separated `runner folder name command` lines, one per task
+    #[arg(long, conflicts_with_all = ["json", "json_stream", "json_flat", "generate", "edit", "first", "projects"])]
+    list: bool,
+
+    /// Run the task named NAME (`build`, or `<folder> <name>` / `<runner> <folder> <name>`
+    /// as in --list), falling back to the best fuzzy match when no task has that name.
+    /// Exits with 1 if nothing matches and 2 if several tasks do; otherwise with the
+    /// task's exit code
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["json", "json_stream", "json_flat", "generate", "edit", "first", "projects", "list"])]
+    exec: Option<String>,
+
+    /// Print the commands the best match for QUERY runs, its declared
+    /// dependencies (Turborepo `dependsOn`, Makefile prerequisites) first,
+    /// without running anything. Exits with 1 on no match or a dependency cycle
+    #[arg(long, value_name = "QUERY", conflicts_with_all = ["json", "json_stream", "json_flat", "generate", "edit", "first", "projects", "list", "exec"])]
+    plan: Option<String>,
+
+    /// Run the best match for QUERY and re-run it whenever files change
+    #[arg(long, value_name = "QUERY", conflicts_with_all = ["json", "json_stream", "json_flat", "generate", "edit", "first"])]
+    watch_run: Option<String>,
+
+    /// Path to watch with --watch-run (repeatable, defaults to the task's folder)
+    #[arg(long = "watch", value_name = "PATH", requires = "watch_run")]
+    watch_paths: Vec<PathBuf>,
+
+    /// Glob for paths whose changes --watch-run ignores, on top of gitignored paths (repeatable)
+    #[arg(long, value_name = "GLOB", requires = "watch_run", value_parser = glob_arg)]
+    watch_ignore: Vec<String>,
+
+    /// High-contrast output: no dim text and a plain `>` selection marker
+    #[arg(long)]
+    accessible: bool,
+
+    /// Don't show emoji icons for folders and runners
+    #[arg(long)]
+    no_emoji: bool,
+
+    /// Match the query case-sensitively
+    #[arg(long)]
+    case_sensitive: bool,
+
+    /// Don't normalize accented characters when matching (é no longer matches e)
+    #[arg(long)]
+    no_normalize: bool,
+
+    /// List tasks in the order they're declared in their config file instead of by name
+    #[arg(long)]
+    preserve_order: bool,
+
+    /// Within a folder, list these runners first, in this order (e.g. cargo,make)
+    #[arg(long, value_name = "RUNNERS", value_delimiter = ',')]
+    runner_priority: Vec<RunnerType>,
+
+    /// Start with folders collapsed; expand them with → or Enter, collapse with ←
+    #[arg(long)]
+    group_collapse: bool,
+
+    /// Show each folder's absolute path next to its name in the picker
+    #[arg(long)]
+    abs_paths: bool,
+
+    /// Navigate the picker with j/k, g/G and Ctrl+D/Ctrl+U; press / to search
+    #[arg(long, env = "TASK_VIM_KEYS")]
+    vim: bool,
+
     /// Don't respect .gitignore and scan all files
     #[arg(short = 'i', long)]
     no_ignore: bool,
 
+    /// Also list ad-hoc script files (run.sh, dev.sh, ...) as tasks
+    #[arg(long)]
+    scripts: bool,
+
+    /// Filename glob for --scripts (repeatable, replaces the default list)
+    #[arg(long = "script-pattern", value_name = "GLOB", requires = "scripts", value_parser = glob_arg)]
+    script_patterns: Vec<String>,
+
+    /// Hide tasks whose name matches this glob, in every runner (repeatable)
+    #[arg(long = "exclude-task", value_name = "GLOB", value_parser = glob_arg)]
+    exclude_tasks: Vec<String>,
+
+    /// Only parse config files whose path relative to the root matches this
+    /// glob, e.g. 'apps/*/package.json' (repeatable)
+    #[arg(long = "only", value_name = "GLOB", value_parser = glob_arg)]
+    only_paths: Vec<String>,
+
+    /// Infer lint/format tasks from ESLint/Prettier/Biome configs that have no script
+    #[arg(long)]
+    implied_tasks: bool,
+
+    /// List Makefile targets that just call another runner as that command
+    #[arg(long)]
+    flatten_make: bool,
+
+    /// Fill missing task descriptions from a task table in TASKS.md / README.md
+    #[arg(long)]
+    readme_descriptions: bool,
+
+    /// Run commands through `proto run` when a .prototools above them pins the tool
+    #[arg(long)]
+    proto: bool,
+
+    /// Also list event hooks tools run on their own (Composer `post-install-cmd`, ...)
+    #[arg(long)]
+    all: bool,
+
+    /// Note in descriptions when a task likely needs a running service
+    /// (e2e/integration tests, dependencies that start docker compose)
+    #[arg(long)]
+    service_notes: bool,
+
+    /// Also list your global tasks (`name = "command"` entries in
+    /// ~/.config/task-runner/tasks.toml) under a "global" folder
+    #[arg(long)]
+    global: bool,
+
+    /// When to color output: auto (only on a terminal, off with NO_COLOR), always or never
+    #[arg(long, value_name = "WHEN", value_enum, default_value_t = ColorChoice::Auto, global = true)]
+    color: ColorChoice,
+
     /// Directory to scan (defaults to current directory)
     #[arg(value_name = "PATH")]
     path: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
 }
 
-/// Get folder key from a config path relative to root
-fn folder_key(config_path: &Path, root: &Path) -> String {
-    let relative = config_path.strip_prefix(root).unwrap_or(config_path);
-    let path_str = relative.to_string_lossy();
+/// When to color non-interactive output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
 
-    if !path_str.contains('/') && !path_str.contains('\\') {
-        ".".to_string()
-    } else {
-        relative
-            .parent()
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_else(|| ".".to_string())
+impl ColorChoice {
+    /// Whether to color a stream, given whether it's a terminal and the value of NO_COLOR
+    fn enabled(self, is_terminal: bool, no_color: Option<&str>) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => is_terminal && no_color.map_or(true, str::is_empty),
+        }
+    }
+
+    /// Turn styling of stdout and stderr on or off
+    fn apply(self) {
+        let no_color = env::var("NO_COLOR").ok();
+        console::set_colors_enabled(self.enabled(stdout().is_terminal(), no_color.as_deref()));
+        console::set_colors_enabled_stderr(
+            self.enabled(stderr().is_terminal(), no_color.as_deref()),
+        );
     }
 }
 
-/// Filter a single runner's tasks by query
-fn filter_runner_by_query(
-    runner: &TaskRunner,
-    pattern: Option<&Pattern>,
-    matcher: &mut Matcher,
+#[derive(Subcommand)]
+enum Commands {
+    /// Run every task named PATTERN (one after another unless --parallel), ordered by folder then task name
+    RunAll {
+        /// Task name, or a glob over task names (`lint*`, `test:*`), selecting the tasks to run
+        pattern: String,
+
+        /// Run the matched tasks without asking first (required when stdin isn't a terminal)
+        #[arg(long, short = 'y')]
+        yes: bool,
+
+        /// Keep going after a task fails instead of stopping
+        #[arg(long)]
+        continue_on_error: bool,
+
+        /// Run the tasks concurrently, with each output line prefixed by its task
+        #[arg(long)]
+        parallel: bool,
+
+        /// Maximum number of tasks running at once with --parallel (default: one per CPU)
+        #[arg(long, value_name = "N", requires = "parallel")]
+        jobs: Option<usize>,
+
+        /// Directory to scan (defaults to current directory)
+        #[arg(value_name = "PATH")]
+        path: Option<PathBuf>,
+    },
+}
+
+/// The highest-scoring task for a query. Ties go to the task that comes
+/// first in run-all order so the result doesn't depend on scan order.
+fn best_match<'a>(
+    runners: &'a [TaskRunner],
+    query: &str,
+    match_options: matching::MatchOptions,
     root: &Path,
-) -> Option<TaskRunner> {
-    let Some(pattern) = pattern else {
-        return Some(runner.clone());
-    };
+) -> Option<(&'a TaskRunner, &'a Task)> {
+    top_matches(runners, query, match_options, root)
+        .into_iter()
+        .next()
+}
 
-    let folder = folder_key(&runner.config_path, root);
-    let matching_tasks: Vec<Task> = runner
-        .tasks
+/// Every task sharing the best fuzzy score for `query`, in run-all order
+fn top_matches<'a>(
+    runners: &'a [TaskRunner],
+    query: &str,
+    match_options: matching::MatchOptions,
+    root: &Path,
+) -> Vec<(&'a TaskRunner, &'a Task)> {
+    let pattern = match_options.pattern(query);
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let mut best_score = 0;
+    let mut best = Vec::new();
+
+    for (runner, task) in ordered_tasks(runners, root) {
+        let search_text = matching::search_text(
+            &folder_display(&runner.config_path, root),
+            &task.command,
+            runner.runner_type,
+        );
+        let mut buf = Vec::new();
+        let haystack = Utf32Str::new(&search_text, &mut buf);
+        if let Some(score) = pattern.score(haystack, &mut matcher) {
+            if best.is_empty() || score > best_score {
+                best_score = score;
+                best.clear();
+            }
+            if score == best_score {
+                best.push((runner, task));
+            }
+        }
+    }
+
+    best
+}
+
+/// The names `--exec` accepts for a task, least specific first: its name,
+/// `<folder> <name>` and `<runner> <folder> <name>` (the `--list` columns)
+fn exec_names(runner: &TaskRunner, task: &Task, root: &Path) -> [String; 3] {
+    let in_folder = format!(
+        "{} {}",
+        folder_display(&runner.config_path, root),
+        task.name
+    );
+    let qualified = format!("{} {}", runner.runner_type.display_name(), in_folder);
+    [task.name.clone(), in_folder, qualified]
+}
+
+/// The tasks `--exec NAME` refers to: every task NAME names exactly (see
+/// [`exec_names`]), or the best fuzzy matches if no task has that name
+fn resolve_exec<'a>(
+    runners: &'a [TaskRunner],
+    name: &str,
+    match_options: matching::MatchOptions,
+    root: &Path,
+) -> Vec<(&'a TaskRunner, &'a Task)> {
+    let named: Vec<_> = ordered_tasks(runners, root)
+        .into_iter()
+        .filter(|(runner, task)| exec_names(runner, task, root).iter().any(|n| n == name))
+        .collect();
+    if named.is_empty() {
+        top_matches(runners, name, match_options, root)
+    } else {
+        named
+    }
+}
+
+/// Completion candidates for `--exec`: for every task, the shortest of its
+/// [`exec_names`] that no other task shares, so each one resolves to exactly
+/// that task. Tasks identical in all three are left out.
+fn exec_completions(runners: &[TaskRunner], root: &Path) -> Vec<String> {
+    let names: Vec<[String; 3]> = ordered_tasks(runners, root)
+        .into_iter()
+        .map(|(runner, task)| exec_names(runner, task, root))
+        .collect();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for name in names.iter().flatten() {
+        *counts.entry(name).or_default() += 1;
+    }
+    names
         .iter()
-        .filter(|task| {
-            let search_text = format!("{} {}", folder, task.command);
-            let mut buf = Vec::new();
-            let haystack = Utf32Str::new(&search_text, &mut buf);
-            pattern.score(haystack, matcher).is_some()
-        })
+        .filter_map(|names| names.iter().find(|name| counts[name.as_str()] == 1))
         .cloned()
-        .collect();
+        .collect()
+}
 
-    if matching_tasks.is_empty() {
-        None
-    } else {
-        Some(TaskRunner {
-            config_path: runner.config_path.clone(),
-            runner_type: runner.runner_type,
-            tasks: matching_tasks,
+/// `runner<TAB>folder<TAB>name<TAB>command` lines for --list. Tabs and line
+/// breaks inside fields become spaces so every task stays on one line.
+fn list_lines(runner: &TaskRunner, root: &Path) -> Vec<String> {
+    let field = |text: &str| text.replace(['\t', '\n', '\r'], " ");
+    let folder = folder_display(&runner.config_path, root);
+    runner
+        .tasks
+        .iter()
+        .map(|task| {
+            format!(
+                "{}\t{}\t{}\t{}",
+                runner.runner_type.display_name(),
+                field(&folder),
+                field(&task.name),
+                field(&task.command)
+            )
         })
-    }
+        .collect()
 }
 
-/// Filter all runners by query
-fn filter_runners_by_query(
-    runners: Vec<TaskRunner>,
-    query: Option<&str>,
-    root: &Path,
-) -> Vec<TaskRunner> {
-    let Some(query) = query else {
-        return runners;
-    };
+/// Check a GLOB argument, so a typo is reported instead of silently never matching
+fn glob_arg(pattern: &str) -> Result<String, globset::Error> {
+    globset::Glob::new(pattern).map(|_| pattern.to_string())
+}
 
-    let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
-    let mut matcher = Matcher::new(Config::DEFAULT);
+/// Runner types passing both --runner and --ecosystem (None when neither is given)
+fn include_runners(runners: &[RunnerType], ecosystems: &[Ecosystem]) -> Option<Vec<RunnerType>> {
+    if runners.is_empty() && ecosystems.is_empty() {
+        return None;
+    }
+    let included = RunnerType::all()
+        .iter()
+        .copied()
+        .filter(|runner_type| runners.is_empty() || runners.contains(runner_type))
+        .filter(|runner_type| ecosystems.is_empty() || ecosystems.contains(&runner_type.category()))
+        .collect();
+    Some(included)
+}
 
-    runners
+/// One `<runner> <folder> (<N> tasks)` line per runner, ordered by folder
+fn project_lines(runners: &[TaskRunner], root: &Path) -> Vec<String> {
+    let mut projects: Vec<_> = runners
+        .iter()
+        .map(|runner| {
+            (
+                folder_display(&runner.config_path, root),
+                runner.runner_type.display_name(),
+                runner.tasks.len(),
+            )
+        })
+        .collect();
+    projects.sort();
+    projects
         .into_iter()
-        .filter_map(|runner| filter_runner_by_query(&runner, Some(&pattern), &mut matcher, root))
+        .map(|(folder, runner, count)| {
+            let noun = if count == 1 { "task" } else { "tasks" };
+            format!("{} {} ({} {})", runner, folder, count, noun)
+        })
         .collect()
 }
 
+/// Opt-in fields added to runners in JSON output
+struct JsonExtras {
+    availability: Option<availability::Availability>,
+    versions: Option<versions::PackageManagerVersions>,
+}
+
+impl JsonExtras {
+    fn new(cli: &Cli) -> Self {
+        Self {
+            availability: cli
+                .check_availability
+                .then(availability::Availability::default),
+            versions: cli
+                .package_manager_version
+                .then(|| versions::PackageManagerVersions::new(cli.probe_version)),
+        }
+    }
+
+    /// Serialize a task on its own (for --json-flat) with the enabled extras
+    fn task_json(&mut self, runner: &TaskRunner, task: &Task, root: &Path) -> serde_json::Value {
+        let mut value = serde_json::to_value(task).unwrap_or_default();
+        if let Some(object) = value.as_object_mut() {
+            // Where the task actually runs, which isn't always the config's folder
+            let working_dir = work_dir(&runner.config_path, root);
+            object.insert("runner_type".to_string(), json!(runner.runner_type));
+            object.insert("config_path".to_string(), json!(runner.config_path));
+            object.insert("working_dir".to_string(), json!(working_dir));
+            if let Some(workspace) = &runner.workspace {
+                object.insert("workspace".to_string(), json!(workspace));
+            }
+            if let Some(availability) = self.availability.as_mut() {
+                object.insert(
+                    "available".to_string(),
+                    availability.check(runner, task).into(),
+                );
+            }
+        }
+        if let Some(versions) = self.versions.as_mut() {
+            versions.annotate(runner, &mut value);
+        }
+        value
+    }
+
+    /// Serialize a runner with the enabled extras
+    fn runner_json(&mut self, runner: &TaskRunner) -> serde_json::Value {
+        let mut value = match self.availability.as_mut() {
+            Some(availability) => availability.runner_json(runner),
+            None => serde_json::to_value(runner).unwrap_or_default(),
+        };
+        if let Some(versions) = self.versions.as_mut() {
+            versions.annotate(runner, &mut value);
+        }
+        value
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
+    cli.color.apply();
+    let mut json_extras = JsonExtras::new(&cli);
 
-    let root = cli
-        .path
-        .unwrap_or_else(|| env::current_dir().expect("Failed to get current directory"));
+    let path = match &cli.command {
+        Some(Commands::RunAll { path, .. }) => path.clone().or(cli.path),
+        None => cli.path,
+    };
+    let root = path.unwrap_or_else(|| env::current_dir().expect("Failed to get current directory"));
 
     let root = root.canonicalize().unwrap_or_else(|_| root.clone());
 
     let options = ScanOptions {
         no_ignore: cli.no_ignore,
+        generic_scripts: cli.scripts,
+        script_patterns: cli.script_patterns,
+        readme_descriptions: cli.readme_descriptions,
+        flatten_make_wrappers: cli.flatten_make,
+        implied_tasks: cli.implied_tasks,
+        global_tasks: cli.global,
+        exclude_task_names: cli.exclude_tasks,
+        only_paths: cli.only_paths,
+        proto: cli.proto,
+        include_hooks: cli.all,
+        service_notes: cli.service_notes,
+        max_depth: cli.depth,
+        shallow_first: cli.shallow_first,
+        include_runners: include_runners(&cli.runners, &cli.ecosystems),
         ..Default::default()
     };
 
+    let match_options = matching::MatchOptions {
+        case_sensitive: cli.case_sensitive,
+        no_normalize: cli.no_normalize,
+    };
+
+    let mut theme = if cli.accessible {
+        render::Theme::accessible()
+    } else {
+        render::Theme::default()
+    };
+    theme.emoji = !cli.no_emoji;
+
+    // Non-interactive output: scan → filter by runner → filter by query → sort → format
+    let pipeline = pipeline::Pipeline {
+        runner_types: &cli.runners,
+        query: cli.query.as_deref(),
+        match_options,
+        sort: cli.sort,
+    };
+
+    // Headless run-all mode
+    if let Some(Commands::RunAll {
+        pattern,
+        yes,
+        continue_on_error,
+        parallel,
+        jobs,
+        ..
+    }) = &cli.command
+    {
+        let names = match matching::NamePattern::new(pattern) {
+            Ok(names) => names,
+            Err(e) => {
+                eprintln!("{} Invalid pattern {:?}: {}", style("✗").red(), pattern, e);
+                std::process::exit(1);
+            }
+        };
+        let pipeline = pipeline::Pipeline {
+            query: None,
+            ..pipeline
+        };
+        let mut runners =
+            pipeline.run(scan_with_options(&root, options).unwrap_or_default(), &root);
+        for runner in &mut runners {
+            runner.tasks.retain(|task| names.is_match(&task.name));
+        }
+        runners.retain(|runner| !runner.tasks.is_empty());
+        let jobs = parallel.then(|| jobs.unwrap_or_else(parallel::default_jobs));
+        let code = run_all(&runners, &root, &theme, !*yes, *continue_on_error, jobs);
+        std::process::exit(code);
+    }
+
+    // Best-match mode: print one command for shell capture
+    if let Some(query) = &cli.first {
+        let pipeline = pipeline::Pipeline {
+            query: None,
+            ..pipeline
+        };
+        let runners = pipeline.run(scan_with_options(&root, options).unwrap_or_default(), &root);
+        match best_match(&runners, query, match_options, &root) {
+            Some((_, task)) => println!("{}", task.command),
+            None => std::process::exit(1),
+        }
+        return;
+    }
+
+    // Plan mode: print the dependency-ordered commands for the best match
+    if let Some(query) = &cli.plan {
+        let pipeline = pipeline::Pipeline {
+            query: None,
+            ..pipeline
+        };
+        let runners = pipeline.run(scan_with_options(&root, options).unwrap_or_default(), &root);
+        let Some((runner, task)) = best_match(&runners, query, match_options, &root) else {
+            eprintln!("{} No task matches {:?}", style("✗").red(), query);
+            std::process::exit(1);
+        };
+        match plan::run_order(runner, task) {
+            Ok(order) => {
+                for task in order {
+                    println!("{}", task.command);
+                }
+            }
+            Err(cycle) => {
+                eprintln!(
+                    "{} Dependency cycle: {}",
+                    style("✗").red(),
+                    cycle.join(" → ")
+                );
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Direct exec mode: run the one task named (or else matching) NAME, for scripts
+    if let Some(name) = &cli.exec {
+        let pipeline = pipeline::Pipeline {
+            query: None,
+            ..pipeline
+        };
+        let runners = pipeline.run(scan_with_options(&root, options).unwrap_or_default(), &root);
+        let (runner, task) = match resolve_exec(&runners, name, match_options, &root)[..] {
+            [] => {
+                eprintln!("{} No task matches {:?}", style("✗").red(), name);
+                std::process::exit(1);
+            }
+            [found] => found,
+            ref candidates => {
+                eprintln!("{} {:?} matches several tasks:", style("✗").red(), name);
+                for (runner, task) in candidates {
+                    eprintln!(
+                        "  {}  {}",
+                        folder_display(&runner.config_path, &root),
+                        task.command
+                    );
+                }
+                std::process::exit(2);
+            }
+        };
+        let selected = messages::SelectedTask {
+            command: task.command.clone(),
+            script: task.script.clone(),
+            runner_type: runner.runner_type,
+            config_path: runner.config_path.clone(),
+        };
+        run_task(&selected, &task.command, &root, &theme);
+        return;
+    }
+
+    // Watch mode: run the best match and restart it on file changes
+    if let Some(query) = &cli.watch_run {
+        let pipeline = pipeline::Pipeline {
+            query: None,
+            ..pipeline
+        };
+        let runners = pipeline.run(scan_with_options(&root, options).unwrap_or_default(), &root);
+        let Some((runner, task)) = best_match(&runners, query, match_options, &root) else {
+            eprintln!("{} No task matches {:?}", style("✗").red(), query);
+            std::process::exit(1);
+        };
+        let selected = messages::SelectedTask {
+            command: task.command.clone(),
+            script: task.script.clone(),
+            runner_type: runner.runner_type,
+            config_path: runner.config_path.clone(),
+        };
+        let watch_options = watch::WatchOptions {
+            paths: cli.watch_paths.clone(),
+            ignore: cli.watch_ignore.clone(),
+        };
+        let code = watch::watch_run(&selected, &task.command, &root, &theme, &watch_options);
+        std::process::exit(code);
+    }
+
+    // Projects mode: one line per config file, without its tasks
+    if cli.projects {
+        let runners = pipeline.run(
+            scan_with_options(&root, options.clone()).unwrap_or_default(),
+            &root,
+        );
+        for line in project_lines(&runners, &root) {
+            println!("{}", line);
+        }
+        return;
+    }
+
+    // Code generation mode
+    if let Some(format) = cli.generate {
+        let runners = pipeline.run(
+            scan_with_options(&root, options.clone()).unwrap_or_default(),
+            &root,
+        );
+        print!("{}", generate::generate(format, &runners, &root));
+        return;
+    }
+
+    // JSON output collapses tasks repeated within a config file
+    let json_options = ScanOptions {
+        dedup: true,
+        ..options.clone()
+    };
+
     // JSON array output mode
     if cli.json {
-        let runners = scan_with_options(&root, options.clone()).unwrap_or_default();
-        let runners = filter_runners_by_query(runners, cli.query.as_deref(), &root);
+        let (runners, errors) = if cli.report_errors {
+            let report = scan_report(&root, json_options.clone());
+            (report.runners, Some(report.errors))
+        } else {
+            let runners = scan_with_options(&root, json_options.clone()).unwrap_or_default();
+            (runners, None)
+        };
+        let runners = pipeline.run(runners, &root);
+        let output = match cli.group_by {
+            Some(group_by) => {
+                let groups = grouping::grouped_json(group_by, &runners, &root, |runner| {
+                    json_extras.runner_json(runner)
+                });
+                serde_json::to_value(groups).unwrap_or_default()
+            }
+            None => runners
+                .iter()
+                .map(|runner| json_extras.runner_json(runner))
+                .collect(),
+        };
+        let output = match errors {
+            Some(errors) => {
+                let errors: Vec<_> = errors.iter().map(error_json).collect();
+                json!({ "runners": output, "errors": errors })
+            }
+            None => output,
+        };
+        let output = serde_json::to_string_pretty(&output);
+        println!("{}", output.unwrap_or_else(|_| "[]".into()));
+        return;
+    }
+
+    // Flat JSON output mode: one object per task
+    if cli.json_flat {
+        let runners = pipeline.run(
+            scan_with_options(&root, json_options).unwrap_or_default(),
+            &root,
+        );
+        let values: Vec<_> = runners
+            .iter()
+            .flat_map(|runner| runner.tasks.iter().map(move |task| (runner, task)))
+            .map(|(runner, task)| json_extras.task_json(runner, task, &root))
+            .collect();
         println!(
             "{}",
-            serde_json::to_string_pretty(&runners).unwrap_or_else(|_| "[]".into())
+            serde_json::to_string_pretty(&values).unwrap_or_else(|_| "[]".into())
         );
         return;
     }
 
-    // NDJSON streaming output mode
-    if cli.json_stream {
+    // Completion script mode: bake the discovered task names into a script
+    if let Some(shell) = cli.complete {
         let (tx, rx) = mpsc::channel();
         let _scanner_handle = scan_streaming(root.clone(), options, tx);
 
+        let runners: Vec<_> = rx.into_iter().filter_map(pipeline.filter(&root)).collect();
+        let names = exec_completions(&runners, &root);
+        print!(
+            "{}",
+            completions::script(shell, names.iter().map(String::as_str))
+        );
+        return;
+    }
+
+    // Plain list mode: tab-separated lines for scripts
+    if cli.list {
+        let (tx, rx) = mpsc::channel();
+        let _scanner_handle = scan_streaming(root.clone(), options, tx);
+
+        // Lines are written as runners arrive unless they have to be sorted
         let mut stdout = stdout().lock();
-        let mut matcher = Matcher::new(Config::DEFAULT);
-        let pattern = cli
-            .query
-            .as_ref()
-            .map(|q| Pattern::parse(q, CaseMatching::Ignore, Normalization::Smart));
-
-        for runner in rx {
-            let filtered = filter_runner_by_query(&runner, pattern.as_ref(), &mut matcher, &root);
-            if let Some(filtered) = filtered {
-                writeln!(
-                    stdout,
-                    "{}",
-                    serde_json::to_string(&filtered).unwrap_or_default()
-                )
-                .ok();
+        let runners: Box<dyn Iterator<Item = TaskRunner>> = match pipeline.sort {
+            Some(_) => Box::new(pipeline.run(rx.into_iter().collect(), &root).into_iter()),
+            None => Box::new(rx.into_iter().filter_map(pipeline.filter(&root))),
+        };
+        for runner in runners {
+            for line in list_lines(&runner, &root) {
+                writeln!(stdout, "{}", line).ok();
             }
         }
         return;
     }
 
+    // NDJSON streaming output mode
+    if cli.json_stream {
+        let (tx, rx) = mpsc::channel();
+        let options = ScanOptions {
+            report_errors: cli.report_errors,
+            ..options
+        };
+        let _scanner_handle = scan_streaming_items(root.clone(), options, tx);
+
+        // Results are written as they arrive, so --sort doesn't apply
+        let mut stdout = stdout().lock();
+        let mut filter = pipeline.filter(&root);
+        for item in rx {
+            let value = match item {
+                ScanItem::Runner(runner) => match filter(runner) {
+                    Some(runner) => json_extras.runner_json(&runner),
+                    None => continue,
+                },
+                ScanItem::Error(error) => json!({ "error": error_json(&error) }),
+            };
+            let line = serde_json::to_string(&value);
+            writeln!(stdout, "{}", line.unwrap_or_default()).ok();
+        }
+        return;
+    }
+
     // Interactive mode - use new UI/Backend architecture
     let (request_tx, request_rx) = mpsc::channel();
     let (response_tx, response_rx) = mpsc::channel();
@@ -189,67 +874,321 @@ fn main() {
         .unwrap_or_else(|| ".".to_string());
 
     // Spawn backend thread
-    let _backend_handle = backend::spawn_backend(
+    let (_backend_handle, scan_handle) = backend::spawn_backend(
         root.clone(),
         options,
+        match_options,
+        backend::OrderOptions {
+            preserve_order: cli.preserve_order,
+            runner_priority: cli.runner_priority.clone(),
+        },
         tasks.clone(),
         request_rx,
         response_tx,
     );
 
-    // Run UI on main thread
-    match ui::run(request_tx, response_rx, tasks, root_name) {
-        Some(result) => {
-            run_task(&result.task, &result.command, &root);
-        }
+    let picker_options = ui::PickerOptions {
+        edit: cli.edit.is_some(),
+        query: cli.edit.unwrap_or_default(),
+        theme: theme.clone(),
+        match_options,
+        collapse_folders: cli.group_collapse,
+        abs_root: cli.abs_paths.then(|| root.clone()),
+        vim_keys: cli.vim,
+    };
+
+    // Run UI on main thread. Once it returns the scan results aren't needed,
+    // so stop walking instead of leaving the scan running during the task.
+    let picked = ui::run(request_tx, response_rx, tasks, root_name, picker_options);
+    scan_handle.cancel();
+    match picked {
+        Some(result) => run_picked(&result.tasks, &root, &theme),
         None => {
             println!();
-            println!("  {} Cancelled", style("✗").dim());
+            println!("  {} Cancelled", theme.muted("✗"));
         }
     }
 }
 
-/// Run a task
-fn run_task(task: &messages::SelectedTask, command: &str, root: &Path) {
-    let work_dir = task.config_path.parent().unwrap_or(root);
-    let sep = style("─".repeat(60)).dim();
+/// A scan error as JSON: the config file it's about (if any) and the message
+fn error_json(error: &ScanError) -> serde_json::Value {
+    json!({ "path": error.path(), "message": error.to_string() })
+}
+
+/// Run all tasks sequentially and print a summary. Returns the exit code.
+/// With `confirm`, the tasks are listed and only run once the user agrees.
+fn run_all(
+    runners: &[TaskRunner],
+    root: &Path,
+    theme: &render::Theme,
+    confirm: bool,
+    continue_on_error: bool,
+    parallel_jobs: Option<usize>,
+) -> i32 {
+    let tasks = ordered_tasks(runners, root);
+    if tasks.is_empty() {
+        println!("\n  {} No matching tasks\n", theme.muted("✗"));
+        return 1;
+    }
+    if confirm && !confirm_run_all(&tasks, root, theme) {
+        return 1;
+    }
+    let selected = |runner: &TaskRunner, task: &Task| messages::SelectedTask {
+        command: task.command.clone(),
+        script: task.script.clone(),
+        runner_type: runner.runner_type,
+        config_path: runner.config_path.clone(),
+    };
+
+    let mut results: Vec<(&TaskRunner, &Task, i32)> = Vec::new();
+    if let Some(jobs) = parallel_jobs {
+        let labelled: Vec<_> = tasks
+            .iter()
+            .map(|&(runner, task)| {
+                let folder = folder_display(&runner.config_path, root);
+                let label = if folder == "." {
+                    task.name.clone()
+                } else {
+                    format!("{} {}", folder, task.name)
+                };
+                (label, selected(runner, task))
+            })
+            .collect();
+        println!();
+        let codes = parallel::run_parallel(&labelled, root, jobs, !continue_on_error);
+        println!();
+        for ((runner, task), code) in tasks.into_iter().zip(codes) {
+            if let Some(code) = code {
+                results.push((runner, task, code));
+            }
+        }
+    } else {
+        for (runner, task) in tasks {
+            let code = execute_task(&selected(runner, task), &task.command, root, theme);
+            results.push((runner, task, code));
+            if code != 0 && (!continue_on_error || interrupt::interrupted()) {
+                break;
+            }
+        }
+    }
+
+    let failed = results.iter().filter(|(_, _, code)| *code != 0).count();
+    println!("  {}", style("Summary").bold());
+    for (runner, task, code) in &results {
+        let mark = if *code == 0 {
+            style("✓").green().bold()
+        } else {
+            style("✗").red().bold()
+        };
+        println!(
+            "  {} {} {}",
+            mark,
+            theme.muted(folder_display(&runner.config_path, root)),
+            task.command
+        );
+    }
+    println!(
+        "\n  {} succeeded, {} failed\n",
+        results.len() - failed,
+        failed
+    );
+
+    if interrupt::interrupted() {
+        return interrupt::EXIT_CODE;
+    }
+    results
+        .iter()
+        .map(|(_, _, code)| *code)
+        .find(|code| *code != 0)
+        .unwrap_or(0)
+}
+
+/// List the tasks run-all is about to run and ask whether to go ahead.
+/// Without a terminal to ask on, nothing runs unless --yes was given.
+fn confirm_run_all(tasks: &[(&TaskRunner, &Task)], root: &Path, theme: &render::Theme) -> bool {
+    println!(
+        "\n  {}",
+        style(format!("{} matching task(s)", tasks.len())).bold()
+    );
+    for (runner, task) in tasks {
+        println!(
+            "  {} {}",
+            theme.muted(folder_display(&runner.config_path, root)),
+            task.command
+        );
+    }
+    if !std::io::stdin().is_terminal() {
+        eprintln!(
+            "\n  {} Not a terminal; pass --yes to run these tasks\n",
+            style("✗").red()
+        );
+        return false;
+    }
+
+    print!("\n  Run them? [y/N] ");
+    stdout().flush().ok();
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).ok();
+    let confirmed = matches!(answer.trim(), "y" | "Y" | "yes");
+    if !confirmed {
+        println!("  {} Cancelled", theme.muted("✗"));
+    }
+    confirmed
+}
+
+/// Run a task, exiting the process if it fails
+fn run_task(task: &messages::SelectedTask, command: &str, root: &Path, theme: &render::Theme) {
+    let code = execute_task(task, command, root, theme);
+    if code != 0 {
+        std::process::exit(code);
+    }
+}
+
+/// Run the tasks picked in the UI one after another, exiting the process
+/// with the first failure's code (the tasks after it don't run)
+fn run_picked(tasks: &[ui::PickedTask], root: &Path, theme: &render::Theme) {
+    for (i, picked) in tasks.iter().enumerate() {
+        if tasks.len() > 1 {
+            println!(
+                "\n  {}",
+                theme.muted(format!("[{}/{}]", i + 1, tasks.len()))
+            );
+        }
+        let code = execute_task(&picked.task, &picked.command, root, theme);
+        if code != 0 {
+            let skipped = tasks.len() - i - 1;
+            if skipped > 0 {
+                println!(
+                    "  {} Skipped {} remaining task(s)\n",
+                    theme.muted("✗"),
+                    skipped
+                );
+            }
+            std::process::exit(code);
+        }
+    }
+}
+
+/// Run a task and report its result. Returns the exit code.
+fn execute_task(
+    task: &messages::SelectedTask,
+    command: &str,
+    root: &Path,
+    theme: &render::Theme,
+) -> i32 {
+    print_task_header(task, command, root, theme);
+
+    let status = match spawn_task(task, command, root) {
+        Some(child) => child.and_then(|mut child| child.wait()),
+        None => {
+            eprintln!("{} Empty command", style("✗").red());
+            return 1;
+        }
+    };
+    report_status(status, theme)
+}
+
+/// Folder a task runs in: its config file's folder, or the root for global
+/// tasks (whose config file lives outside the project). GitHub workflows
+/// run from the repository that holds `.github/workflows`, and Dev Container
+/// lifecycle commands from the workspace that holds `.devcontainer`.
+fn work_dir<'a>(config_path: &'a Path, root: &'a Path) -> &'a Path {
+    let dir = config_path.parent().filter(|dir| dir.starts_with(root));
+    let repo = dir.and_then(|dir| {
+        if dir.ends_with(".github/workflows") {
+            dir.parent()?.parent()
+        } else if dir.ends_with(".devcontainer") {
+            dir.parent()
+        } else {
+            None
+        }
+    });
+    repo.or(dir).unwrap_or(root)
+}
+
+/// Print the "Running <command>" banner shown before a task starts
+fn print_task_header(
+    task: &messages::SelectedTask,
+    command: &str,
+    root: &Path,
+    theme: &render::Theme,
+) {
+    let work_dir = work_dir(&task.config_path, root);
+    let sep = theme.muted("─".repeat(60));
 
+    let icon = if theme.emoji {
+        format!("{} ", task.runner_type.icon())
+    } else {
+        String::new()
+    };
     println!(
-        "\n  {} {} {}",
-        task.runner_type.icon(),
+        "\n  {}{} {}",
+        icon,
         style("Running").green().bold(),
         style(command).white().bold()
     );
     if work_dir != root {
         println!(
             "  {} {}",
-            style("in").dim(),
-            style(work_dir.strip_prefix(root).unwrap_or(work_dir).display()).dim()
+            theme.muted("in"),
+            theme.muted(work_dir.strip_prefix(root).unwrap_or(work_dir).display())
         );
     }
     println!("\n{}\n", sep);
+}
 
-    let parts: Vec<&str> = command.split_whitespace().collect();
-    if parts.is_empty() {
-        eprintln!("{} Empty command", style("✗").red());
-        return;
-    }
+/// A task's command, set to run in its config folder. Returns None for an
+/// empty command. From here on Ctrl+C is left to the task (see `interrupt`).
+fn task_command(task: &messages::SelectedTask, command: &str, root: &Path) -> Option<Command> {
+    interrupt::install();
+    let work_dir = work_dir(&task.config_path, root);
+    // Quotes group words like a shell would (`sh -c 'a && b'`); a command
+    // with unbalanced quotes falls back to plain words
+    let parts = shlex::split(command)
+        .unwrap_or_else(|| command.split_whitespace().map(String::from).collect());
+    let (program, args) = parts.split_first()?;
+
+    let mut command = Command::new(program);
+    command.args(args).current_dir(work_dir);
+    Some(command)
+}
 
-    let status = Command::new(parts[0])
-        .args(&parts[1..])
-        .current_dir(work_dir)
-        .stdin(Stdio::inherit())
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .status();
+/// Start a task's command in its config folder. Returns None for an empty command.
+fn spawn_task(
+    task: &messages::SelectedTask,
+    command: &str,
+    root: &Path,
+) -> Option<std::io::Result<std::process::Child>> {
+    Some(
+        task_command(task, command, root)?
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .spawn(),
+    )
+}
 
+/// Print how a task ended. Returns the exit code.
+fn report_status(status: std::io::Result<std::process::ExitStatus>, theme: &render::Theme) -> i32 {
+    let sep = theme.muted("─".repeat(60));
     println!("\n{}", sep);
     match status {
-        Ok(s) if s.success() => println!(
-            "\n  {} {}\n",
-            style("✓").green().bold(),
-            style("Task completed successfully").green()
-        ),
+        Ok(s) if interrupt::interrupted() => {
+            println!(
+                "\n  {} {}\n",
+                style("✗").red().bold(),
+                style("Interrupted").red()
+            );
+            interrupt::exit_code(s)
+        }
+        Ok(s) if s.success() => {
+            println!(
+                "\n  {} {}\n",
+                style("✓").green().bold(),
+                style("Task completed successfully").green()
+            );
+            0
+        }
         Ok(s) => {
             println!(
                 "\n  {} {} {}\n",
@@ -257,7 +1196,7 @@ fn run_task(task: &messages::SelectedTask, command: &str, root: &Path) {
                 style("Task failed with exit code").red(),
                 style(s.code().unwrap_or(-1)).red().bold()
             );
-            std::process::exit(s.code().unwrap_or(1));
+            interrupt::exit_code(s)
         }
         Err(e) => {
             println!(
@@ -266,7 +1205,7 @@ fn run_task(task: &messages::SelectedTask, command: &str, root: &Path) {
                 style("Failed to execute:").red(),
                 style(e).red()
             );
-            std::process::exit(1);
+            1
         }
     }
 }
@@ -274,11 +1213,262 @@ fn run_task(task: &messages::SelectedTask, command: &str, root: &Path) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::backend::{Backend, SharedTasks};
+    use crate::backend::{Backend, OrderOptions, SharedTasks};
+    use crate::matching::MatchOptions;
     use crate::messages::SearchRequest;
     use crate::render::render;
     use crate::ui::{Mode, UIState};
 
+    fn runner(config_path: &str, runner_type: RunnerType, names: &[&str]) -> TaskRunner {
+        TaskRunner {
+            config_path: PathBuf::from(config_path),
+            runner_type,
+            tasks: names
+                .iter()
+                .map(|name| Task {
+                    name: name.to_string(),
+                    command: format!("{} {}", runner_type, name),
+                    description: None,
+                    script: None,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ordered_tasks() {
+        let root = Path::new("/repo");
+        let runners = vec![
+            runner("/repo/b/package.json", RunnerType::Npm, &["lint"]),
+            runner("/repo/package.json", RunnerType::Npm, &["test", "lint"]),
+            runner("/repo/Makefile", RunnerType::Make, &["lint"]),
+            runner("/repo/a/Makefile", RunnerType::Make, &["lint"]),
+        ];
+
+        let order: Vec<_> = ordered_tasks(&runners, root)
+            .into_iter()
+            .map(|(runner, task)| {
+                (
+                    folder_display(&runner.config_path, root),
+                    task.command.clone(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            order,
+            vec![
+                (".".to_string(), "make lint".to_string()),
+                (".".to_string(), "npm lint".to_string()),
+                (".".to_string(), "npm test".to_string()),
+                ("a".to_string(), "make lint".to_string()),
+                ("b".to_string(), "npm lint".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_best_match() {
+        let root = Path::new("/repo");
+        let runners = vec![
+            runner(
+                "/repo/web/package.json",
+                RunnerType::Npm,
+                &["build", "test"],
+            ),
+            runner("/repo/Makefile", RunnerType::Make, &["build"]),
+        ];
+        let command = |query: &str| {
+            best_match(&runners, query, MatchOptions::default(), root)
+                .map(|(_, task)| task.command.clone())
+        };
+
+        assert_eq!(command("web test").as_deref(), Some("npm test"));
+        // Equal scores resolve to run-all order (root folder first)
+        assert_eq!(command("build").as_deref(), Some("make build"));
+        assert_eq!(command("deploy"), None);
+    }
+
+    #[test]
+    fn test_resolve_exec() {
+        let root = Path::new("/repo");
+        let mut runners = vec![
+            runner("/repo/web/package.json", RunnerType::Npm, &["build", "dev"]),
+            runner("/repo/Makefile", RunnerType::Make, &["build"]),
+            runner(
+                "/repo/.devcontainer/devcontainer.json",
+                RunnerType::Devcontainer,
+                &["postCreate"],
+            ),
+        ];
+        runners[2].tasks[0].command = "sh -c 'npm install'".to_string();
+        let commands = |name: &str| -> Vec<String> {
+            resolve_exec(&runners, name, MatchOptions::default(), root)
+                .into_iter()
+                .map(|(_, task)| task.command.clone())
+                .collect()
+        };
+
+        // Names are matched exactly, even when the command doesn't contain them
+        assert_eq!(commands("postCreate"), ["sh -c 'npm install'"]);
+        assert_eq!(commands("build"), ["make build", "npm build"]);
+        assert_eq!(commands("web build"), ["npm build"]);
+        assert_eq!(commands("make . build"), ["make build"]);
+        // Without an exact name it's the best fuzzy matches
+        assert_eq!(commands("npm dev"), ["npm dev"]);
+        assert!(commands("deploy").is_empty());
+    }
+
+    #[test]
+    fn test_exec_completions_resolve() {
+        let root = Path::new("/repo");
+        let runners = vec![
+            runner("/repo/web/package.json", RunnerType::Npm, &["build", "dev"]),
+            runner("/repo/Makefile", RunnerType::Make, &["build", "lint"]),
+            runner("/repo/package.json", RunnerType::Npm, &["build"]),
+        ];
+
+        let names = exec_completions(&runners, root);
+        assert_eq!(
+            names,
+            ["make . build", "npm . build", "lint", "web build", "dev"]
+        );
+        for name in &names {
+            assert_eq!(
+                resolve_exec(&runners, name, MatchOptions::default(), root).len(),
+                1,
+                "{:?} should resolve to one task",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_include_runners_intersects_flags() {
+        assert_eq!(include_runners(&[], &[]), None);
+        assert_eq!(
+            include_runners(&[], &[Ecosystem::Jvm]),
+            Some(vec![RunnerType::Maven, RunnerType::Gradle])
+        );
+        assert_eq!(
+            include_runners(
+                &[RunnerType::Make, RunnerType::Npm],
+                &[Ecosystem::JavaScript]
+            ),
+            Some(vec![RunnerType::Npm])
+        );
+    }
+
+    #[test]
+    fn test_project_lines() {
+        let root = Path::new("/repo");
+        let runners = vec![
+            runner(
+                "/repo/web/package.json",
+                RunnerType::Npm,
+                &["build", "test"],
+            ),
+            runner("/repo/Makefile", RunnerType::Make, &["build"]),
+        ];
+
+        assert_eq!(
+            project_lines(&runners, root),
+            vec!["make . (1 task)", "npm web (2 tasks)"]
+        );
+    }
+
+    #[test]
+    fn test_work_dir() {
+        let root = Path::new("/repo");
+        let work_dir = |config: &str| work_dir(Path::new(config), root).to_path_buf();
+        assert_eq!(work_dir("/repo/web/package.json"), Path::new("/repo/web"));
+        assert_eq!(
+            work_dir("/repo/.github/workflows/ci.yml"),
+            Path::new("/repo")
+        );
+        assert_eq!(
+            work_dir("/repo/api/.devcontainer/devcontainer.json"),
+            Path::new("/repo/api")
+        );
+        assert_eq!(work_dir("/home/me/tasks.toml"), Path::new("/repo"));
+    }
+
+    #[test]
+    fn test_task_command_keeps_quoted_arguments() {
+        let command = "just deploy 'eu west' ''";
+        let task = messages::SelectedTask {
+            command: command.to_string(),
+            script: None,
+            runner_type: RunnerType::Just,
+            config_path: PathBuf::from("/repo/justfile"),
+        };
+        let built = task_command(&task, command, Path::new("/repo")).unwrap();
+        assert_eq!(built.get_program(), "just");
+        let args: Vec<_> = built.get_args().collect();
+        assert_eq!(args, ["deploy", "eu west", ""]);
+    }
+
+    #[test]
+    fn test_color_choice() {
+        assert!(ColorChoice::Auto.enabled(true, None));
+        assert!(ColorChoice::Auto.enabled(true, Some("")));
+        assert!(!ColorChoice::Auto.enabled(false, None));
+        assert!(!ColorChoice::Auto.enabled(true, Some("1")));
+        assert!(ColorChoice::Always.enabled(false, Some("1")));
+        assert!(!ColorChoice::Never.enabled(true, None));
+    }
+
+    #[test]
+    fn test_flat_task_json() {
+        let runner = runner("/repo/web/package.json", RunnerType::Npm, &["build"]);
+        let mut extras = JsonExtras {
+            availability: None,
+            versions: None,
+        };
+
+        let value = extras.task_json(&runner, &runner.tasks[0], Path::new("/repo"));
+        assert_eq!(value["name"], "build");
+        assert_eq!(value["command"], "npm build");
+        assert_eq!(value["runner_type"], "npm");
+        assert_eq!(value["config_path"], "/repo/web/package.json");
+        assert_eq!(value["working_dir"], "/repo/web");
+    }
+    #[test]
+    fn test_flat_task_json_working_dir_of_workflow() {
+        let runner = runner(
+            "/repo/.github/workflows/ci.yml",
+            RunnerType::GithubActions,
+            &["test"],
+        );
+        let mut extras = JsonExtras {
+            availability: None,
+            versions: None,
+        };
+
+        // Workflows run from the repository root, not the workflows folder
+        let value = extras.task_json(&runner, &runner.tasks[0], Path::new("/repo"));
+        assert_eq!(value["config_path"], "/repo/.github/workflows/ci.yml");
+        assert_eq!(value["working_dir"], "/repo");
+    }
+    #[test]
+    fn test_flat_task_json_working_dir_of_global_task() {
+        let runner = runner(
+            "/home/me/.config/task-runner/tasks.toml",
+            RunnerType::Script,
+            &["deploy"],
+        );
+        let mut extras = JsonExtras {
+            availability: None,
+            versions: None,
+        };
+
+        // Global tasks run from the scanned folder, not the config's folder
+        let value = extras.task_json(&runner, &runner.tasks[0], Path::new("/repo"));
+        assert_eq!(value["working_dir"], "/repo");
+    }
+
     /// Test that the first render matches the expected output
     #[test]
     fn test_first_render_matches_expected() {
@@ -293,7 +1483,13 @@ mod tests {
         let tasks: SharedTasks = Arc::new(RwLock::new(Vec::new()));
 
         // Build a backend and populate it with tasks
-        let mut backend = Backend::new(root.clone(), tasks.clone());
+        let mut backend = Backend::new(
+            root.clone(),
+            tasks.clone(),
+            MatchOptions::default(),
+            OrderOptions::default(),
+        );
+        backend.skip_availability_for_test();
         for runner in &runners {
             backend.add_runner_for_test(runner.clone());
         }
@@ -305,6 +1501,8 @@ mod tests {
             limit: 100,
             viewport_lines: 30,
             selected_index: 0,
+            follow_task: None,
+            folders: Default::default(),
         };
 
         // Get search response
@@ -319,6 +1517,18 @@ mod tests {
             scroll_offset: 0,
             edit_buffer: String::new(),
             edit_cursor: 0,
+            theme: render::Theme::default(),
+            match_options: MatchOptions::default(),
+            show_help: false,
+            show_script: false,
+            show_preview: false,
+            marked: Vec::new(),
+            flash: None,
+            vim_keys: false,
+            searching: false,
+            page_lines: 0,
+            folders: Default::default(),
+            abs_root: None,
         };
 
         // Get root name for display
@@ -328,7 +1538,7 @@ mod tests {
             .unwrap_or_else(|| ".".to_string());
 
         // Render
-        let result = render(&state, &response, &tasks, &root_name, 50);
+        let result = render(&state, &response, &tasks, &root_name, 80, 50);
 
         // Read expected output and compare
         let expected_path = root.join("fixtures/first_render.txt");
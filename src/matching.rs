@@ -0,0 +1,132 @@
+//! Fuzzy-match settings shared by every place that builds a search pattern
+
+use globset::{Glob, GlobMatcher};
+use nucleo::pattern::{CaseMatching, Normalization, Pattern};
+use task_runner_detector::RunnerType;
+
+/// How queries are matched against tasks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchOptions {
+    /// Match case exactly instead of ignoring it
+    pub case_sensitive: bool,
+    /// Match accented characters exactly instead of normalizing them
+    pub no_normalize: bool,
+}
+
+impl MatchOptions {
+    pub fn case_matching(&self) -> CaseMatching {
+        if self.case_sensitive {
+            CaseMatching::Respect
+        } else {
+            CaseMatching::Ignore
+        }
+    }
+
+    pub fn normalization(&self) -> Normalization {
+        if self.no_normalize {
+            Normalization::Never
+        } else {
+            Normalization::Smart
+        }
+    }
+
+    /// Parse a query into a pattern using these settings
+    pub fn pattern(&self, query: &str) -> Pattern {
+        Pattern::parse(query, self.case_matching(), self.normalization())
+    }
+}
+
+/// Text a task is matched against: its folder, its command and the runner's
+/// display name, so runners whose commands don't start with their name
+/// (e.g. Dev Container hooks running `npm install`) can be searched by it
+pub fn search_text(folder: &str, command: &str, runner_type: RunnerType) -> String {
+    format!("{} {} {}", folder, command, runner_type.display_name())
+}
+
+/// Keep the match indices (char positions in [`search_text`]) that fall in
+/// the command, made relative to it
+pub fn command_indices(folder: &str, command: &str, indices: &[u32]) -> Vec<u32> {
+    let start = folder.chars().count() as u32 + 1;
+    let end = start + command.chars().count() as u32;
+    indices
+        .iter()
+        .filter(|&&i| (start..end).contains(&i))
+        .map(|&i| i - start)
+        .collect()
+}
+
+/// Task names selected by a command that runs several tasks at once: one
+/// exact name, or a glob over names (`lint*`, `test:*`). Unlike a fuzzy
+/// query, `lint` never selects `dotnet publish`.
+pub struct NamePattern(GlobMatcher);
+
+impl NamePattern {
+    pub fn new(pattern: &str) -> Result<Self, globset::Error> {
+        Ok(Self(Glob::new(pattern)?.compile_matcher()))
+    }
+
+    pub fn is_match(&self, name: &str) -> bool {
+        self.0.is_match(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nucleo::{Config, Matcher, Utf32Str};
+
+    fn matches(options: MatchOptions, query: &str, text: &str) -> bool {
+        let mut matcher = Matcher::new(Config::DEFAULT);
+        let mut buf = Vec::new();
+        options
+            .pattern(query)
+            .score(Utf32Str::new(text, &mut buf), &mut matcher)
+            .is_some()
+    }
+
+    #[test]
+    fn test_default_ignores_case_and_accents() {
+        let options = MatchOptions::default();
+        assert!(matches(options, "build", "npm run Build"));
+        assert!(matches(options, "cafe", "make café"));
+    }
+
+    #[test]
+    fn test_strict_matching() {
+        let options = MatchOptions {
+            case_sensitive: true,
+            no_normalize: true,
+        };
+        assert!(!matches(options, "build", "npm run Build"));
+        assert!(!matches(options, "cafe", "make café"));
+        assert!(matches(options, "Build", "npm run Build"));
+    }
+
+    #[test]
+    fn test_search_text_includes_runner_name() {
+        let options = MatchOptions::default();
+        let text = search_text(".", "npm install", RunnerType::Devcontainer);
+        assert!(matches(options, "devcontainer install", &text));
+        assert!(matches(options, "'container", &text));
+
+        // Only indices inside the command are kept for highlighting
+        assert_eq!(
+            command_indices("services/api", "mvn test", &[0, 13, 14, 22]),
+            vec![0, 1]
+        );
+        assert_eq!(command_indices("é", "mvn test", &[2, 3, 10]), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_name_pattern() {
+        let lint = NamePattern::new("lint").unwrap();
+        assert!(lint.is_match("lint"));
+        assert!(!lint.is_match("lint:fix"));
+        assert!(!lint.is_match("publish"));
+
+        let glob = NamePattern::new("test:*").unwrap();
+        assert!(glob.is_match("test:unit"));
+        assert!(glob.is_match("test:e2e/chrome"));
+        assert!(!glob.is_match("test"));
+    }
+}
@@ -1,8 +1,48 @@
 //! Message types for UI/Backend communication
 
 use crate::RunnerType;
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
 
+/// Which folders are collapsed in the tree. Only applied without a query,
+/// search results always list every match.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FolderState {
+    /// Folders start collapsed (`--group-collapse`)
+    pub collapsed_by_default: bool,
+    /// Folders toggled away from the default
+    pub toggled: BTreeSet<String>,
+}
+
+impl FolderState {
+    /// Whether a folder (relative path, e.g. "apps/web") is collapsed.
+    /// The root is always expanded.
+    pub fn is_collapsed(&self, folder: &str) -> bool {
+        folder != "." && self.collapsed_by_default != self.toggled.contains(folder)
+    }
+
+    /// Expand or collapse a folder
+    pub fn set_collapsed(&mut self, folder: &str, collapsed: bool) {
+        if collapsed == self.collapsed_by_default {
+            self.toggled.remove(folder);
+        } else {
+            self.toggled.insert(folder.to_string());
+        }
+    }
+
+    /// The outermost collapsed folder containing `folder` (itself included)
+    pub fn collapsed_ancestor<'a>(&self, folder: &'a str) -> Option<&'a str> {
+        if folder == "." {
+            return None;
+        }
+        folder
+            .match_indices('/')
+            .map(|(i, _)| &folder[..i])
+            .chain(std::iter::once(folder))
+            .find(|ancestor| self.is_collapsed(ancestor))
+    }
+}
+
 /// Request from UI to Backend for search results
 #[derive(Debug, Clone)]
 pub struct SearchRequest {
@@ -16,10 +56,15 @@ pub struct SearchRequest {
     pub viewport_lines: usize,
     /// Currently selected task index
     pub selected_index: usize,
+    /// Task (shared storage index) to keep selected if it still matches.
+    /// Overrides `selected_index`, falling back to 0 if it no longer matches.
+    pub follow_task: Option<u32>,
+    /// Collapsed folders to hide the tasks of
+    pub folders: FolderState,
 }
 
 /// Response from Backend to UI with search results
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SearchResponse {
     /// Matched task indices (sorted by folder, then runner type, then name)
     /// This is a slice starting at the corrected offset
@@ -32,6 +77,13 @@ pub struct SearchResponse {
     pub matched_tasks: usize,
     /// Whether scanning is complete
     pub scanning_done: bool,
+    /// Selected task index the offset was computed for (resolved from
+    /// `follow_task` if the request had one)
+    pub selected_index: usize,
+    /// Tasks in `matched_indices` that stand in for a collapsed folder,
+    /// mapped to that folder. Each collapsed folder is listed as one row,
+    /// in place of the first task it hides.
+    pub collapsed: HashMap<u32, String>,
 }
 
 /// Task item stored in shared storage
@@ -40,8 +92,11 @@ pub struct TaskItem {
     pub folder: String,
     pub command: String,
     pub script: Option<String>,
+    pub description: Option<String>,
     pub runner_type: RunnerType,
     pub config_path: PathBuf,
+    /// Whether the program the command invokes was found
+    pub available: bool,
 }
 
 impl TaskItem {
@@ -0,0 +1,82 @@
+//! Folder labels and the canonical task order
+//!
+//! Frontends list tasks grouped by the folder of their config file, relative
+//! to the scan root. These are the labels and the order the `task` CLI uses,
+//! so other frontends can show the same thing.
+
+use std::path::Path;
+
+use crate::{Task, TaskRunner, GLOBAL_FOLDER};
+
+/// Folder label for a config file: its folder relative to `root`, `.` for
+/// the root itself, or [`GLOBAL_FOLDER`] for files outside the scanned tree
+/// (global tasks)
+pub fn folder_display(config_path: &Path, root: &Path) -> String {
+    // Only global tasks come from outside the scanned tree
+    let Ok(relative) = config_path.strip_prefix(root) else {
+        return GLOBAL_FOLDER.to_string();
+    };
+    let path_str = relative.to_string_lossy();
+
+    if !path_str.contains('/') && !path_str.contains('\\') {
+        ".".to_string()
+    } else {
+        relative
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| ".".to_string())
+    }
+}
+
+/// All tasks ordered by folder label, then task name (runner and command
+/// break ties), so the order doesn't depend on scan order
+pub fn ordered_tasks<'a>(
+    runners: &'a [TaskRunner],
+    root: &Path,
+) -> Vec<(&'a TaskRunner, &'a Task)> {
+    let mut tasks: Vec<(String, &TaskRunner, &Task)> = runners
+        .iter()
+        .flat_map(|runner| {
+            let folder = folder_display(&runner.config_path, root);
+            runner
+                .tasks
+                .iter()
+                .map(move |task| (folder.clone(), runner, task))
+        })
+        .collect();
+
+    tasks.sort_by(|(fa, ra, ta), (fb, rb, tb)| {
+        fa.cmp(fb)
+            .then_with(|| ta.name.cmp(&tb.name))
+            .then_with(|| {
+                ra.runner_type
+                    .display_name()
+                    .cmp(rb.runner_type.display_name())
+            })
+            .then_with(|| ta.command.cmp(&tb.command))
+    });
+
+    tasks
+        .into_iter()
+        .map(|(_, runner, task)| (runner, task))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_folder_display() {
+        let root = Path::new("/repo");
+        assert_eq!(folder_display(Path::new("/repo/Makefile"), root), ".");
+        assert_eq!(
+            folder_display(Path::new("/repo/apps/web/package.json"), root),
+            "apps/web"
+        );
+        assert_eq!(
+            folder_display(Path::new("/home/me/.config/task-runner/tasks.toml"), root),
+            GLOBAL_FOLDER
+        );
+    }
+}
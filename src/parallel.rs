@@ -0,0 +1,178 @@
+//! Running several tasks at once (`run-all --parallel`)
+//!
+//! Each task runs in its own folder with its output piped back line by line,
+//! prefixed with the task's label (like `concurrently`), so interleaved output
+//! stays readable. At most `jobs` tasks run at the same time.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use console::{style, Color};
+
+use crate::messages::SelectedTask;
+use crate::{interrupt, task_command};
+
+/// Colors cycled through for task labels
+const LABEL_COLORS: &[Color] = &[
+    Color::Cyan,
+    Color::Magenta,
+    Color::Yellow,
+    Color::Green,
+    Color::Blue,
+    Color::Red,
+];
+
+/// Default cap on concurrent tasks: one per CPU
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(4, |n| n.get())
+}
+
+/// Run `(label, task)` pairs concurrently, at most `jobs` at a time, in order
+/// of the list. Returns each task's exit code, or None for tasks that were
+/// never started because an earlier one failed and `stop_on_error` is set,
+/// or because of Ctrl+C.
+pub fn run_parallel(
+    tasks: &[(String, SelectedTask)],
+    root: &Path,
+    jobs: usize,
+    stop_on_error: bool,
+) -> Vec<Option<i32>> {
+    let width = tasks
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let results = Mutex::new(vec![None; tasks.len()]);
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, tasks.len().max(1)) {
+            scope.spawn(|| loop {
+                if (stop_on_error && failed.load(Ordering::Relaxed)) || interrupt::interrupted() {
+                    break;
+                }
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some((label, task)) = tasks.get(i) else {
+                    break;
+                };
+                let color = LABEL_COLORS[i % LABEL_COLORS.len()];
+                let prefix = style(format!("{:<width$} │", label, width = width))
+                    .fg(color)
+                    .bold()
+                    .to_string();
+
+                let code = run_prefixed(task, root, &prefix);
+                if code != 0 {
+                    failed.store(true, Ordering::Relaxed);
+                }
+                results.lock().unwrap()[i] = Some(code);
+            });
+        }
+    });
+
+    results.into_inner().unwrap_or_default()
+}
+
+/// Run one task with its output prefixed. Returns the exit code.
+fn run_prefixed(task: &SelectedTask, root: &Path, prefix: &str) -> i32 {
+    let Some(mut command) = task_command(task, &task.command, root) else {
+        println!("{} {}", prefix, style("Empty command").red());
+        return 1;
+    };
+    let child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            println!("{} {} {}", prefix, style("Failed to execute:").red(), e);
+            return 1;
+        }
+    };
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    thread::scope(|scope| {
+        if let Some(stdout) = stdout {
+            scope.spawn(|| forward_lines(stdout, prefix, &mut io::stdout()));
+        }
+        if let Some(stderr) = stderr {
+            scope.spawn(|| forward_lines(stderr, prefix, &mut io::stderr()));
+        }
+    });
+
+    let code = match child.wait() {
+        Ok(status) => interrupt::exit_code(status),
+        Err(_) => 1,
+    };
+    if code == 0 {
+        println!("{} {}", prefix, style("✓ done").green());
+    } else {
+        println!(
+            "{} {}",
+            prefix,
+            style(format!("✗ exited with code {}", code)).red()
+        );
+    }
+    code
+}
+
+/// Copy `reader` to `out` line by line, each line prefixed
+fn forward_lines(reader: impl Read, prefix: &str, out: &mut impl Write) {
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::new();
+    while matches!(reader.read_until(b'\n', &mut line), Ok(n) if n > 0) {
+        let text = String::from_utf8_lossy(&line);
+        writeln!(out, "{} {}", prefix, text.trim_end_matches(['\n', '\r'])).ok();
+        line.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use task_runner_detector::RunnerType;
+
+    fn task(command: &str, root: &Path) -> (String, SelectedTask) {
+        let selected = SelectedTask {
+            command: command.to_string(),
+            script: None,
+            runner_type: RunnerType::Make,
+            config_path: root.join("Makefile"),
+        };
+        (command.to_string(), selected)
+    }
+
+    #[test]
+    fn test_forward_lines_prefixes_each_line() {
+        let mut out = Vec::new();
+        forward_lines(&b"one\r\ntwo\nthree"[..], "[web]", &mut out);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "[web] one\n[web] two\n[web] three\n"
+        );
+    }
+
+    #[test]
+    fn test_run_parallel_codes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        let tasks = vec![task("true", root), task("false", root), task("true", root)];
+
+        // One at a time, stopping at the failure
+        assert_eq!(
+            run_parallel(&tasks, root, 1, true),
+            vec![Some(0), Some(1), None]
+        );
+
+        let codes = run_parallel(&tasks, root, 3, false);
+        assert_eq!(codes, vec![Some(0), Some(1), Some(0)]);
+    }
+}
@@ -0,0 +1,100 @@
+//! Parser for Homebrew `Brewfile` (brew bundle)
+//!
+//! The file isn't read for tasks; it's only checked for `brew`/`cask`/`tap`
+//! entries so unrelated files named Brewfile don't produce tasks.
+
+use std::path::Path;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::{read_config, Parser};
+
+/// `brew bundle` subcommands exposed as tasks
+const BUNDLE_COMMANDS: &[(&str, &str)] = &[
+    ("install", "Install everything in the Brewfile"),
+    ("check", "Check whether everything is installed"),
+    ("cleanup", "List installed packages not in the Brewfile"),
+];
+
+pub struct BrewfileParser;
+
+impl BrewfileParser {
+    /// Check for at least one `brew`, `cask` or `tap` entry
+    fn has_entries(content: &str) -> bool {
+        content.lines().any(|line| {
+            let line = line.trim_start();
+            ["brew ", "cask ", "tap "]
+                .iter()
+                .any(|keyword| line.starts_with(keyword))
+        })
+    }
+}
+
+impl Parser for BrewfileParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let content = read_config(path)?;
+
+        if !Self::has_entries(&content) {
+            return Ok(None);
+        }
+
+        let tasks = BUNDLE_COMMANDS
+            .iter()
+            .map(|(name, description)| Task {
+                name: name.to_string(),
+                command: format!("brew bundle {}", name),
+                description: Some(description.to_string()),
+                ..Default::default()
+            })
+            .collect();
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Brew,
+            tasks,
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_brewfile() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Brewfile");
+        fs::write(
+            &path,
+            "tap \"homebrew/bundle\"\nbrew \"git\"\ncask \"firefox\"\n",
+        )
+        .unwrap();
+
+        let parser = BrewfileParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::Brew);
+        let commands: Vec<_> = runner.tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            vec![
+                "brew bundle install",
+                "brew bundle check",
+                "brew bundle cleanup"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_skip_brewfile_without_entries() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Brewfile");
+        fs::write(&path, "# nothing here yet\n").unwrap();
+
+        let parser = BrewfileParser;
+        assert!(parser.parse(&path).unwrap().is_none());
+    }
+}
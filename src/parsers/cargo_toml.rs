@@ -1,30 +1,32 @@
 //! Parser for Cargo.toml (cargo binaries and cargo-make scripts)
 
-use std::collections::HashMap;
-use std::fs;
 use std::path::Path;
 
+use indexmap::IndexMap;
 use serde::Deserialize;
 
 use crate::{RunnerType, ScanError, Task, TaskRunner};
 
-use super::Parser;
+use super::{read_config, summary_line, Parser};
 
 #[derive(Deserialize)]
 struct CargoToml {
     package: Option<Package>,
     bin: Option<Vec<BinTarget>>,
+    workspace: Option<toml::Value>,
 }
 
 #[derive(Deserialize)]
 struct Package {
     name: Option<String>,
+    /// A string, or `{ workspace = true }` when inherited
+    description: Option<toml::Value>,
     metadata: Option<PackageMetadata>,
 }
 
 #[derive(Deserialize)]
 struct PackageMetadata {
-    scripts: Option<HashMap<String, String>>,
+    scripts: Option<IndexMap<String, String>>,
 }
 
 #[derive(Deserialize)]
@@ -32,11 +34,48 @@ struct BinTarget {
     name: String,
 }
 
+#[derive(Deserialize)]
+struct CargoConfig {
+    alias: Option<toml::Table>,
+}
+
 pub struct CargoTomlParser;
 
+impl CargoTomlParser {
+    /// `[alias]` entries from `.cargo/config` (or `.cargo/config.toml`) in
+    /// `dir`, as (alias, expansion). Like Cargo, the legacy extensionless
+    /// config wins when both exist. Only `dir` is read: Cargo also merges
+    /// configs from every folder above it, but listing those would repeat
+    /// the same aliases on each manifest below them.
+    fn aliases(dir: &Path) -> Vec<(String, String)> {
+        let config = ["config", "config.toml"]
+            .iter()
+            .find_map(|file| read_config(&dir.join(".cargo").join(file)).ok());
+        let Some(config) = config.and_then(|c| toml::from_str::<CargoConfig>(&c).ok()) else {
+            return Vec::new();
+        };
+
+        let mut aliases = Vec::new();
+        for (name, value) in config.alias.unwrap_or_default() {
+            // `b = "build"` or `rr = ["run", "--release"]`
+            let expansion = match value {
+                toml::Value::String(s) => s,
+                toml::Value::Array(parts) => parts
+                    .iter()
+                    .filter_map(toml::Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                _ => continue,
+            };
+            aliases.push((name, expansion));
+        }
+        aliases
+    }
+}
+
 impl Parser for CargoTomlParser {
     fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
-        let content = fs::read_to_string(path)?;
+        let content = read_config(path)?;
 
         let cargo: CargoToml = toml::from_str(&content).map_err(|e| ScanError::ParseError {
             path: path.to_path_buf(),
@@ -55,6 +94,7 @@ impl Parser for CargoTomlParser {
                             command: command.clone(),
                             description: None,
                             script: Some(command.clone()),
+                            ..Default::default()
                         });
                     }
                 }
@@ -69,35 +109,68 @@ impl Parser for CargoTomlParser {
                     command: format!("cargo run --bin {}", bin.name),
                     description: Some(format!("Run the {} binary", bin.name)),
                     script: None,
+                    ..Default::default()
                 });
             }
         }
 
-        // Add default cargo commands if this is a package (has a name)
-        if let Some(pkg) = &cargo.package {
-            if pkg.name.is_some() {
-                // Only add if no other tasks (to avoid cluttering)
-                if tasks.is_empty() {
-                    tasks.push(Task {
-                        name: "build".to_string(),
-                        command: "cargo build".to_string(),
-                        description: Some("Build the package".to_string()),
-                        script: None,
-                    });
-                    tasks.push(Task {
-                        name: "test".to_string(),
-                        command: "cargo test".to_string(),
-                        description: Some("Run tests".to_string()),
-                        script: None,
-                    });
-                    tasks.push(Task {
-                        name: "run".to_string(),
-                        command: "cargo run".to_string(),
-                        description: Some("Run the package".to_string()),
-                        script: None,
-                    });
-                }
-            }
+        // Add default cargo commands if this is a package (has a name) that
+        // declares no scripts or binaries of its own (to avoid cluttering)
+        let is_package = cargo.package.as_ref().is_some_and(|pkg| pkg.name.is_some());
+        if is_package && tasks.is_empty() {
+            tasks.push(Task {
+                name: "build".to_string(),
+                command: "cargo build".to_string(),
+                description: Some("Build the package".to_string()),
+                script: None,
+                ..Default::default()
+            });
+            tasks.push(Task {
+                name: "test".to_string(),
+                command: "cargo test".to_string(),
+                description: Some("Run tests".to_string()),
+                script: None,
+                ..Default::default()
+            });
+            tasks.push(Task {
+                name: "run".to_string(),
+                command: "cargo run".to_string(),
+                description: Some("Run the package".to_string()),
+                script: None,
+                ..Default::default()
+            });
+        }
+
+        // Workspace roots (including virtual manifests without [package]).
+        // A root that is also a package keeps `build`/`test` for the package.
+        if cargo.workspace.is_some() {
+            let prefix = if is_package { "workspace:" } else { "" };
+            tasks.push(Task {
+                name: format!("{}build", prefix),
+                command: "cargo build --workspace".to_string(),
+                description: Some("Build all workspace members".to_string()),
+                script: None,
+                ..Default::default()
+            });
+            tasks.push(Task {
+                name: format!("{}test", prefix),
+                command: "cargo test --workspace".to_string(),
+                description: Some("Test all workspace members".to_string()),
+                script: None,
+                ..Default::default()
+            });
+        }
+
+        // Aliases belong to the manifest next to the `.cargo` folder, so a
+        // workspace lists them once instead of under every member
+        for (name, expansion) in Self::aliases(path.parent().unwrap_or(Path::new("."))) {
+            tasks.push(Task {
+                command: format!("cargo {}", name),
+                name,
+                description: None,
+                script: Some(expansion),
+                ..Default::default()
+            });
         }
 
         if tasks.is_empty() {
@@ -107,7 +180,13 @@ impl Parser for CargoTomlParser {
         Ok(Some(TaskRunner {
             config_path: path.to_path_buf(),
             runner_type: RunnerType::Cargo,
+            description: cargo
+                .package
+                .as_ref()
+                .and_then(|pkg| pkg.description.as_ref()?.as_str())
+                .and_then(summary_line),
             tasks,
+            ..Default::default()
         }))
     }
 }
@@ -128,6 +207,9 @@ mod tests {
 [package]
 name = "myapp"
 version = "0.1.0"
+description = """
+  A server and its CLI
+"""
 
 [[bin]]
 name = "server"
@@ -142,6 +224,7 @@ name = "cli"
         let runner = parser.parse(&path).unwrap().unwrap();
 
         assert_eq!(runner.runner_type, RunnerType::Cargo);
+        assert_eq!(runner.description.as_deref(), Some("A server and its CLI"));
         assert_eq!(runner.tasks.len(), 2);
 
         let server_task = runner.tasks.iter().find(|t| t.name == "server").unwrap();
@@ -158,6 +241,7 @@ name = "cli"
 [package]
 name = "myapp"
 version = "0.1.0"
+description.workspace = true
 
 [package.metadata.scripts]
 dev = "cargo watch -x run"
@@ -169,6 +253,8 @@ lint = "cargo clippy -- -D warnings"
         let parser = CargoTomlParser;
         let runner = parser.parse(&path).unwrap().unwrap();
 
+        // Inherited descriptions aren't resolved
+        assert_eq!(runner.description, None);
         assert_eq!(runner.tasks.len(), 2);
 
         let dev_task = runner.tasks.iter().find(|t| t.name == "dev").unwrap();
@@ -196,4 +282,114 @@ version = "0.1.0"
         assert!(runner.tasks.iter().any(|t| t.name == "build"));
         assert!(runner.tasks.iter().any(|t| t.name == "test"));
     }
+
+    #[test]
+    fn test_parse_virtual_workspace() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Cargo.toml");
+        fs::write(
+            &path,
+            r#"
+[workspace]
+members = ["crates/*"]
+resolver = "2"
+"#,
+        )
+        .unwrap();
+
+        let parser = CargoTomlParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        let commands: Vec<_> = runner.tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            vec!["cargo build --workspace", "cargo test --workspace"]
+        );
+    }
+
+    #[test]
+    fn test_workspace_root_package_keeps_its_defaults() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Cargo.toml");
+        fs::write(
+            &path,
+            r#"
+[package]
+name = "app"
+
+[workspace]
+members = ["crates/*"]
+"#,
+        )
+        .unwrap();
+
+        let runner = CargoTomlParser.parse(&path).unwrap().unwrap();
+        let tasks: Vec<_> = runner
+            .tasks
+            .iter()
+            .map(|t| (t.name.as_str(), t.command.as_str()))
+            .collect();
+        assert_eq!(
+            tasks,
+            [
+                ("build", "cargo build"),
+                ("test", "cargo test"),
+                ("run", "cargo run"),
+                ("workspace:build", "cargo build --workspace"),
+                ("workspace:test", "cargo test --workspace"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_aliases_once_for_the_config_root() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join(".cargo")).unwrap();
+        fs::write(
+            dir.path().join(".cargo/config.toml"),
+            r#"
+[alias]
+b = "build"
+rr = ["run", "--release"]
+"#,
+        )
+        .unwrap();
+        let root = dir.path().join("Cargo.toml");
+        fs::write(&root, "[workspace]\nmembers = [\"crates/*\"]\n").unwrap();
+        let member = dir.path().join("crates/app");
+        fs::create_dir_all(&member).unwrap();
+        let path = member.join("Cargo.toml");
+        fs::write(&path, "[package]\nname = \"app\"\n").unwrap();
+
+        let parser = CargoTomlParser;
+        let runner = parser.parse(&root).unwrap().unwrap();
+        let b = runner.tasks.iter().find(|t| t.name == "b").unwrap();
+        assert_eq!(b.command, "cargo b");
+        assert_eq!(b.script.as_deref(), Some("build"));
+        let rr = runner.tasks.iter().find(|t| t.name == "rr").unwrap();
+        assert_eq!(rr.script.as_deref(), Some("run --release"));
+
+        // Members only list their own tasks
+        let runner = parser.parse(&path).unwrap().unwrap();
+        let names: Vec<_> = runner.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["build", "test", "run"]);
+    }
+
+    #[test]
+    fn test_legacy_cargo_config_wins() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join(".cargo")).unwrap();
+        fs::write(dir.path().join(".cargo/config"), "[alias]\nb = \"build\"\n").unwrap();
+        fs::write(
+            dir.path().join(".cargo/config.toml"),
+            "[alias]\nt = \"test\"\n",
+        )
+        .unwrap();
+        let path = dir.path().join("Cargo.toml");
+        fs::write(&path, "[package]\nname = \"app\"\n").unwrap();
+
+        let runner = CargoTomlParser.parse(&path).unwrap().unwrap();
+        assert!(runner.tasks.iter().any(|t| t.name == "b"));
+        assert!(!runner.tasks.iter().any(|t| t.name == "t"));
+    }
 }
@@ -0,0 +1,172 @@
+//! Parser for composer.json scripts (PHP)
+//!
+//! Composer also runs scripts named after its events (`post-install-cmd`,
+//! `pre-update-cmd`, ...) automatically. Those are tagged as hooks, which the
+//! scanner hides unless asked for them or the file defines nothing else.
+
+use std::path::Path;
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::{RunnerType, ScanError, Task, TaskMetadata, TaskRunner};
+
+use super::{read_config, Parser};
+
+/// Script names Composer runs on its own events
+const EVENT_HOOKS: &[&str] = &[
+    "pre-install-cmd",
+    "post-install-cmd",
+    "pre-update-cmd",
+    "post-update-cmd",
+    "pre-status-cmd",
+    "post-status-cmd",
+    "pre-archive-cmd",
+    "post-archive-cmd",
+    "pre-autoload-dump",
+    "post-autoload-dump",
+    "post-root-package-install",
+    "post-create-project-cmd",
+    "pre-operations-exec",
+    "pre-package-install",
+    "post-package-install",
+    "pre-package-update",
+    "post-package-update",
+    "pre-package-uninstall",
+    "post-package-uninstall",
+    "pre-file-download",
+    "post-file-download",
+    "pre-command-run",
+    "pre-pool-create",
+];
+
+/// A script is a single command or a list of commands run in order
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Script {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Script {
+    fn text(self) -> String {
+        match self {
+            Script::One(command) => command,
+            Script::Many(commands) => commands.join("\n"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ComposerJson {
+    scripts: Option<IndexMap<String, Script>>,
+}
+
+pub struct ComposerJsonParser;
+
+impl ComposerJsonParser {
+    fn is_event_hook(name: &str) -> bool {
+        EVENT_HOOKS.contains(&name)
+    }
+}
+
+impl Parser for ComposerJsonParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let content = read_config(path)?;
+        let composer: ComposerJson =
+            serde_json::from_str(&content).map_err(|e| ScanError::ParseError {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            })?;
+
+        let tasks: Vec<Task> = composer
+            .scripts
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, script)| {
+                let hook = Self::is_event_hook(&name);
+                Task {
+                    command: format!("composer run {}", name),
+                    description: hook.then(|| "Composer event hook".to_string()),
+                    name,
+                    script: Some(script.text()),
+                    metadata: TaskMetadata {
+                        hook,
+                        ..Default::default()
+                    },
+                }
+            })
+            .collect();
+
+        if tasks.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Composer,
+            tasks,
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_composer_scripts() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("composer.json");
+        fs::write(
+            &path,
+            r#"{
+                "name": "acme/app",
+                "scripts": {
+                    "post-install-cmd": "@php artisan key:generate",
+                    "test": "phpunit",
+                    "check": ["@test", "phpstan analyse src"]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let parser = ComposerJsonParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::Composer);
+        let names: Vec<_> = runner.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["post-install-cmd", "test", "check"]);
+        let hooks: Vec<_> = runner.tasks.iter().map(|t| t.metadata.hook).collect();
+        assert_eq!(hooks, vec![true, false, false]);
+        assert_eq!(runner.tasks[1].command, "composer run test");
+        assert_eq!(
+            runner.tasks[2].script.as_deref(),
+            Some("@test\nphpstan analyse src")
+        );
+    }
+
+    #[test]
+    fn test_parse_composer_only_hooks() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("composer.json");
+        fs::write(
+            &path,
+            r#"{"scripts": {"post-update-cmd": "php bin/cleanup.php"}}"#,
+        )
+        .unwrap();
+
+        let parser = ComposerJsonParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.tasks.len(), 1);
+        assert_eq!(runner.tasks[0].command, "composer run post-update-cmd");
+        assert_eq!(
+            runner.tasks[0].description.as_deref(),
+            Some("Composer event hook")
+        );
+    }
+}
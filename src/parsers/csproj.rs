@@ -1,12 +1,11 @@
 //! Parser for MSBuild .csproj/.fsproj/.vbproj files
 
-use std::fs;
 use std::path::Path;
 
 use quick_xml::de::from_str;
 use serde::Deserialize;
 
-use super::Parser;
+use super::{read_config, Parser};
 use crate::{RunnerType, ScanError, Task, TaskRunner};
 
 /// Standard dotnet CLI commands available for all projects
@@ -75,7 +74,7 @@ impl CsprojParser {
 
 impl Parser for CsprojParser {
     fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
-        let content = fs::read_to_string(path)?;
+        let content = read_config(path)?;
 
         let project: Project = from_str(&content).map_err(|e| ScanError::ParseError {
             path: path.to_path_buf(),
@@ -96,6 +95,7 @@ impl Parser for CsprojParser {
                 command: format!("dotnet {}", cmd),
                 description: Some(description.to_string()),
                 script: None,
+                ..Default::default()
             });
         }
 
@@ -121,6 +121,7 @@ impl Parser for CsprojParser {
                     command: format!("dotnet msbuild -t:{}", name),
                     description: Some(format!("Run MSBuild target '{}'", name)),
                     script: None,
+                    ..Default::default()
                 });
             }
         }
@@ -133,6 +134,7 @@ impl Parser for CsprojParser {
             config_path: path.to_path_buf(),
             runner_type: RunnerType::DotNet,
             tasks,
+            ..Default::default()
         }))
     }
 }
@@ -1,18 +1,17 @@
 //! Parser for deno.json / deno.jsonc (Deno tasks)
 
-use std::collections::HashMap;
-use std::fs;
 use std::path::Path;
 
+use indexmap::IndexMap;
 use serde::Deserialize;
 
 use crate::{RunnerType, ScanError, Task, TaskRunner};
 
-use super::Parser;
+use super::{jsonc, read_config, Parser};
 
 #[derive(Deserialize)]
 struct DenoJson {
-    tasks: Option<HashMap<String, TaskConfig>>,
+    tasks: Option<IndexMap<String, TaskConfig>>,
 }
 
 #[derive(Deserialize)]
@@ -27,76 +26,13 @@ enum TaskConfig {
 
 pub struct DenoJsonParser;
 
-impl DenoJsonParser {
-    /// Strip JSONC comments from content
-    fn strip_jsonc_comments(content: &str) -> String {
-        let mut result = String::with_capacity(content.len());
-        let mut in_string = false;
-        let mut in_line_comment = false;
-        let mut in_block_comment = false;
-        let mut chars = content.chars().peekable();
-
-        while let Some(c) = chars.next() {
-            if in_line_comment {
-                if c == '\n' {
-                    in_line_comment = false;
-                    result.push(c);
-                }
-                continue;
-            }
-
-            if in_block_comment {
-                if c == '*' && chars.peek() == Some(&'/') {
-                    chars.next();
-                    in_block_comment = false;
-                }
-                continue;
-            }
-
-            if in_string {
-                result.push(c);
-                if c == '\\' {
-                    if let Some(next) = chars.next() {
-                        result.push(next);
-                    }
-                } else if c == '"' {
-                    in_string = false;
-                }
-                continue;
-            }
-
-            // Not in string or comment
-            if c == '"' {
-                in_string = true;
-                result.push(c);
-            } else if c == '/' {
-                match chars.peek() {
-                    Some('/') => {
-                        chars.next();
-                        in_line_comment = true;
-                    }
-                    Some('*') => {
-                        chars.next();
-                        in_block_comment = true;
-                    }
-                    _ => result.push(c),
-                }
-            } else {
-                result.push(c);
-            }
-        }
-
-        result
-    }
-}
-
 impl Parser for DenoJsonParser {
     fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
-        let content = fs::read_to_string(path)?;
+        let content = read_config(path)?;
 
         // Handle JSONC (JSON with comments)
         let content = if path.extension().map(|e| e == "jsonc").unwrap_or(false) {
-            Self::strip_jsonc_comments(&content)
+            jsonc::strip_comments(&content)
         } else {
             content
         };
@@ -131,6 +67,7 @@ impl Parser for DenoJsonParser {
                     },
                     name,
                     script: Some(command_str),
+                    ..Default::default()
                 }
             })
             .collect();
@@ -143,6 +80,7 @@ impl Parser for DenoJsonParser {
             config_path: path.to_path_buf(),
             runner_type: RunnerType::Deno,
             tasks,
+            ..Default::default()
         }))
     }
 }
@@ -172,7 +110,9 @@ mod tests {
         let runner = parser.parse(&path).unwrap().unwrap();
 
         assert_eq!(runner.runner_type, RunnerType::Deno);
-        assert_eq!(runner.tasks.len(), 2);
+        // Tasks keep the order they're declared in
+        let names: Vec<_> = runner.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["dev", "build"]);
 
         let dev_task = runner.tasks.iter().find(|t| t.name == "dev").unwrap();
         assert_eq!(dev_task.command, "deno task dev");
@@ -0,0 +1,186 @@
+//! Parser for Dev Container configs (`.devcontainer/devcontainer.json`,
+//! `.devcontainer.json`)
+//!
+//! Each lifecycle command becomes a task named after its hook
+//! (`postCreateCommand` → `postCreate`). Commands given in object form run in
+//! parallel and are listed individually as `postCreate:<key>`. String
+//! commands are shell command lines, so they run through `sh -c`; array
+//! commands run as given.
+
+use std::path::Path;
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::{jsonc, read_config, Parser};
+
+/// Lifecycle hooks in the order they run, with what they're for
+const LIFECYCLE_HOOKS: &[(&str, &str)] = &[
+    (
+        "initializeCommand",
+        "Runs on the host before the container is created",
+    ),
+    (
+        "onCreateCommand",
+        "Runs when the container is first created",
+    ),
+    ("updateContentCommand", "Runs when new content is available"),
+    ("postCreateCommand", "Runs after the container is created"),
+    ("postStartCommand", "Runs each time the container starts"),
+    (
+        "postAttachCommand",
+        "Runs each time a tool attaches to the container",
+    ),
+];
+
+/// A lifecycle command: a shell string, an exec-form array, or named
+/// commands that run in parallel
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LifecycleCommand {
+    Shell(String),
+    Exec(Vec<String>),
+    Parallel(IndexMap<String, LifecycleCommand>),
+}
+
+impl LifecycleCommand {
+    /// The command to run and the command as written, if this is a single
+    /// command
+    fn command_line(&self) -> Option<(String, String)> {
+        match self {
+            LifecycleCommand::Shell(command) => {
+                let quoted = shlex::try_quote(command).ok()?;
+                Some((format!("sh -c {}", quoted), command.clone()))
+            }
+            LifecycleCommand::Exec(args) if !args.is_empty() => {
+                let line = shlex::try_join(args.iter().map(String::as_str)).ok()?;
+                Some((line.clone(), line))
+            }
+            _ => None,
+        }
+    }
+}
+
+pub struct DevcontainerParser;
+
+impl Parser for DevcontainerParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let content = read_config(path)?;
+        let content = jsonc::strip_trailing_commas(&jsonc::strip_comments(&content));
+
+        let mut config: IndexMap<String, serde_json::Value> = serde_json::from_str(&content)
+            .map_err(|e| ScanError::ParseError {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            })?;
+
+        let mut tasks = Vec::new();
+        for (key, description) in LIFECYCLE_HOOKS {
+            let Some(value) = config.swap_remove(*key) else {
+                continue;
+            };
+            let Ok(command) = serde_json::from_value::<LifecycleCommand>(value) else {
+                continue;
+            };
+            let name = key.trim_end_matches("Command");
+
+            let commands = match command {
+                LifecycleCommand::Parallel(commands) => commands
+                    .into_iter()
+                    .filter_map(|(label, command)| {
+                        let (command, script) = command.command_line()?;
+                        Some((format!("{}:{}", name, label), command, script))
+                    })
+                    .collect(),
+                command => command
+                    .command_line()
+                    .map(|(command, script)| vec![(name.to_string(), command, script)])
+                    .unwrap_or_default(),
+            };
+
+            for (name, command, script) in commands {
+                tasks.push(Task {
+                    name,
+                    script: Some(script),
+                    command,
+                    description: Some(description.to_string()),
+                    ..Default::default()
+                });
+            }
+        }
+
+        if tasks.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Devcontainer,
+            tasks,
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_devcontainer() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("devcontainer.json");
+        fs::write(
+            &path,
+            r#"{
+    // Generated by the Dev Containers extension
+    "name": "Rust",
+    "image": "mcr.microsoft.com/devcontainers/rust:1",
+    "postStartCommand": ["git", "fetch", "--depth", "1 0"],
+    "postCreateCommand": {
+        "deps": "cargo fetch",
+        "hooks": "pre-commit install && npm i",
+    },
+    "onCreateCommand": "rustup component add clippy", /* toolchain */
+}"#,
+        )
+        .unwrap();
+
+        let parser = DevcontainerParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::Devcontainer);
+        let tasks: Vec<_> = runner
+            .tasks
+            .iter()
+            .map(|t| (t.name.as_str(), t.command.as_str()))
+            .collect();
+        assert_eq!(
+            tasks,
+            vec![
+                ("onCreate", "sh -c 'rustup component add clippy'"),
+                ("postCreate:deps", "sh -c 'cargo fetch'"),
+                ("postCreate:hooks", "sh -c 'pre-commit install && npm i'"),
+                ("postStart", "git fetch --depth '1 0'"),
+            ]
+        );
+        assert_eq!(
+            runner.tasks[2].script.as_deref(),
+            Some("pre-commit install && npm i")
+        );
+    }
+
+    #[test]
+    fn test_skip_devcontainer_without_commands() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("devcontainer.json");
+        fs::write(&path, r#"{ "image": "ubuntu" }"#).unwrap();
+
+        let parser = DevcontainerParser;
+        assert!(parser.parse(&path).unwrap().is_none());
+    }
+}
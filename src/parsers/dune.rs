@@ -0,0 +1,240 @@
+//! Parser for dune-project (Dune, OCaml)
+//!
+//! The standard dune commands are always listed. Executables come from
+//! `(executable ...)` and `(executables ...)` stanzas in the `dune` files
+//! next to `dune-project` and one folder down (`bin/dune`, `src/dune`); those
+//! are found with a targeted scan for the `name`/`public_name` fields rather
+//! than a full S-expression parser.
+
+use std::fs;
+use std::path::Path;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::{read_config, Parser};
+
+/// Standard commands listed for every project
+const STANDARD_COMMANDS: &[(&str, &str)] = &[
+    ("build", "Build the project"),
+    ("test", "Run tests"),
+    ("runtest", "Run the runtest alias"),
+];
+
+/// An executable declared in a `dune` file
+#[derive(Debug, PartialEq)]
+struct Executable {
+    name: String,
+    public_name: Option<String>,
+}
+
+pub struct DuneParser;
+
+impl DuneParser {
+    /// Drop `;` comments, leaving string contents alone
+    fn strip_comments(content: &str) -> String {
+        content
+            .lines()
+            .map(|line| {
+                let mut in_string = false;
+                let end = line
+                    .char_indices()
+                    .find(|&(_, c)| {
+                        if c == '"' {
+                            in_string = !in_string;
+                        }
+                        c == ';' && !in_string
+                    })
+                    .map_or(line.len(), |(i, _)| i);
+                &line[..end]
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The text of each balanced `(<head> ...)` form
+    fn stanzas<'a>(content: &'a str, head: &str) -> Vec<&'a str> {
+        let open = format!("({}", head);
+        let mut stanzas = Vec::new();
+        let mut rest = content;
+        let mut offset = 0;
+        while let Some(start) = rest.find(&open).map(|i| offset + i) {
+            let after = start + open.len();
+            offset = after;
+            rest = &content[after..];
+            // `(executables` isn't `(executable`
+            if !rest.starts_with(char::is_whitespace) {
+                continue;
+            }
+            let mut depth = 0;
+            for (i, c) in content[start..].char_indices() {
+                match c {
+                    '(' => depth += 1,
+                    ')' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            stanzas.push(&content[start..start + i + 1]);
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        stanzas
+    }
+
+    /// The atoms of a `(<field> a b ...)` entry in a stanza
+    fn field(stanza: &str, field: &str) -> Vec<String> {
+        let Some(body) = Self::stanzas(stanza, field).into_iter().next() else {
+            return Vec::new();
+        };
+        body[field.len() + 1..body.len() - 1]
+            .split_whitespace()
+            .map(|atom| atom.trim_matches('"').to_string())
+            .collect()
+    }
+
+    /// Executables declared in one `dune` file
+    fn parse_executables(content: &str) -> Vec<Executable> {
+        let content = Self::strip_comments(content);
+        let mut executables = Vec::new();
+
+        for stanza in Self::stanzas(&content, "executable") {
+            if let Some(name) = Self::field(stanza, "name").into_iter().next() {
+                executables.push(Executable {
+                    name,
+                    public_name: Self::field(stanza, "public_name").into_iter().next(),
+                });
+            }
+        }
+        for stanza in Self::stanzas(&content, "executables") {
+            let mut public_names = Self::field(stanza, "public_names").into_iter();
+            for name in Self::field(stanza, "names") {
+                // `-` means the executable isn't installed
+                let public_name = public_names.next().filter(|p| p != "-");
+                executables.push(Executable { name, public_name });
+            }
+        }
+        executables
+    }
+
+    /// `dune` files next to `dune-project` and one folder down, as
+    /// (folder relative to the project, contents)
+    fn dune_files(dir: &Path) -> Vec<(String, String)> {
+        let mut files = Vec::new();
+        if let Ok(content) = read_config(&dir.join("dune")) {
+            files.push((String::new(), content));
+        }
+
+        let mut subdirs: Vec<_> = fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .filter(|name| !name.starts_with('.') && name != "_build")
+            .collect();
+        subdirs.sort();
+        for subdir in subdirs {
+            if let Ok(content) = read_config(&dir.join(&subdir).join("dune")) {
+                files.push((subdir, content));
+            }
+        }
+        files
+    }
+}
+
+impl Parser for DuneParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        // Only the presence of dune-project matters; make sure it's readable
+        fs::metadata(path)?;
+
+        let mut tasks: Vec<Task> = STANDARD_COMMANDS
+            .iter()
+            .map(|(name, description)| Task {
+                name: name.to_string(),
+                command: format!("dune {}", name),
+                description: Some(description.to_string()),
+                script: None,
+                ..Default::default()
+            })
+            .collect();
+
+        let dir = path.parent().unwrap_or(Path::new("."));
+        for (subdir, content) in Self::dune_files(dir) {
+            for executable in Self::parse_executables(&content) {
+                // Installed executables run by public name, others by path
+                let target = match executable.public_name {
+                    Some(public_name) => public_name,
+                    None if subdir.is_empty() => format!("./{}.exe", executable.name),
+                    None => format!("./{}/{}.exe", subdir, executable.name),
+                };
+                tasks.push(Task {
+                    name: executable.name,
+                    command: format!("dune exec {}", target),
+                    description: None,
+                    script: None,
+                    ..Default::default()
+                });
+            }
+        }
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Dune,
+            tasks,
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_dune_project() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("dune-project");
+        fs::write(&path, "(lang dune 3.0)\n(name app)\n").unwrap();
+        fs::write(
+            dir.path().join("dune"),
+            "(executable (name gen))\n; (executable (name ignored))\n",
+        )
+        .unwrap();
+        fs::create_dir(dir.path().join("bin")).unwrap();
+        fs::write(
+            dir.path().join("bin/dune"),
+            r#"
+(executable
+ (public_name app)
+ (name main)
+ (libraries app.lib))
+
+(executables
+ (names tool bench)
+ (public_names app-tool -))
+"#,
+        )
+        .unwrap();
+
+        let parser = DuneParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::Dune);
+        let commands: Vec<_> = runner.tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            vec![
+                "dune build",
+                "dune test",
+                "dune runtest",
+                "dune exec ./gen.exe",
+                "dune exec app",
+                "dune exec app-tool",
+                "dune exec ./bin/bench.exe",
+            ]
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Parser for GitHub Actions workflows (`.github/workflows/*.yml`), run locally with `act`
+//!
+//! Each job becomes an `act -W .github/workflows/<file> -j <job-id>` task;
+//! without `-W`, act would run the job in every workflow that has one with
+//! that id. YAML files in the workflows folder without a `jobs` map aren't
+//! workflows and are skipped.
+
+use std::path::Path;
+
+use indexmap::IndexMap;
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::{read_config, Parser};
+
+#[derive(Deserialize)]
+struct Workflow {
+    name: Option<String>,
+    jobs: Option<IndexMap<String, Job>>,
+}
+
+#[derive(Deserialize)]
+struct Job {
+    name: Option<String>,
+}
+
+/// Just enough of a document to tell whether it's a workflow
+#[derive(Deserialize)]
+struct Probe {
+    jobs: Option<IgnoredAny>,
+}
+
+/// Whether a path is a workflow file: `.github/workflows/<name>.yml` or `.yaml`
+pub(crate) fn is_workflow_file(path: &Path) -> bool {
+    let is_yaml = path
+        .extension()
+        .is_some_and(|ext| ext == "yml" || ext == "yaml");
+    let parent = path.parent();
+    let in_workflows = parent
+        .and_then(Path::file_name)
+        .is_some_and(|name| name == "workflows")
+        && parent
+            .and_then(Path::parent)
+            .and_then(Path::file_name)
+            .is_some_and(|name| name == ".github");
+    is_yaml && in_workflows
+}
+
+pub struct GithubActionsParser;
+
+impl GithubActionsParser {
+    /// Check for a top-level `jobs` key before parsing, so other YAML kept
+    /// in the folder (label lists, configs) isn't reported as a parse error.
+    /// A document that isn't valid YAML counts as a workflow if a line starts
+    /// with `jobs:`, so broken workflows are still reported.
+    fn has_jobs(content: &str) -> bool {
+        match serde_saphyr::from_str::<Probe>(content) {
+            Ok(probe) => probe.jobs.is_some(),
+            Err(_) => content.lines().any(|line| line.starts_with("jobs:")),
+        }
+    }
+}
+
+impl Parser for GithubActionsParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let content = read_config(path)?;
+        if !Self::has_jobs(&content) {
+            return Ok(None);
+        }
+
+        let workflow: Workflow =
+            serde_saphyr::from_str(&content).map_err(|e| ScanError::ParseError {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            })?;
+
+        let Some(jobs) = workflow.jobs.filter(|jobs| !jobs.is_empty()) else {
+            return Ok(None);
+        };
+
+        // Tasks run from the repository root (see `work_dir` in main.rs)
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let workflow_path = format!(".github/workflows/{}", file_name);
+
+        let tasks = jobs
+            .into_iter()
+            .map(|(id, job)| {
+                // Like the GitHub UI: "CI / Build"
+                let description = match (&workflow.name, job.name) {
+                    (Some(workflow), Some(job)) => Some(format!("{} / {}", workflow, job)),
+                    (Some(workflow), None) => Some(workflow.clone()),
+                    (None, job) => job,
+                };
+                Task {
+                    command: format!("act -W {} -j {}", workflow_path, id),
+                    name: id,
+                    description,
+                    script: None,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::GithubActions,
+            tasks,
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_workflow_jobs() {
+        let dir = TempDir::new().unwrap();
+        let workflows = dir.path().join(".github/workflows");
+        fs::create_dir_all(&workflows).unwrap();
+        let path = workflows.join("ci.yml");
+        fs::write(
+            &path,
+            r#"
+name: CI
+on:
+  push:
+    branches: [main]
+jobs: # run on every push
+  test:
+    name: Test suite
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - run: cargo clippy
+"#,
+        )
+        .unwrap();
+
+        assert!(is_workflow_file(&path));
+        let runner = GithubActionsParser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::GithubActions);
+        let commands: Vec<_> = runner.tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            vec![
+                "act -W .github/workflows/ci.yml -j test",
+                "act -W .github/workflows/ci.yml -j lint"
+            ]
+        );
+        assert_eq!(
+            runner.tasks[0].description.as_deref(),
+            Some("CI / Test suite")
+        );
+        assert_eq!(runner.tasks[1].description.as_deref(), Some("CI"));
+    }
+
+    #[test]
+    fn test_skip_non_workflow_yaml() {
+        let dir = TempDir::new().unwrap();
+        let workflows = dir.path().join(".github/workflows");
+        fs::create_dir_all(&workflows).unwrap();
+        let path = workflows.join("labels.yml");
+        fs::write(&path, "- name: bug\n  color: d73a4a\n").unwrap();
+        let config = workflows.join("config.yaml");
+        fs::write(&config, "retries: 3\n").unwrap();
+
+        assert!(GithubActionsParser.parse(&path).unwrap().is_none());
+        assert!(GithubActionsParser.parse(&config).unwrap().is_none());
+
+        // Broken workflows are still reported
+        let broken = workflows.join("broken.yml");
+        fs::write(&broken, "jobs:\n  test: [unclosed\n").unwrap();
+        assert!(GithubActionsParser.parse(&broken).is_err());
+        assert!(!is_workflow_file(Path::new(".github/ci.yml")));
+        assert!(!is_workflow_file(Path::new("workflows/ci.yml")));
+    }
+}
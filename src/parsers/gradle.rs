@@ -0,0 +1,205 @@
+//! Simple parser for Gradle build scripts (`build.gradle`, `build.gradle.kts`)
+//!
+//! Every Gradle project has the base lifecycle tasks, so those are always
+//! listed. Custom tasks are picked up line by line from `tasks.register("name")`
+//! (Groovy and Kotlin DSL, including `tasks.register<Type>("name")`) and
+//! Groovy's `task name { ... }` declarations.
+
+use std::path::Path;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::{read_config, Parser};
+
+/// Lifecycle tasks the Gradle base plugin always provides
+const LIFECYCLE_TASKS: &[(&str, &str)] = &[
+    ("build", "Assemble and test the project"),
+    ("test", "Run unit tests"),
+    ("clean", "Delete the build directory"),
+    ("assemble", "Assemble the outputs of the project"),
+    ("check", "Run all checks"),
+];
+
+pub struct GradleParser;
+
+impl GradleParser {
+    /// Parse custom task names from build script content, in declaration order
+    fn parse_custom_tasks(content: &str) -> Vec<String> {
+        let mut tasks: Vec<String> = Vec::new();
+        for line in content.lines() {
+            let line = line.trim();
+            let name = match line.strip_prefix("tasks.register") {
+                Some(rest) => Self::registered_name(rest),
+                None => line.strip_prefix("task ").and_then(Self::declared_name),
+            };
+            if let Some(name) = name {
+                let known = LIFECYCLE_TASKS.iter().any(|(task, _)| *task == name)
+                    || tasks.iter().any(|task| task == name);
+                if !known {
+                    tasks.push(name.to_string());
+                }
+            }
+        }
+        tasks
+    }
+
+    /// Name from the arguments of `tasks.register`: `("name"...` or `<Type>("name"...`
+    fn registered_name(rest: &str) -> Option<&str> {
+        let rest = match rest.strip_prefix('<') {
+            Some(generic) => generic.split_once('>')?.1,
+            None => rest,
+        };
+        let rest = rest.trim_start().strip_prefix('(')?.trim_start();
+        let quote = rest.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+        let (name, _) = rest[1..].split_once(quote)?;
+        Self::valid_name(name).then_some(name)
+    }
+
+    /// Name from a Groovy `task name {`, `task name(type: Copy) {` or `task name`
+    fn declared_name(rest: &str) -> Option<&str> {
+        let rest = rest.trim_start();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '{' || c == '(')
+            .unwrap_or(rest.len());
+        let name = &rest[..end];
+        Self::valid_name(name).then_some(name)
+    }
+
+    /// Task names are identifiers (letters, digits, `_`, `-`)
+    fn valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-'))
+    }
+
+    /// The Gradle wrapper if one sits next to the build script, else `gradle`
+    fn command_prefix(dir: &Path) -> &'static str {
+        if dir.join("gradlew").is_file() {
+            "./gradlew"
+        } else {
+            "gradle"
+        }
+    }
+}
+
+impl Parser for GradleParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let content = read_config(path)?;
+        let prefix = Self::command_prefix(path.parent().unwrap_or(Path::new(".")));
+
+        let lifecycle = LIFECYCLE_TASKS.iter().map(|(name, description)| Task {
+            name: name.to_string(),
+            command: format!("{} {}", prefix, name),
+            description: Some(description.to_string()),
+            script: None,
+            ..Default::default()
+        });
+        let custom = Self::parse_custom_tasks(&content)
+            .into_iter()
+            .map(|name| Task {
+                command: format!("{} {}", prefix, name),
+                name,
+                description: None,
+                script: None,
+                ..Default::default()
+            });
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Gradle,
+            tasks: lifecycle.chain(custom).collect(),
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_groovy_build_script() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("build.gradle");
+        fs::write(
+            &path,
+            r#"
+plugins {
+    id 'java'
+}
+
+task hello {
+    doLast { println 'Hello' }
+}
+
+task copyDocs(type: Copy) {
+    from 'docs'
+}
+
+tasks.register('integrationTest', Test) {
+    useJUnitPlatform()
+}
+
+tasks.register("test") {
+}
+"#,
+        )
+        .unwrap();
+
+        let parser = GradleParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::Gradle);
+        let names: Vec<_> = runner.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "build",
+                "test",
+                "clean",
+                "assemble",
+                "check",
+                "hello",
+                "copyDocs",
+                "integrationTest"
+            ]
+        );
+        assert_eq!(runner.tasks[0].command, "gradle build");
+        assert_eq!(runner.tasks[5].command, "gradle hello");
+    }
+
+    #[test]
+    fn test_parse_kotlin_build_script_with_wrapper() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("gradlew"), "#!/bin/sh\n").unwrap();
+        let path = dir.path().join("build.gradle.kts");
+        fs::write(
+            &path,
+            r#"
+tasks.register<Exec>("runServer") {
+    commandLine("java", "-jar", "server.jar")
+}
+tasks.register("printVersion") {
+    doLast { println(version) }
+}
+"#,
+        )
+        .unwrap();
+
+        let parser = GradleParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        let custom: Vec<_> = runner.tasks[LIFECYCLE_TASKS.len()..]
+            .iter()
+            .map(|t| t.command.as_str())
+            .collect();
+        assert_eq!(
+            custom,
+            vec!["./gradlew runServer", "./gradlew printVersion"]
+        );
+        assert_eq!(runner.tasks[0].command, "./gradlew build");
+    }
+}
@@ -0,0 +1,110 @@
+//! Helpers for JSON with comments (deno.jsonc, devcontainer.json)
+
+/// Strip `//` and `/* */` comments, leaving strings untouched
+pub(crate) fn strip_comments(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut in_string = false;
+    let mut in_line_comment = false;
+    let mut in_block_comment = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_line_comment {
+            if c == '\n' {
+                in_line_comment = false;
+                result.push(c);
+            }
+            continue;
+        }
+
+        if in_block_comment {
+            if c == '*' && chars.peek() == Some(&'/') {
+                chars.next();
+                in_block_comment = false;
+            }
+            continue;
+        }
+
+        if in_string {
+            result.push(c);
+            if c == '\\' {
+                if let Some(next) = chars.next() {
+                    result.push(next);
+                }
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        // Not in string or comment
+        if c == '"' {
+            in_string = true;
+            result.push(c);
+        } else if c == '/' {
+            match chars.peek() {
+                Some('/') => {
+                    chars.next();
+                    in_line_comment = true;
+                }
+                Some('*') => {
+                    chars.next();
+                    in_block_comment = true;
+                }
+                _ => result.push(c),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Remove trailing commas before `}` or `]`, which JSONC allows.
+/// Expects comments to have been stripped already.
+pub(crate) fn strip_trailing_commas(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut in_string = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            result.push(c);
+            if c == '\\' {
+                if let Some(next) = chars.next() {
+                    result.push(next);
+                }
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        if c == ',' {
+            let rest = chars.clone().find(|c| !c.is_whitespace());
+            if matches!(rest, Some('}') | Some(']')) {
+                continue;
+            }
+        } else if c == '"' {
+            in_string = true;
+        }
+        result.push(c);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_trailing_commas() {
+        let content = r#"{ "a": [1, 2,], "b": "x,}", }"#;
+        assert_eq!(
+            strip_trailing_commas(content),
+            r#"{ "a": [1, 2], "b": "x,}" }"#
+        );
+    }
+}
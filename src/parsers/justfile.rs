@@ -2,12 +2,115 @@
 
 use std::path::Path;
 
+use just::summary::{Expression, Parameter, ParameterKind};
+
 use crate::{RunnerType, ScanError, Task, TaskRunner};
 
-use super::Parser;
+use super::{read_config, Parser};
 
 pub struct JustfileParser;
 
+impl JustfileParser {
+    /// Build the command line for a recipe, including its parameters.
+    ///
+    /// Parameters with a string literal default are shown as `name=value`,
+    /// the value shell-quoted when needed (`name='a b'`, `name=''`) so the
+    /// command splits back into the same arguments when run. Required
+    /// parameters are `<name>` placeholders (`<name>...` for `+name`).
+    /// Optional variadics and defaults we can't evaluate are left out.
+    fn invocation(name: &str, parameters: &[Parameter]) -> String {
+        let mut command = format!("just {}", name);
+
+        for param in parameters {
+            match (&param.default, param.kind) {
+                (Some(Expression::String { text }), _) => match shlex::try_quote(text) {
+                    Ok(value) => command.push_str(&format!(" {}", value)),
+                    Err(_) => break,
+                },
+                (Some(_), _) => break,
+                (None, ParameterKind::Star) => {}
+                (None, ParameterKind::Singular) => {
+                    command.push_str(&format!(" <{}>", param.name));
+                }
+                (None, ParameterKind::Plus) => {
+                    command.push_str(&format!(" <{}>...", param.name));
+                }
+            }
+        }
+
+        command
+    }
+
+    /// Line on which a recipe is declared, so tasks can keep justfile order
+    /// (the summary API returns recipes sorted by name)
+    fn declaration_line(content: &str, name: &str) -> usize {
+        content
+            .lines()
+            .position(|line| {
+                let line = line.strip_prefix('@').unwrap_or(line);
+                line.strip_prefix(name).is_some_and(|rest| {
+                    rest.starts_with([':', ' ', '\t']) && !rest.trim_start().starts_with(":=")
+                })
+            })
+            .unwrap_or(usize::MAX)
+    }
+
+    /// A recipe's doc: a `[doc("...")]` attribute, or else the `# comment`
+    /// line right above the recipe (attribute lines in between are skipped),
+    /// which is what `just --list` shows
+    fn recipe_doc(content: &str, name: &str) -> Option<String> {
+        let line = Self::declaration_line(content, name);
+        let above: Vec<&str> = content.lines().take(line).collect();
+
+        let mut comment = None;
+        for line in above.iter().rev().map(|line| line.trim()) {
+            if let Some(attributes) = line.strip_prefix('[') {
+                if let Some(doc) = attributes
+                    .split_once("doc(")
+                    .and_then(|(_, rest)| rest.split_once(')'))
+                    .map(|(doc, _)| doc.trim().trim_matches(['"', '\'']))
+                {
+                    return Some(doc.to_string()).filter(|doc| !doc.is_empty());
+                }
+                continue;
+            }
+            if comment.is_none() && !line.starts_with("#!") {
+                comment = line.strip_prefix('#').map(|text| text.trim().to_string());
+            }
+            break;
+        }
+        comment.filter(|doc| !doc.is_empty())
+    }
+
+    /// A recipe's body, dedented with the `@`/`-` line prefixes removed,
+    /// so it can be read or run by hand when `just` isn't installed
+    fn recipe_body(content: &str, name: &str) -> Option<String> {
+        let start = Self::declaration_line(content, name).checked_add(1)?;
+        let mut lines: Vec<&str> = content
+            .lines()
+            .skip(start)
+            .take_while(|line| line.trim().is_empty() || line.starts_with([' ', '\t']))
+            .collect();
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+
+        let indent = lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()?;
+        let body: Vec<&str> = lines
+            .iter()
+            .map(|line| {
+                let line = line.get(indent..).unwrap_or("");
+                line.strip_prefix(['@', '-']).unwrap_or(line)
+            })
+            .collect();
+        Some(body.join("\n"))
+    }
+}
+
 impl Parser for JustfileParser {
     fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
         // Use just's summary API to parse the justfile
@@ -26,6 +129,7 @@ impl Parser for JustfileParser {
             }
         };
 
+        let content = read_config(path).ok();
         let mut tasks = Vec::new();
 
         for (name, recipe) in &summary.recipes {
@@ -36,9 +140,14 @@ impl Parser for JustfileParser {
 
             tasks.push(Task {
                 name: name.clone(),
-                command: format!("just {}", name),
-                description: None,
-                script: None, // Just recipes are more complex
+                command: Self::invocation(name, &recipe.parameters),
+                description: content
+                    .as_deref()
+                    .and_then(|content| Self::recipe_doc(content, name)),
+                script: content
+                    .as_deref()
+                    .and_then(|content| Self::recipe_body(content, name)),
+                ..Default::default()
             });
         }
 
@@ -46,10 +155,15 @@ impl Parser for JustfileParser {
             return Ok(None);
         }
 
+        if let Some(content) = &content {
+            tasks.sort_by_cached_key(|task| Self::declaration_line(content, &task.name));
+        }
+
         Ok(Some(TaskRunner {
             config_path: path.to_path_buf(),
             runner_type: RunnerType::Just,
             tasks,
+            ..Default::default()
         }))
     }
 }
@@ -83,8 +197,10 @@ _helper:
 internal:
     echo "internal"
 
-deploy env="prod":
-    ./deploy.sh {{env}}
+[no-cd]
+[doc('Ship it')]
+deploy env region="eu":
+    ./deploy.sh {{env}} {{region}}
 "#,
         )
         .unwrap();
@@ -102,8 +218,96 @@ deploy env="prod":
         assert!(!names.contains(&"_helper"));
         assert!(!names.contains(&"internal"));
 
+        // Recipes keep the order they're declared in
+        assert_eq!(names, vec!["build", "test", "deploy"]);
+
         let build_task = runner.tasks.iter().find(|t| t.name == "build").unwrap();
         assert_eq!(build_task.command, "just build");
+        assert_eq!(build_task.description.as_deref(), Some("Build the project"));
+        let test_task = runner.tasks.iter().find(|t| t.name == "test").unwrap();
+        assert_eq!(test_task.description.as_deref(), Some("Run tests"));
+
+        // Required parameters get placeholders so Edit mode has something to fill in
+        let deploy_task = runner.tasks.iter().find(|t| t.name == "deploy").unwrap();
+        assert_eq!(deploy_task.command, "just deploy <env> eu");
+        assert_eq!(deploy_task.description.as_deref(), Some("Ship it"));
+    }
+
+    #[test]
+    fn test_recipe_body_as_script() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("justfile");
+        fs::write(
+            &path,
+            "release version:\n    @echo releasing {{version}}\n    -git tag v{{version}}\n\npublish:\n    #!/usr/bin/env bash\n    if true; then\n      cargo publish\n    fi\n\nlint:\n\tcargo clippy\n",
+        )
+        .unwrap();
+
+        let runner = JustfileParser.parse(&path).unwrap().unwrap();
+        let script = |name: &str| {
+            runner
+                .tasks
+                .iter()
+                .find(|t| t.name == name)
+                .and_then(|t| t.script.clone())
+        };
+        assert_eq!(
+            script("release").as_deref(),
+            Some("echo releasing {{version}}\ngit tag v{{version}}")
+        );
+        assert_eq!(
+            script("publish").as_deref(),
+            Some("#!/usr/bin/env bash\nif true; then\n  cargo publish\nfi")
+        );
+        assert_eq!(script("lint").as_deref(), Some("cargo clippy"));
+    }
+
+    #[test]
+    fn test_parse_recipe_parameters() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("justfile");
+        fs::write(
+            &path,
+            r#"
+greet name greeting="hello there":
+    echo "{{greeting}} {{name}}"
+
+tag suffix="" note='say "hi"':
+    echo {{suffix}} {{note}}
+
+build target=arch() mode="release":
+    cargo build --target {{target}} --{{mode}}
+
+lint +files:
+    eslint {{files}}
+
+fmt *args:
+    cargo fmt {{args}}
+"#,
+        )
+        .unwrap();
+
+        let parser = JustfileParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        let command = |name: &str| {
+            runner
+                .tasks
+                .iter()
+                .find(|t| t.name == name)
+                .unwrap()
+                .command
+                .clone()
+        };
+        assert_eq!(command("greet"), "just greet <name> 'hello there'");
+        assert_eq!(command("tag"), r#"just tag '' 'say "hi"'"#);
+        assert_eq!(
+            shlex::split(&command("tag")).unwrap(),
+            ["just", "tag", "", r#"say "hi""#]
+        );
+        assert_eq!(command("build"), "just build");
+        assert_eq!(command("lint"), "just lint <files>...");
+        assert_eq!(command("fmt"), "just fmt");
     }
 
     #[test]
@@ -0,0 +1,217 @@
+//! Implied lint/format tasks for ESLint, Prettier and Biome config files
+//!
+//! Repos that lean on editor integration often have a linter config but no
+//! script to run it. When the sibling package.json doesn't already cover the
+//! tool, the config file yields a single inferred task (`npx eslint .`, or
+//! `bunx eslint .` in Bun projects).
+//! The scanner only dispatches here when implied tasks are enabled.
+
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::{read_config, PackageJsonParser, Parser};
+
+/// A tool whose config file implies a task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tool {
+    Eslint,
+    Prettier,
+    Biome,
+}
+
+impl Tool {
+    /// The tool a config file belongs to, if any
+    fn from_file_name(name: &str) -> Option<Self> {
+        let matches = |dotfile: &str, config: &str| {
+            name == dotfile
+                || name.starts_with(&format!("{}.", dotfile))
+                || name.starts_with(&format!("{}.", config))
+        };
+        if matches(".eslintrc", "eslint.config") {
+            Some(Tool::Eslint)
+        } else if matches(".prettierrc", "prettier.config") {
+            Some(Tool::Prettier)
+        } else if name == "biome.json" || name == "biome.jsonc" {
+            Some(Tool::Biome)
+        } else {
+            None
+        }
+    }
+
+    /// Implied task name and the arguments of its command. The arguments
+    /// name the npm package, as npx/bunx fetch it when it isn't installed
+    /// (Biome is `@biomejs/biome`; the `biome` package is something else).
+    fn task(self) -> (&'static str, &'static str) {
+        match self {
+            Tool::Eslint => ("lint", "eslint ."),
+            Tool::Prettier => ("format", "prettier --write ."),
+            Tool::Biome => ("check", "@biomejs/biome check ."),
+        }
+    }
+
+    /// Program name as it appears in package.json scripts
+    fn program(self) -> &'static str {
+        match self {
+            Tool::Eslint => "eslint",
+            Tool::Prettier => "prettier",
+            Tool::Biome => "biome",
+        }
+    }
+}
+
+/// Whether a file name is a config file that implies a lint/format task
+pub(crate) fn is_lint_config(name: &str) -> bool {
+    Tool::from_file_name(name).is_some()
+}
+
+/// The package.json in `dir`, if there is a readable one
+fn read_package(dir: &Path) -> Option<Value> {
+    let content = read_config(&dir.join("package.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Whether package.json already has a script for the tool: one named like
+/// the implied task, or one that invokes the tool
+fn has_script_for(package: &Value, tool: Tool) -> bool {
+    let Some(scripts) = package.get("scripts").and_then(Value::as_object) else {
+        return false;
+    };
+
+    let (task_name, _) = tool.task();
+    scripts.iter().any(|(name, command)| {
+        name == task_name
+            || command
+                .as_str()
+                .is_some_and(|c| c.split_whitespace().any(|word| word == tool.program()))
+    })
+}
+
+pub struct LintConfigParser;
+
+impl Parser for LintConfigParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let Some(file_name) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            return Ok(None);
+        };
+        let Some(tool) = Tool::from_file_name(&file_name) else {
+            return Ok(None);
+        };
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let package = read_package(dir);
+        if package.as_ref().is_some_and(|p| has_script_for(p, tool)) {
+            return Ok(None);
+        }
+        let package_manager = package
+            .as_ref()
+            .and_then(|p| p.get("packageManager"))
+            .and_then(Value::as_str);
+        let runner_type = PackageJsonParser::detect_runner_type(package_manager, dir);
+
+        let (name, args) = tool.task();
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Script,
+            tasks: vec![Task {
+                name: name.to_string(),
+                command: PackageJsonParser::exec_command(runner_type, args),
+                description: Some(format!("Implied by {}", file_name)),
+                script: None,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_config_file_names() {
+        assert!(is_lint_config(".eslintrc"));
+        assert!(is_lint_config(".eslintrc.json"));
+        assert!(is_lint_config("eslint.config.mjs"));
+        assert!(is_lint_config(".prettierrc.yaml"));
+        assert!(is_lint_config("prettier.config.js"));
+        assert!(is_lint_config("biome.json"));
+        assert!(!is_lint_config(".eslintignore"));
+        assert!(!is_lint_config("package.json"));
+    }
+
+    #[test]
+    fn test_implied_task_unless_scripted() {
+        let dir = TempDir::new().unwrap();
+        let eslint = dir.path().join(".eslintrc.json");
+        let prettier = dir.path().join(".prettierrc");
+        fs::write(&eslint, "{}").unwrap();
+        fs::write(&prettier, "{}").unwrap();
+
+        // No package.json: both tools imply a task
+        let runner = LintConfigParser.parse(&eslint).unwrap().unwrap();
+        assert_eq!(runner.runner_type, RunnerType::Script);
+        assert_eq!(runner.tasks[0].name, "lint");
+        assert_eq!(runner.tasks[0].command, "npx eslint .");
+        assert_eq!(
+            runner.tasks[0].description.as_deref(),
+            Some("Implied by .eslintrc.json")
+        );
+
+        // A script invoking eslint covers it; prettier is still implied
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"check": "eslint src && tsc"}}"#,
+        )
+        .unwrap();
+        assert!(LintConfigParser.parse(&eslint).unwrap().is_none());
+        let runner = LintConfigParser.parse(&prettier).unwrap().unwrap();
+        assert_eq!(runner.tasks[0].command, "npx prettier --write .");
+
+        // So does a script with the implied task's name
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"format": "dprint fmt"}}"#,
+        )
+        .unwrap();
+        assert!(LintConfigParser.parse(&prettier).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_bun_projects_never_use_npx() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("bun.lock"), "{}").unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"dev": "bun --hot src/index.ts"}}"#,
+        )
+        .unwrap();
+        let eslint = dir.path().join("eslint.config.js");
+        let biome = dir.path().join("biome.json");
+        fs::write(&eslint, "export default [];").unwrap();
+        fs::write(&biome, "{}").unwrap();
+
+        let package = PackageJsonParser
+            .parse(&dir.path().join("package.json"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(package.runner_type, RunnerType::Bun);
+        let mut commands: Vec<_> = package.tasks.into_iter().map(|t| t.command).collect();
+        for config in [&eslint, &biome] {
+            let runner = LintConfigParser.parse(config).unwrap().unwrap();
+            commands.extend(runner.tasks.into_iter().map(|t| t.command));
+        }
+
+        assert!(commands.contains(&"bunx eslint .".to_string()));
+        assert!(commands.contains(&"bunx @biomejs/biome check .".to_string()));
+        assert!(
+            commands.iter().all(|c| !c.contains("npx ")),
+            "{:?}",
+            commands
+        );
+    }
+}
@@ -1,33 +1,76 @@
 //! Simple parser for Makefile targets (thread-safe, no external deps)
 
-use std::fs;
 use std::path::Path;
 
-use crate::{RunnerType, ScanError, Task, TaskRunner};
+use crate::{RunnerType, ScanError, Task, TaskMetadata, TaskRunner};
 
-use super::Parser;
+use super::{read_config, Parser};
+
+/// Programs a wrapper target may invoke to be flattened to its recipe
+const KNOWN_RUNNERS: &[&str] = &[
+    "npm",
+    "yarn",
+    "pnpm",
+    "bun",
+    "npx",
+    "cargo",
+    "just",
+    "deno",
+    "poetry",
+    "pdm",
+    "uv",
+    "mvn",
+    "./mvnw",
+    "gradle",
+    "./gradlew",
+    "dotnet",
+    "flutter",
+    "dart",
+    "turbo",
+    "go",
+    "task",
+];
 
 pub struct MakefileParser;
 
+/// A target with its recipe lines, prerequisites and `##` description
+#[derive(Default)]
+struct Target {
+    name: String,
+    recipe: Vec<String>,
+    description: Option<String>,
+    prerequisites: Vec<String>,
+}
+
 impl MakefileParser {
     /// Check if a target name should be exposed as a runnable task
     fn is_runnable_target(name: &str) -> bool {
         !name.starts_with('.') && !name.starts_with('_') && !name.contains('%') && !name.is_empty()
     }
 
-    /// Parse targets from makefile content
-    fn parse_targets(content: &str) -> Vec<String> {
-        let mut targets = Vec::new();
+    /// Parse targets and their recipe lines from makefile content
+    fn parse_targets(content: &str) -> Vec<Target> {
+        let mut targets: Vec<Target> = Vec::new();
+        // Indices of the targets the following recipe lines belong to
+        let mut current: Vec<usize> = Vec::new();
         for line in content.lines() {
-            // Skip empty lines, comments, and lines starting with whitespace (recipes)
+            // Recipe lines belong to the most recent rule
+            if let Some(recipe) = line.strip_prefix('\t') {
+                let recipe = recipe.trim();
+                if !recipe.is_empty() && !recipe.starts_with('#') {
+                    for &i in &current {
+                        targets[i].recipe.push(recipe.to_string());
+                    }
+                }
+                continue;
+            }
+
+            // Skip empty lines, comments, and other indented lines
             let trimmed = line.trim_start();
-            if trimmed.is_empty()
-                || trimmed.starts_with('#')
-                || line.starts_with('\t')
-                || line.starts_with(' ')
-            {
+            if trimmed.is_empty() || trimmed.starts_with('#') || line.starts_with(' ') {
                 continue;
             }
+            current.clear();
             // Look for target definitions: "target:" or "target: deps"
             if let Some(colon_pos) = line.find(':') {
                 // Skip := and ::= (variable assignments)
@@ -35,11 +78,43 @@ impl MakefileParser {
                     continue;
                 }
                 let target_part = &line[..colon_pos];
+                // "build: deps ## Build the project" documents the target
+                let (rule, description) = match line[colon_pos + 1..].split_once("##") {
+                    Some((rule, text)) => (rule, Some(text.trim()).filter(|t| !t.is_empty())),
+                    None => (&line[colon_pos + 1..], None),
+                };
+                // Order-only prerequisites (after `|`) still run first
+                let prerequisites: Vec<&str> = rule
+                    .split('#')
+                    .next()
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .filter(|word| *word != "|")
+                    .collect();
                 // Handle multiple targets on same line: "foo bar: deps"
                 for target in target_part.split_whitespace() {
-                    if Self::is_runnable_target(target) && !targets.contains(&target.to_string()) {
-                        targets.push(target.to_string());
+                    if !Self::is_runnable_target(target) {
+                        continue;
                     }
+                    let i = match targets.iter().position(|t| t.name == target) {
+                        Some(i) => i,
+                        None => {
+                            targets.push(Target {
+                                name: target.to_string(),
+                                ..Target::default()
+                            });
+                            targets.len() - 1
+                        }
+                    };
+                    if targets[i].description.is_none() {
+                        targets[i].description = description.map(str::to_string);
+                    }
+                    for &prerequisite in &prerequisites {
+                        if !targets[i].prerequisites.iter().any(|p| p == prerequisite) {
+                            targets[i].prerequisites.push(prerequisite.to_string());
+                        }
+                    }
+                    current.push(i);
                 }
             }
         }
@@ -47,22 +122,56 @@ impl MakefileParser {
     }
 }
 
+/// Replace `make <target>` with the underlying command for targets whose
+/// recipe is a single plain invocation of another runner (`npm run build`)
+pub(crate) fn flatten_make_wrappers(runner: &mut TaskRunner) {
+    for task in &mut runner.tasks {
+        let Some(script) = &task.script else {
+            continue;
+        };
+        // `@` (silent) and `-` (ignore errors) prefixes don't change the command
+        let command = script.trim_start_matches(['@', '-']).trim();
+
+        let single_plain_command = !script.contains('\n')
+            && !command.contains('$')
+            && !command.contains(['&', '|', ';', '>', '<', '`']);
+        let invokes_runner = command
+            .split_whitespace()
+            .next()
+            .is_some_and(|program| KNOWN_RUNNERS.contains(&program));
+
+        if single_plain_command && invokes_runner {
+            task.command = command.to_string();
+        }
+    }
+}
+
 impl Parser for MakefileParser {
     fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
-        let content = fs::read_to_string(path)?;
+        let content = read_config(path)?;
         let targets = Self::parse_targets(&content);
 
         if targets.is_empty() {
             return Ok(None);
         }
 
+        // Prerequisites that aren't targets are files, not tasks
+        let names: Vec<String> = targets.iter().map(|t| t.name.clone()).collect();
         let tasks = targets
             .into_iter()
-            .map(|name| Task {
-                command: format!("make {}", name),
-                name,
-                description: None,
-                script: None,
+            .map(|target| Task {
+                command: format!("make {}", target.name),
+                metadata: TaskMetadata {
+                    depends_on: target
+                        .prerequisites
+                        .into_iter()
+                        .filter(|p| names.contains(p))
+                        .collect(),
+                    ..Default::default()
+                },
+                name: target.name,
+                description: target.description,
+                script: (!target.recipe.is_empty()).then(|| target.recipe.join("\n")),
             })
             .collect();
 
@@ -70,6 +179,7 @@ impl Parser for MakefileParser {
             config_path: path.to_path_buf(),
             runner_type: RunnerType::Make,
             tasks,
+            ..Default::default()
         }))
     }
 }
@@ -134,4 +244,100 @@ build:
         assert_eq!(runner.tasks.len(), 1);
         assert_eq!(runner.tasks[0].name, "build");
     }
+
+    #[test]
+    fn test_hash_hash_descriptions() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Makefile");
+        fs::write(
+            &path,
+            r#"
+## Targets
+build: deps ## Build the project
+	cargo build ## not a description
+
+test:
+	cargo test
+"#,
+        )
+        .unwrap();
+
+        let parser = MakefileParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.tasks.len(), 2);
+        assert_eq!(
+            runner.tasks[0].description.as_deref(),
+            Some("Build the project")
+        );
+        assert_eq!(
+            runner.tasks[0].script.as_deref(),
+            Some("cargo build ## not a description")
+        );
+        assert_eq!(runner.tasks[1].description, None);
+    }
+
+    #[test]
+    fn test_prerequisites_become_dependencies() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Makefile");
+        fs::write(
+            &path,
+            "all: build test ## Everything\nbuild: main.c | dist\n\tcc main.c\ntest: build # unit\ndist:\n",
+        )
+        .unwrap();
+
+        let runner = MakefileParser.parse(&path).unwrap().unwrap();
+        let deps: Vec<_> = runner
+            .tasks
+            .iter()
+            .map(|t| (t.name.as_str(), t.metadata.depends_on.clone()))
+            .collect();
+        assert_eq!(
+            deps,
+            vec![
+                ("all", vec!["build".to_string(), "test".to_string()]),
+                ("build", vec!["dist".to_string()]),
+                ("test", vec!["build".to_string()]),
+                ("dist", vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flatten_make_wrappers() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Makefile");
+        fs::write(
+            &path,
+            r#"
+.PHONY: build dev test release
+
+build:
+	@npm run build
+
+dev: build
+	npm run dev -- --port $(PORT)
+
+test:
+	cargo test && cargo clippy
+
+release:
+	cargo build --release
+	cp target/release/app dist/
+"#,
+        )
+        .unwrap();
+
+        let parser = MakefileParser;
+        let mut runner = parser.parse(&path).unwrap().unwrap();
+        assert_eq!(runner.tasks[0].script.as_deref(), Some("@npm run build"));
+
+        flatten_make_wrappers(&mut runner);
+        let commands: Vec<_> = runner.tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            vec!["npm run build", "make dev", "make test", "make release"]
+        );
+    }
 }
@@ -0,0 +1,231 @@
+//! Parser for maskfile.md (mask)
+//!
+//! Commands are `##` headings, subcommands are nested under them (`###`
+//! and deeper), the paragraph under a heading describes it and the first
+//! fenced code block is its script. The `#` title heading and headings
+//! without a code block (section or parent-only headings) aren't commands.
+
+use std::path::Path;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::{read_config, Parser};
+
+/// A heading being read, until the next heading ends it
+#[derive(Default)]
+struct Command {
+    /// Names of the heading and its parents (`["db", "migrate"]`)
+    path: Vec<String>,
+    /// Required positional arguments, from `(name)` after the heading name
+    args: Vec<String>,
+    description: Option<String>,
+    /// Set once a blank line ends the description paragraph
+    description_done: bool,
+    script: Option<String>,
+}
+
+impl Command {
+    fn into_task(self) -> Option<Task> {
+        let script = self.script?;
+        let name = self.path.join(" ");
+        let mut command = format!("mask {}", name);
+        for arg in &self.args {
+            command.push_str(&format!(" <{}>", arg));
+        }
+        Some(Task {
+            name,
+            command,
+            description: self.description,
+            script: Some(script),
+            ..Default::default()
+        })
+    }
+}
+
+pub struct MaskfileParser;
+
+impl MaskfileParser {
+    /// Split a heading into its level and text: `## build` → (2, "build")
+    fn heading(line: &str) -> Option<(usize, &str)> {
+        let level = line.chars().take_while(|&c| c == '#').count();
+        let text = line[level..].strip_prefix(' ')?;
+        (1..=6).contains(&level).then(|| (level, text.trim()))
+    }
+
+    /// Split heading text into the command name and its required arguments:
+    /// `deploy (env) (region?)` → ("deploy", ["env"])
+    fn name_and_args(text: &str) -> (String, Vec<String>) {
+        let (name, rest) = text.split_once('(').unwrap_or((text, ""));
+        let args = rest
+            .split(['(', ')'])
+            .map(str::trim)
+            .filter(|arg| !arg.is_empty() && !arg.ends_with('?'))
+            .map(str::to_string)
+            .collect();
+        (name.trim().to_string(), args)
+    }
+
+    fn parse_tasks(content: &str) -> Vec<Task> {
+        let mut tasks = Vec::new();
+        // Heading names by level, below the title
+        let mut stack: Vec<String> = Vec::new();
+        let mut current: Option<Command> = None;
+        // Open code fence marker and the lines read so far
+        let mut fence: Option<(&str, Vec<&str>)> = None;
+
+        for line in content.lines() {
+            if let Some((marker, lines)) = &mut fence {
+                if line.trim_start().starts_with(*marker) {
+                    let script = lines.join("\n");
+                    if let Some(command) = current.as_mut().filter(|c| c.script.is_none()) {
+                        command.script = Some(script);
+                    }
+                    fence = None;
+                } else {
+                    lines.push(line);
+                }
+                continue;
+            }
+
+            let trimmed = line.trim_start();
+            if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+                fence = Some((marker, Vec::new()));
+                continue;
+            }
+
+            if let Some((level, text)) = Self::heading(line) {
+                tasks.extend(current.take().and_then(Command::into_task));
+                if level == 1 {
+                    stack.clear();
+                    continue;
+                }
+                let (name, args) = Self::name_and_args(text);
+                stack.truncate(level - 2);
+                // mask spells out the full path (`### db migrate`), but a
+                // bare `### migrate` under `## db` means the same
+                let parent = stack.join(" ");
+                let name = match name.strip_prefix(&parent) {
+                    Some(rest) if !parent.is_empty() && rest.starts_with(' ') => {
+                        rest.trim().to_string()
+                    }
+                    _ => name,
+                };
+                stack.push(name);
+                current = Some(Command {
+                    path: stack.clone(),
+                    args,
+                    ..Command::default()
+                });
+                continue;
+            }
+
+            let Some(command) = current.as_mut() else {
+                continue;
+            };
+            if trimmed.is_empty() {
+                command.description_done |= command.description.is_some();
+            } else if !command.description_done && command.script.is_none() {
+                command.description = Some(match command.description.take() {
+                    Some(text) => format!("{} {}", text, trimmed),
+                    None => trimmed.to_string(),
+                });
+            }
+        }
+        tasks.extend(current.and_then(Command::into_task));
+        tasks
+    }
+}
+
+impl Parser for MaskfileParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let content = read_config(path)?;
+        let tasks = Self::parse_tasks(&content);
+
+        if tasks.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Mask,
+            tasks,
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_maskfile() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("maskfile.md");
+        fs::write(
+            &path,
+            r#"# Project tasks
+
+Run these with mask.
+
+## build
+
+Builds the project
+for release.
+
+```bash
+cargo build --release
+```
+
+## db
+
+### db migrate (direction) (steps?)
+
+> Runs migrations
+
+~~~sh
+# ## not a heading
+sqlx migrate run
+~~~
+
+### status
+
+```sh
+sqlx migrate info
+```
+
+## notes
+
+No code here, so not a command.
+"#,
+        )
+        .unwrap();
+
+        let parser = MaskfileParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::Mask);
+        let commands: Vec<_> = runner.tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            vec![
+                "mask build",
+                "mask db migrate <direction>",
+                "mask db status"
+            ]
+        );
+
+        let build = &runner.tasks[0];
+        assert_eq!(
+            build.description.as_deref(),
+            Some("Builds the project for release.")
+        );
+        assert_eq!(build.script.as_deref(), Some("cargo build --release"));
+        assert_eq!(
+            runner.tasks[1].script.as_deref(),
+            Some("# ## not a heading\nsqlx migrate run")
+        );
+    }
+}
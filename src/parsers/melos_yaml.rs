@@ -0,0 +1,178 @@
+//! Parser for melos.yaml (Melos, the Dart/Flutter monorepo tool)
+
+use std::path::Path;
+
+use indexmap::IndexMap;
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::{read_config, Parser};
+
+#[derive(Deserialize)]
+struct MelosYaml {
+    #[serde(default)]
+    scripts: IndexMap<String, Script>,
+}
+
+/// A script: just the command, or a map with `run` (or `exec`/`steps`)
+/// and a `description`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Script {
+    Command(String),
+    Config(ScriptConfig),
+}
+
+#[derive(Deserialize)]
+struct ScriptConfig {
+    run: Option<String>,
+    description: Option<String>,
+    exec: Option<Exec>,
+    #[serde(default)]
+    steps: Vec<String>,
+}
+
+/// `exec: <command>` runs the command in every package; as a map it only
+/// holds options for `run`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Exec {
+    Command(String),
+    Options(IgnoredAny),
+}
+
+impl Script {
+    /// The script body: its command, `melos exec` command or steps
+    fn body(&self) -> Option<String> {
+        match self {
+            Script::Command(command) => Some(command.clone()),
+            Script::Config(config) => config
+                .run
+                .clone()
+                .or_else(|| match &config.exec {
+                    Some(Exec::Command(command)) => Some(format!("melos exec -- {}", command)),
+                    _ => None,
+                })
+                .or_else(|| (!config.steps.is_empty()).then(|| config.steps.join("\n"))),
+        }
+    }
+
+    fn description(&self) -> Option<String> {
+        match self {
+            Script::Command(_) => None,
+            Script::Config(config) => config.description.as_deref().map(|d| d.trim().to_string()),
+        }
+    }
+}
+
+pub struct MelosYamlParser;
+
+impl Parser for MelosYamlParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let content = read_config(path)?;
+
+        let melos: MelosYaml =
+            serde_saphyr::from_str(&content).map_err(|e| ScanError::ParseError {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            })?;
+
+        let tasks: Vec<Task> = melos
+            .scripts
+            .iter()
+            .map(|(name, script)| Task {
+                name: name.clone(),
+                command: format!("melos run {}", name),
+                description: script.description(),
+                script: script.body(),
+                ..Default::default()
+            })
+            .collect();
+
+        if tasks.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Melos,
+            tasks,
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_melos_yaml() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("melos.yaml");
+        fs::write(
+            &path,
+            r#"name: my_workspace
+packages:
+  - packages/**
+scripts:
+  analyze: dart analyze .
+  test:
+    run: melos exec -- flutter test
+    description: |
+      Run tests in all packages
+  format:
+    exec: dart format .
+    description: Format every package
+  ci:
+    steps:
+      - analyze
+      - test
+"#,
+        )
+        .unwrap();
+
+        let runner = MelosYamlParser.parse(&path).unwrap().unwrap();
+        assert_eq!(runner.runner_type, RunnerType::Melos);
+        let tasks: Vec<_> = runner
+            .tasks
+            .iter()
+            .map(|t| {
+                (
+                    t.command.as_str(),
+                    t.description.as_deref(),
+                    t.script.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            tasks,
+            [
+                ("melos run analyze", None, Some("dart analyze .")),
+                (
+                    "melos run test",
+                    Some("Run tests in all packages"),
+                    Some("melos exec -- flutter test")
+                ),
+                (
+                    "melos run format",
+                    Some("Format every package"),
+                    Some("melos exec -- dart format .")
+                ),
+                ("melos run ci", None, Some("analyze\ntest")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_melos_yaml_without_scripts() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("melos.yaml");
+        fs::write(&path, "name: my_workspace\npackages:\n  - packages/**\n").unwrap();
+        assert!(MelosYamlParser.parse(&path).unwrap().is_none());
+    }
+}
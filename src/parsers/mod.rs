@@ -1,31 +1,124 @@
 //! Parsers for various task runner config file formats
 
+mod brewfile;
 mod cargo_toml;
+mod composer_json;
 mod csproj;
 mod deno_json;
+mod devcontainer;
+mod dune;
+mod github_actions;
+mod gradle;
+mod jsonc;
 mod justfile;
+mod lint_config;
 mod makefile;
+mod maskfile;
+mod melos_yaml;
+mod nu;
+mod nx;
 mod package_json;
 mod pom_xml;
+mod pre_commit;
+mod procfile;
 mod pubspec_yaml;
 mod pyproject_toml;
+mod rakefile;
+mod rebar3;
+mod script;
+mod snakemake;
 mod turbo_json;
 
+pub use brewfile::BrewfileParser;
 pub use cargo_toml::CargoTomlParser;
+pub use composer_json::ComposerJsonParser;
 pub use csproj::CsprojParser;
 pub use deno_json::DenoJsonParser;
+pub use devcontainer::DevcontainerParser;
+pub use dune::DuneParser;
+pub(crate) use github_actions::is_workflow_file;
+pub use github_actions::GithubActionsParser;
+pub use gradle::GradleParser;
 pub use justfile::JustfileParser;
+pub(crate) use lint_config::is_lint_config;
+pub use lint_config::LintConfigParser;
+pub(crate) use makefile::flatten_make_wrappers;
 pub use makefile::MakefileParser;
+pub use maskfile::MaskfileParser;
+pub use melos_yaml::MelosYamlParser;
+pub use nu::NuParser;
+pub use nx::NxParser;
 pub use package_json::PackageJsonParser;
 pub use pom_xml::PomXmlParser;
+pub use pre_commit::PreCommitParser;
+pub use procfile::ProcfileParser;
 pub use pubspec_yaml::PubspecYamlParser;
 pub use pyproject_toml::PyprojectTomlParser;
+pub use rakefile::RakefileParser;
+pub use rebar3::Rebar3Parser;
+pub use script::ScriptParser;
+pub use snakemake::SnakemakeParser;
 pub use turbo_json::TurboJsonParser;
 
-use std::path::Path;
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use crate::{ScanError, TaskRunner};
 
+thread_local! {
+    /// Folder that lookups above a config file stop at, set by [`within`]
+    static BOUNDARY: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
+#[cfg(feature = "archive")]
+/// Run `f` (parsing on this thread) with lookups of files above a config
+/// file kept inside `root`, e.g. an archive's staging folder
+pub(crate) fn within<T>(root: &Path, f: impl FnOnce() -> T) -> T {
+    let previous = BOUNDARY.with(|b| b.replace(Some(root.to_path_buf())));
+    let result = f();
+    BOUNDARY.with(|b| *b.borrow_mut() = previous);
+    result
+}
+
+/// `dir` and its ancestors, for parsers looking for files above a config
+/// file (workspace roots, `.mvn`, `.prototools`). Stops at the folder set
+/// with [`within`], if any.
+pub(crate) fn ancestors(dir: &Path) -> impl Iterator<Item = &Path> {
+    let boundary = BOUNDARY.with(|b| b.borrow().clone());
+    let mut past_boundary = false;
+    dir.ancestors().take_while(move |ancestor| {
+        let keep = !past_boundary;
+        past_boundary |= boundary.as_deref() == Some(*ancestor);
+        keep
+    })
+}
+
+/// Byte order mark some Windows editors put at the start of UTF-8 files
+const UTF8_BOM: char = '\u{feff}';
+
+/// Read a config file as UTF-8 text without a leading byte order mark.
+/// Content that isn't valid UTF-8 is a parse error for that file.
+fn read_config(path: &Path) -> Result<String, ScanError> {
+    let bytes = fs::read(path)?;
+    let content = String::from_utf8(bytes).map_err(|e| ScanError::ParseError {
+        path: path.to_path_buf(),
+        message: format!("not valid UTF-8 ({})", e.utf8_error()),
+    })?;
+    Ok(match content.strip_prefix(UTF8_BOM) {
+        Some(stripped) => stripped.to_string(),
+        None => content,
+    })
+}
+
+/// First non-empty line of a project description, for [`TaskRunner::description`]
+fn summary_line(text: &str) -> Option<String> {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
 /// Trait for parsing task runner config files
 pub trait Parser {
     /// Parse a config file and return a TaskRunner if tasks are found
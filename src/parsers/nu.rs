@@ -0,0 +1,174 @@
+//! Simple parser for Nushell `toolkit.nu` task modules
+//!
+//! Line-based: picks up top-level `export def name` and `export def "name"`
+//! declarations, and `def "main name"` subcommands. Subcommands of `main`
+//! run as `nu toolkit.nu <name>`; exported commands need the module loaded
+//! first, so they run as `nu -c 'use toolkit.nu; toolkit <name>'`.
+
+use std::path::Path;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::{read_config, Parser};
+
+/// How a command is declared, which decides how it's invoked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DefKind {
+    /// `def "main name"`: a subcommand of the script
+    Main,
+    /// `export def name`: a command of the module
+    Exported,
+}
+
+pub struct NuParser;
+
+impl NuParser {
+    /// Parse the command name from a single `def` line, if it declares one
+    fn parse_def(line: &str) -> Option<(String, DefKind)> {
+        let rest = line
+            .strip_prefix("export def ")
+            .or_else(|| line.strip_prefix("def "))?;
+        let exported = line.starts_with("export ");
+
+        // Skip def flags like --env / --wrapped
+        let mut rest = rest.trim_start();
+        while let Some(after) = rest.strip_prefix("--") {
+            rest = after
+                .split_once(char::is_whitespace)
+                .map(|(_, r)| r.trim_start())
+                .unwrap_or("");
+        }
+
+        let name = if let Some(quoted) = rest.strip_prefix('"') {
+            quoted.split_once('"')?.0
+        } else {
+            rest.split(|c: char| c.is_whitespace() || c == '[').next()?
+        };
+
+        // `def "main build"` is callable as `nu toolkit.nu build`
+        let (name, kind) = match name.strip_prefix("main ") {
+            Some(sub) => (sub.trim(), DefKind::Main),
+            None if exported => (name.trim(), DefKind::Exported),
+            None => return None,
+        };
+
+        if name.is_empty() || name == "main" || name.starts_with('_') {
+            return None;
+        }
+        Some((name.to_string(), kind))
+    }
+
+    /// Parse command names from module content, in declaration order
+    fn parse_commands(content: &str) -> Vec<(String, DefKind)> {
+        let mut commands: Vec<(String, DefKind)> = Vec::new();
+        for line in content.lines() {
+            if let Some((name, kind)) = Self::parse_def(line) {
+                if !commands.iter().any(|(existing, _)| *existing == name) {
+                    commands.push((name, kind));
+                }
+            }
+        }
+        commands
+    }
+
+    /// The command line running `name` from `file_name`
+    fn invocation(file_name: &str, name: &str, kind: DefKind) -> String {
+        match kind {
+            DefKind::Main => format!("nu {} {}", file_name, name),
+            DefKind::Exported => {
+                let module = file_name.strip_suffix(".nu").unwrap_or(file_name);
+                let script = format!("use {}; {} {}", file_name, module, name);
+                let quoted = shlex::try_quote(&script).unwrap_or(script.as_str().into());
+                format!("nu -c {}", quoted)
+            }
+        }
+    }
+}
+
+impl Parser for NuParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let content = read_config(path)?;
+        let commands = Self::parse_commands(&content);
+
+        if commands.is_empty() {
+            return Ok(None);
+        }
+
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "toolkit.nu".to_string());
+
+        let tasks = commands
+            .into_iter()
+            .map(|(name, kind)| Task {
+                command: Self::invocation(&file_name, &name, kind),
+                name,
+                description: None,
+                script: None,
+                ..Default::default()
+            })
+            .collect();
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Nu,
+            tasks,
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_toolkit() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("toolkit.nu");
+        fs::write(
+            &path,
+            r#"
+# Format all files
+export def fmt [--check] {
+    cargo fmt --all
+}
+
+export def "check pr" [] {
+    fmt --check
+}
+
+export def --env setup [] {
+    $env.FOO = "bar"
+}
+
+def helper [] {}
+
+def "main release" [] {}
+
+def main [] {}
+"#,
+        )
+        .unwrap();
+
+        let parser = NuParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::Nu);
+        let names: Vec<_> = runner.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["fmt", "check pr", "setup", "release"]);
+        let commands: Vec<_> = runner.tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            vec![
+                "nu -c 'use toolkit.nu; toolkit fmt'",
+                "nu -c 'use toolkit.nu; toolkit check pr'",
+                "nu -c 'use toolkit.nu; toolkit setup'",
+                "nu toolkit.nu release",
+            ]
+        );
+    }
+}
@@ -0,0 +1,188 @@
+//! Parser for Nx project.json files (per-project targets in an Nx monorepo)
+
+use std::path::Path;
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::{RunnerType, ScanError, Task, TaskMetadata, TaskRunner};
+
+use super::{read_config, Parser};
+
+/// Only files with both `name` and `targets` are Nx projects; other tools
+/// (old .NET, editors) use project.json for something else
+#[derive(Deserialize)]
+struct ProjectJson {
+    name: Option<String>,
+    targets: Option<IndexMap<String, Target>>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Target {
+    executor: Option<String>,
+    options: Option<serde_json::Value>,
+    depends_on: Vec<serde_json::Value>,
+}
+
+impl Target {
+    /// The shell command(s) of an `nx:run-commands` target
+    fn script(&self) -> Option<String> {
+        let options = self.options.as_ref()?;
+        if let Some(command) = options.get("command").and_then(|c| c.as_str()) {
+            return Some(command.to_string());
+        }
+        let commands: Vec<&str> = options
+            .get("commands")?
+            .as_array()?
+            .iter()
+            .filter_map(|c| c.as_str().or_else(|| c.get("command")?.as_str()))
+            .collect();
+        (!commands.is_empty()).then(|| commands.join("\n"))
+    }
+
+    /// `dependsOn` as target names: `"^build"` and `"lint"` as is, and
+    /// `{ "target": "build", "projects": "dependencies" }` as `^build`
+    fn depends_on(&self) -> Vec<String> {
+        self.depends_on
+            .iter()
+            .filter_map(|dep| {
+                if let Some(name) = dep.as_str() {
+                    return Some(name.to_string());
+                }
+                let target = dep.get("target")?.as_str()?;
+                let upstream = dep.get("projects").and_then(|p| p.as_str()) == Some("dependencies")
+                    || dep.get("dependencies").and_then(|d| d.as_bool()) == Some(true);
+                Some(if upstream {
+                    format!("^{}", target)
+                } else {
+                    target.to_string()
+                })
+            })
+            .collect()
+    }
+}
+
+pub struct NxParser;
+
+impl Parser for NxParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let content = read_config(path)?;
+
+        // project.json isn't Nx-specific, so a file that isn't a valid Nx
+        // project is skipped instead of reported
+        let Ok(ProjectJson {
+            name: Some(project),
+            targets: Some(targets),
+        }) = serde_json::from_str::<ProjectJson>(&content)
+        else {
+            return Ok(None);
+        };
+
+        let tasks: Vec<Task> = targets
+            .iter()
+            .map(|(name, target)| Task {
+                name: name.clone(),
+                command: format!("nx run {}:{}", project, name),
+                description: target.executor.clone(),
+                script: target.script(),
+                metadata: TaskMetadata {
+                    depends_on: target.depends_on(),
+                    ..Default::default()
+                },
+            })
+            .collect();
+
+        if tasks.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Nx,
+            tasks,
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_project_json() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.json");
+        fs::write(
+            &path,
+            r#"{
+                "name": "web",
+                "$schema": "../../node_modules/nx/schemas/project-schema.json",
+                "targets": {
+                    "build": {
+                        "executor": "@nx/vite:build",
+                        "dependsOn": ["^build", { "target": "codegen" }]
+                    },
+                    "codegen": {
+                        "executor": "nx:run-commands",
+                        "options": { "commands": ["graphql-codegen", { "command": "prettier -w src/gql" }] }
+                    },
+                    "serve": {
+                        "executor": "nx:run-commands",
+                        "options": { "command": "vite dev" }
+                    },
+                    "lint": {}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let runner = NxParser.parse(&path).unwrap().unwrap();
+        assert_eq!(runner.runner_type, RunnerType::Nx);
+        let commands: Vec<_> = runner.tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            [
+                "nx run web:build",
+                "nx run web:codegen",
+                "nx run web:serve",
+                "nx run web:lint"
+            ]
+        );
+
+        let build = &runner.tasks[0];
+        assert_eq!(build.description.as_deref(), Some("@nx/vite:build"));
+        assert_eq!(build.script, None);
+        assert_eq!(build.metadata.depends_on, ["^build", "codegen"]);
+        assert_eq!(
+            runner.tasks[1].script.as_deref(),
+            Some("graphql-codegen\nprettier -w src/gql")
+        );
+        assert_eq!(runner.tasks[2].script.as_deref(), Some("vite dev"));
+        assert_eq!(runner.tasks[3].description, None);
+    }
+
+    #[test]
+    fn test_skip_non_nx_project_json() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.json");
+
+        // Legacy .NET project.json
+        fs::write(
+            &path,
+            r#"{ "dependencies": { "NETStandard.Library": "1.6.0" }, "frameworks": { "netstandard1.6": {} } }"#,
+        )
+        .unwrap();
+        assert!(NxParser.parse(&path).unwrap().is_none());
+
+        // Targets without a project name
+        fs::write(&path, r#"{ "targets": { "build": {} } }"#).unwrap();
+        assert!(NxParser.parse(&path).unwrap().is_none());
+
+        fs::write(&path, "{ not json").unwrap();
+        assert!(NxParser.parse(&path).unwrap().is_none());
+    }
+}
@@ -1,35 +1,156 @@
 //! Parser for package.json (npm/bun/yarn/pnpm scripts)
+//!
+//! In Yarn Berry monorepos the root also gets `yarn workspaces foreach`
+//! tasks for every script the workspaces define, and packages that are
+//! members of an npm/yarn/pnpm workspace record the workspace's name.
 
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
 
+use globset::GlobBuilder;
+use indexmap::IndexMap;
 use serde::Deserialize;
 
-use crate::{RunnerType, ScanError, Task, TaskRunner};
+use crate::{RunnerType, ScanError, Task, TaskMetadata, TaskRunner};
 
-use super::Parser;
+use super::{ancestors, read_config, summary_line, Parser};
 
 #[derive(Deserialize)]
 struct PackageJson {
-    scripts: Option<HashMap<String, String>>,
+    scripts: Option<IndexMap<String, String>>,
     #[serde(rename = "packageManager")]
     package_manager: Option<String>,
+    workspaces: Option<Workspaces>,
+    description: Option<serde_json::Value>,
+    name: Option<String>,
+    bin: Option<Bin>,
+}
+
+/// The `bin` field: a single executable named after the package, or a map
+/// of executable names to files
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Bin {
+    Single(String),
+    Map(IndexMap<String, String>),
+    Other(serde::de::IgnoredAny),
+}
+
+/// The `workspaces` field: a list of globs, or (Yarn 1) an object with
+/// `packages` and `nohoist` lists
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Workspaces {
+    Patterns(Vec<String>),
+    Config {
+        #[serde(default)]
+        packages: Vec<String>,
+    },
+    /// Anything else is ignored rather than failing the whole file
+    Other(serde::de::IgnoredAny),
+}
+
+/// pnpm-workspace.yaml, which lists workspace globs under `packages`
+#[derive(Deserialize)]
+struct PnpmWorkspace {
+    #[serde(default)]
+    packages: Vec<String>,
+}
+
+/// A folder declaring a workspace: the name its members are listed under
+/// and the globs matching them
+struct WorkspaceRoot {
+    name: Option<String>,
+    patterns: Vec<String>,
+}
+
+/// A cached [`WorkspaceRoot`] lookup, with the (modified time, size) of the
+/// package.json and pnpm-workspace.yaml it was read from
+type WorkspaceRootEntry = (
+    [Option<(Option<SystemTime>, u64)>; 2],
+    Option<Arc<WorkspaceRoot>>,
+);
+
+impl PackageJson {
+    /// Workspace globs from the `workspaces` field, in either form
+    fn workspace_patterns(&self) -> Vec<&str> {
+        match &self.workspaces {
+            Some(Workspaces::Patterns(patterns))
+            | Some(Workspaces::Config { packages: patterns }) => {
+                patterns.iter().map(String::as_str).collect()
+            }
+            Some(Workspaces::Other(_)) | None => Vec::new(),
+        }
+    }
+
+    /// Executables from the `bin` field as (name, file) pairs. The
+    /// single-file form is named after the package, without its scope (or
+    /// the file, if there's no name).
+    fn bins(&self) -> Vec<(&str, &str)> {
+        match &self.bin {
+            Some(Bin::Single(file)) => match self.name.as_deref() {
+                Some(name) => vec![(name.rsplit('/').next().unwrap_or(name), file.as_str())],
+                None => Path::new(file)
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .map(|stem| (stem, file.as_str()))
+                    .into_iter()
+                    .collect(),
+            },
+            Some(Bin::Map(bins)) => bins
+                .iter()
+                .map(|(name, file)| (name.as_str(), file.as_str()))
+                .collect(),
+            Some(Bin::Other(_)) | None => Vec::new(),
+        }
+    }
 }
 
 pub struct PackageJsonParser;
 
+/// Tools that run other package.json scripts
+#[derive(Clone, Copy)]
+enum Composer {
+    NpmRunAll,
+    Concurrently,
+}
+
 impl PackageJsonParser {
-    /// Detect the package manager from the packageManager field
-    fn detect_runner_type(package_manager: Option<&str>) -> RunnerType {
+    /// Detect the package manager from the packageManager field, falling
+    /// back to the lockfile next to package.json or, in a workspace member,
+    /// at the workspace root
+    pub(super) fn detect_runner_type(package_manager: Option<&str>, dir: &Path) -> RunnerType {
         match package_manager {
             Some(pm) if pm.starts_with("bun") => RunnerType::Bun,
             Some(pm) if pm.starts_with("yarn") => RunnerType::Yarn,
             Some(pm) if pm.starts_with("pnpm") => RunnerType::Pnpm,
-            _ => RunnerType::Npm,
+            Some(_) => RunnerType::Npm,
+            None => Self::lockfile_runner_type(dir)
+                .or_else(|| {
+                    Self::workspace(dir).and_then(|(root, _)| Self::lockfile_runner_type(root))
+                })
+                .unwrap_or(RunnerType::Npm),
         }
     }
 
+    /// Package manager implied by a lockfile in `dir`, if there is one
+    fn lockfile_runner_type(dir: &Path) -> Option<RunnerType> {
+        const LOCKFILES: &[(&str, RunnerType)] = &[
+            ("bun.lockb", RunnerType::Bun),
+            ("bun.lock", RunnerType::Bun),
+            ("pnpm-lock.yaml", RunnerType::Pnpm),
+            ("yarn.lock", RunnerType::Yarn),
+            ("package-lock.json", RunnerType::Npm),
+        ];
+        LOCKFILES
+            .iter()
+            .find(|(lockfile, _)| dir.join(lockfile).is_file())
+            .map(|&(_, runner_type)| runner_type)
+    }
+
     /// Get the run command prefix for the package manager
     fn run_command(runner_type: RunnerType, script_name: &str) -> String {
         match runner_type {
@@ -39,11 +160,342 @@ impl PackageJsonParser {
             _ => format!("npm run {}", script_name),
         }
     }
+
+    /// Install command for the package manager
+    fn install_command(runner_type: RunnerType) -> String {
+        match runner_type {
+            RunnerType::Bun => "bun install".to_string(),
+            RunnerType::Yarn => "yarn install".to_string(),
+            RunnerType::Pnpm => "pnpm install".to_string(),
+            _ => "npm install".to_string(),
+        }
+    }
+
+    /// Tasks for a package with no scripts that is still clearly a project
+    /// (it has a lockfile, workspaces or `bin` entries): `install`, plus
+    /// running each of its own executables. Empty for anything else.
+    fn fallback_tasks(pkg: &PackageJson, runner_type: RunnerType, dir: &Path) -> Vec<Task> {
+        let bins = pkg.bins();
+        if bins.is_empty()
+            && pkg.workspace_patterns().is_empty()
+            && Self::lockfile_runner_type(dir).is_none()
+        {
+            return Vec::new();
+        }
+
+        let mut tasks = vec![Task {
+            name: "install".to_string(),
+            command: Self::install_command(runner_type),
+            description: Some("Install dependencies".to_string()),
+            ..Default::default()
+        }];
+        tasks.extend(bins.into_iter().map(|(bin, file)| Task {
+            name: bin.to_string(),
+            command: Self::bin_command(runner_type, file),
+            description: Some(format!("Run the {} executable", bin)),
+            ..Default::default()
+        }));
+        tasks
+    }
+
+    /// Command running one of the package's own executables. Its bin isn't
+    /// linked into its own node_modules/.bin, so `npx <bin>` would fetch an
+    /// unrelated package from the registry; the file is run directly instead.
+    fn bin_command(runner_type: RunnerType, file: &str) -> String {
+        match runner_type {
+            RunnerType::Bun => format!("bun {}", file),
+            _ => format!("node {}", file),
+        }
+    }
+
+    /// Command running a package binary (`eslint .`) without a script: `bunx`
+    /// in Bun projects, so npx doesn't pull in Node, else `npx`
+    pub(super) fn exec_command(runner_type: RunnerType, args: &str) -> String {
+        match runner_type {
+            RunnerType::Bun => format!("bunx {}", args),
+            _ => format!("npx {}", args),
+        }
+    }
+
+    /// Whether the project uses Yarn 2+ ("Berry"), which has `workspaces foreach`.
+    /// Without a `packageManager` field, a `.yarnrc.yml` next to `yarn.lock` is
+    /// taken as Berry (Yarn 1 uses `.yarnrc`).
+    fn is_yarn_berry(package_manager: Option<&str>, dir: &Path) -> bool {
+        match package_manager {
+            Some(pm) => pm
+                .strip_prefix("yarn@")
+                .and_then(|version| version.split('.').next()?.parse::<u32>().ok())
+                .is_some_and(|major| major >= 2),
+            None => dir.join("yarn.lock").is_file() && dir.join(".yarnrc.yml").is_file(),
+        }
+    }
+
+    /// Resolve workspace globs (`packages/*`, `apps/web`) to package folders.
+    /// Wildcards are matched one level below their literal prefix.
+    fn workspace_dirs(root: &Path, patterns: &[&str]) -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        for pattern in patterns {
+            let pattern = pattern.trim_end_matches('/');
+            let Some(wildcard) = pattern.find(['*', '?', '[', '{']) else {
+                dirs.push(root.join(pattern));
+                continue;
+            };
+
+            let prefix = pattern[..wildcard].trim_end_matches(|c| c != '/');
+            let Ok(matcher) = GlobBuilder::new(pattern)
+                .build()
+                .map(|g| g.compile_matcher())
+            else {
+                continue;
+            };
+            let Ok(entries) = fs::read_dir(root.join(prefix)) else {
+                continue;
+            };
+            let mut matched: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_ok_and(|ft| ft.is_dir()))
+                .filter(|entry| {
+                    let name = entry.file_name();
+                    matcher.is_match(format!("{}{}", prefix, name.to_string_lossy()))
+                })
+                .map(|entry| entry.path())
+                .collect();
+            matched.sort();
+            dirs.extend(matched);
+        }
+        dirs
+    }
+
+    /// `dir` and its ancestors up to its repository root (the first folder
+    /// holding `.git`); all of them outside a repository
+    fn repo_ancestors(dir: &Path) -> impl Iterator<Item = &Path> {
+        let mut past_repo_root = false;
+        ancestors(dir).take_while(move |ancestor| {
+            let keep = !past_repo_root;
+            past_repo_root |= ancestor.join(".git").exists();
+            keep
+        })
+    }
+
+    /// The workspace `dir` is a member of, and the folder declaring it: the
+    /// nearest ancestor within its repository whose package.json
+    /// `workspaces` (or pnpm-workspace.yaml `packages`) globs match it
+    fn workspace(dir: &Path) -> Option<(&Path, Arc<WorkspaceRoot>)> {
+        for root in Self::repo_ancestors(dir).skip(1) {
+            let Some(workspace) = Self::workspace_root(root) else {
+                continue;
+            };
+            let Ok(relative) = dir.strip_prefix(root) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if Self::matches_workspace(&relative, &workspace.patterns) {
+                return Some((root, workspace));
+            }
+        }
+        None
+    }
+
+    /// The workspace globs declared in `root`, if any. Every package looks
+    /// at all of its ancestors, so roots are cached by folder and only read
+    /// again once their package.json or pnpm-workspace.yaml changes.
+    fn workspace_root(root: &Path) -> Option<Arc<WorkspaceRoot>> {
+        static CACHE: OnceLock<Mutex<HashMap<PathBuf, WorkspaceRootEntry>>> = OnceLock::new();
+        let stamp = ["package.json", "pnpm-workspace.yaml"].map(|file| {
+            fs::metadata(root.join(file))
+                .ok()
+                .map(|meta| (meta.modified().ok(), meta.len()))
+        });
+
+        let cache = CACHE.get_or_init(Default::default);
+        if let Some((cached_stamp, workspace)) = cache.lock().ok()?.get(root) {
+            if *cached_stamp == stamp {
+                return workspace.clone();
+            }
+        }
+        let workspace = Self::read_workspace_root(root).map(Arc::new);
+        cache
+            .lock()
+            .ok()?
+            .insert(root.to_path_buf(), (stamp, workspace.clone()));
+        workspace
+    }
+
+    fn read_workspace_root(root: &Path) -> Option<WorkspaceRoot> {
+        let pkg = read_config(&root.join("package.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<PackageJson>(&content).ok());
+        let mut patterns: Vec<String> = pkg
+            .as_ref()
+            .map(|pkg| {
+                pkg.workspace_patterns()
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if patterns.is_empty() {
+            patterns = read_config(&root.join("pnpm-workspace.yaml"))
+                .ok()
+                .and_then(|content| serde_saphyr::from_str::<PnpmWorkspace>(&content).ok())
+                .map(|workspace| workspace.packages)
+                .unwrap_or_default();
+        }
+        if patterns.is_empty() {
+            return None;
+        }
+
+        let name = pkg.and_then(|pkg| pkg.name).or_else(|| {
+            root.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        });
+        Some(WorkspaceRoot { name, patterns })
+    }
+
+    /// Whether `relative` (a `/`-separated folder below the workspace root)
+    /// matches the globs, with `!` patterns excluding folders again
+    fn matches_workspace(relative: &str, patterns: &[String]) -> bool {
+        let mut member = false;
+        for pattern in patterns {
+            let (exclude, pattern) = match pattern.strip_prefix('!') {
+                Some(pattern) => (true, pattern),
+                None => (false, pattern.as_str()),
+            };
+            let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+            let Ok(matcher) = GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+                .map(|g| g.compile_matcher())
+            else {
+                continue;
+            };
+            if matcher.is_match(relative) {
+                member = !exclude;
+            }
+        }
+        member
+    }
+
+    /// `yarn workspaces foreach` tasks for every script defined in a workspace
+    fn foreach_tasks(root: &Path, patterns: &[&str]) -> Vec<Task> {
+        let mut script_names: Vec<String> = Vec::new();
+        for dir in Self::workspace_dirs(root, patterns) {
+            let Ok(content) = read_config(&dir.join("package.json")) else {
+                continue;
+            };
+            let Ok(pkg) = serde_json::from_str::<PackageJson>(&content) else {
+                continue;
+            };
+            for name in pkg.scripts.into_iter().flat_map(|s| s.into_keys()) {
+                if !script_names.contains(&name) {
+                    script_names.push(name);
+                }
+            }
+        }
+
+        script_names
+            .into_iter()
+            .map(|name| Task {
+                command: format!("yarn workspaces foreach --all run {}", name),
+                description: Some(format!("Run {} in all workspaces", name)),
+                name: format!("workspaces:{}", name),
+                script: None,
+                metadata: TaskMetadata {
+                    workspace: true,
+                    ..Default::default()
+                },
+            })
+            .collect()
+    }
+
+    /// Describe a script that composes sibling scripts via npm-run-all
+    /// (`run-s`, `run-p`) or concurrently, e.g. "Runs build:css, build:js".
+    /// Patterns like `build:*` are resolved against `names`.
+    fn describe_composite(name: &str, script: &str, names: &[&str]) -> Option<String> {
+        let tokens: Vec<&str> = script
+            .split_whitespace()
+            .map(|t| t.trim_matches(|c| c == '"' || c == '\''))
+            .collect();
+
+        let mut parallel = false;
+        let mut patterns = Vec::new();
+        let mut tool = None;
+        for token in tokens {
+            if matches!(token, "&&" | "||" | ";" | "|" | "&") {
+                tool = None;
+                continue;
+            }
+            match tool {
+                None => {
+                    let program = token.rsplit('/').next().unwrap_or(token);
+                    tool = match program {
+                        "npm-run-all" | "run-s" => Some(Composer::NpmRunAll),
+                        "run-p" => {
+                            parallel = true;
+                            Some(Composer::NpmRunAll)
+                        }
+                        "concurrently" => {
+                            parallel = true;
+                            Some(Composer::Concurrently)
+                        }
+                        _ => None,
+                    };
+                }
+                Some(_) if matches!(token, "-p" | "--parallel") => parallel = true,
+                Some(_) if token.starts_with('-') => {}
+                // concurrently only runs scripts given as `npm:name` (or yarn:, pnpm:, bun:)
+                Some(Composer::Concurrently) => {
+                    if let Some((_, pattern)) = token
+                        .split_once(':')
+                        .filter(|(pm, _)| matches!(*pm, "npm" | "yarn" | "pnpm" | "bun"))
+                    {
+                        patterns.push((pattern, false));
+                    }
+                }
+                // npm-run-all globs treat `:` like a path separator
+                Some(Composer::NpmRunAll) => patterns.push((token, true)),
+            }
+        }
+
+        let mut matched: Vec<&str> = Vec::new();
+        for (pattern, segmented) in patterns {
+            let glob = if segmented {
+                GlobBuilder::new(&pattern.replace(':', "/"))
+                    .literal_separator(true)
+                    .build()
+            } else {
+                GlobBuilder::new(pattern).build()
+            };
+            let Ok(matcher) = glob.map(|g| g.compile_matcher()) else {
+                continue;
+            };
+            for &candidate in names {
+                let subject = if segmented {
+                    candidate.replace(':', "/")
+                } else {
+                    candidate.to_string()
+                };
+                if candidate != name && !matched.contains(&candidate) && matcher.is_match(subject) {
+                    matched.push(candidate);
+                }
+            }
+        }
+
+        if matched.is_empty() {
+            return None;
+        }
+        let prefix = if parallel {
+            "Runs in parallel: "
+        } else {
+            "Runs "
+        };
+        Some(format!("{}{}", prefix, matched.join(", ")))
+    }
 }
 
 impl Parser for PackageJsonParser {
     fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
-        let content = fs::read_to_string(path)?;
+        let content = read_config(path)?;
 
         let pkg: PackageJson =
             serde_json::from_str(&content).map_err(|e| ScanError::ParseError {
@@ -51,26 +503,47 @@ impl Parser for PackageJsonParser {
                 message: e.to_string(),
             })?;
 
-        let scripts = match pkg.scripts {
-            Some(s) if !s.is_empty() => s,
-            _ => return Ok(None),
-        };
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let runner_type = Self::detect_runner_type(pkg.package_manager.as_deref(), dir);
+        let patterns = pkg.workspace_patterns();
+        let foreach_tasks =
+            if !patterns.is_empty() && Self::is_yarn_berry(pkg.package_manager.as_deref(), dir) {
+                Self::foreach_tasks(dir, &patterns)
+            } else {
+                Vec::new()
+            };
 
-        let runner_type = Self::detect_runner_type(pkg.package_manager.as_deref());
+        let scripts = pkg.scripts.clone().unwrap_or_default();
+        let mut extra_tasks = foreach_tasks;
+        if scripts.is_empty() && extra_tasks.is_empty() {
+            extra_tasks = Self::fallback_tasks(&pkg, runner_type, dir);
+            if extra_tasks.is_empty() {
+                return Ok(None);
+            }
+        }
 
-        let tasks: Vec<Task> = scripts
-            .into_iter()
+        let names: Vec<&str> = scripts.keys().map(String::as_str).collect();
+        let mut tasks: Vec<Task> = scripts
+            .iter()
             .map(|(name, script)| Task {
-                command: Self::run_command(runner_type, &name),
-                name,
-                description: None,
-                script: Some(script),
+                name: name.clone(),
+                command: Self::run_command(runner_type, name),
+                description: Self::describe_composite(name, script, &names),
+                script: Some(script.clone()),
+                ..Default::default()
             })
             .collect();
+        tasks.extend(extra_tasks);
 
         Ok(Some(TaskRunner {
             config_path: path.to_path_buf(),
             runner_type,
+            description: pkg
+                .description
+                .as_ref()
+                .and_then(|d| d.as_str())
+                .and_then(summary_line),
+            workspace: Self::workspace(dir).and_then(|(_, workspace)| workspace.name.clone()),
             tasks,
         }))
     }
@@ -90,6 +563,7 @@ mod tests {
             &path,
             r#"{
                 "name": "test",
+                "description": "Test package",
                 "scripts": {
                     "build": "tsc",
                     "test": "jest"
@@ -102,12 +576,88 @@ mod tests {
         let runner = parser.parse(&path).unwrap().unwrap();
 
         assert_eq!(runner.runner_type, RunnerType::Npm);
+        assert_eq!(runner.description.as_deref(), Some("Test package"));
         assert_eq!(runner.tasks.len(), 2);
 
         let build_task = runner.tasks.iter().find(|t| t.name == "build").unwrap();
         assert_eq!(build_task.command, "npm run build");
     }
 
+    #[test]
+    fn test_scripts_keep_declaration_order() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("package.json");
+        fs::write(
+            &path,
+            r#"{
+                "scripts": {
+                    "test": "jest",
+                    "build": "tsc",
+                    "lint": "eslint .",
+                    "dev": "vite"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let parser = PackageJsonParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        let names: Vec<_> = runner.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["test", "build", "lint", "dev"]);
+    }
+
+    #[test]
+    fn test_describe_composite_scripts() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("package.json");
+        fs::write(
+            &path,
+            r#"{
+                "scripts": {
+                    "build": "npm-run-all clean build:*",
+                    "build:css": "sass src:dist",
+                    "build:js": "tsc",
+                    "build:js:watch": "tsc -w",
+                    "clean": "rimraf dist",
+                    "dev": "concurrently -k \"npm:watch-*\" \"npm:api\"",
+                    "watch-css": "sass --watch src:dist",
+                    "api": "node server.js",
+                    "check": "run-p --aggressive lint test",
+                    "lint": "eslint ."
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let parser = PackageJsonParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+        let description = |name: &str| {
+            runner
+                .tasks
+                .iter()
+                .find(|t| t.name == name)
+                .unwrap()
+                .description
+                .clone()
+        };
+
+        assert_eq!(
+            description("build").as_deref(),
+            Some("Runs clean, build:css, build:js")
+        );
+        assert_eq!(
+            description("dev").as_deref(),
+            Some("Runs in parallel: watch-css, api")
+        );
+        // Unknown scripts (`test`) are left out
+        assert_eq!(
+            description("check").as_deref(),
+            Some("Runs in parallel: lint")
+        );
+        assert_eq!(description("clean"), None);
+    }
+
     #[test]
     fn test_parse_bun_scripts() {
         let dir = TempDir::new().unwrap();
@@ -142,4 +692,255 @@ mod tests {
         let runner = parser.parse(&path).unwrap();
         assert!(runner.is_none());
     }
+
+    #[test]
+    fn test_no_scripts_but_lockfile_or_bin() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("package.json");
+        fs::write(&path, r#"{"name": "@acme/cli", "bin": "./cli.js"}"#).unwrap();
+
+        let runner = PackageJsonParser.parse(&path).unwrap().unwrap();
+        assert_eq!(runner.runner_type, RunnerType::Npm);
+        let commands: Vec<_> = runner.tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(commands, ["npm install", "node ./cli.js"]);
+
+        fs::write(
+            &path,
+            r#"{"name": "tools", "bin": {"lint": "./lint.js", "fmt": "./fmt.js"}}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("bun.lock"), "{}").unwrap();
+        let runner = PackageJsonParser.parse(&path).unwrap().unwrap();
+        assert_eq!(runner.runner_type, RunnerType::Bun);
+        let names: Vec<_> = runner.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["install", "lint", "fmt"]);
+        assert_eq!(runner.tasks[1].command, "bun ./lint.js");
+
+        // A lockfile alone keeps the project visible
+        fs::write(&path, r#"{"name": "app"}"#).unwrap();
+        fs::remove_file(dir.path().join("bun.lock")).unwrap();
+        fs::write(
+            dir.path().join("pnpm-lock.yaml"),
+            "lockfileVersion: '9.0'\n",
+        )
+        .unwrap();
+        let runner = PackageJsonParser.parse(&path).unwrap().unwrap();
+        assert_eq!(runner.tasks.len(), 1);
+        assert_eq!(runner.tasks[0].command, "pnpm install");
+    }
+
+    #[test]
+    fn test_detect_runner_from_lockfile() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("package.json");
+        fs::write(&path, r#"{"scripts": {"build": "tsc"}}"#).unwrap();
+        fs::write(
+            dir.path().join("pnpm-lock.yaml"),
+            "lockfileVersion: '9.0'\n",
+        )
+        .unwrap();
+
+        let runner = PackageJsonParser.parse(&path).unwrap().unwrap();
+        assert_eq!(runner.runner_type, RunnerType::Pnpm);
+        assert_eq!(runner.tasks[0].command, "pnpm run build");
+
+        // packageManager still wins over the lockfile
+        fs::write(
+            &path,
+            r#"{"packageManager": "yarn@1.22.19", "scripts": {"build": "tsc"}}"#,
+        )
+        .unwrap();
+        let runner = PackageJsonParser.parse(&path).unwrap().unwrap();
+        assert_eq!(runner.runner_type, RunnerType::Yarn);
+    }
+
+    #[test]
+    fn test_workspace_patterns_forms() {
+        let patterns = |json: &str| {
+            let pkg: PackageJson = serde_json::from_str(json).unwrap();
+            pkg.workspace_patterns()
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            patterns(r#"{"workspaces": ["packages/*", "apps/web"]}"#),
+            vec!["packages/*", "apps/web"]
+        );
+        assert_eq!(
+            patterns(
+                r#"{"workspaces": {"packages": ["packages/*"], "nohoist": ["**/react-native"]}}"#
+            ),
+            vec!["packages/*"]
+        );
+        assert!(patterns(r#"{"workspaces": {"nohoist": ["**/x"]}}"#).is_empty());
+        assert!(patterns(r#"{"workspaces": true}"#).is_empty());
+        assert!(patterns(r#"{}"#).is_empty());
+    }
+
+    #[test]
+    fn test_yarn_berry_workspaces_foreach() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let path = root.join("package.json");
+        fs::write(
+            &path,
+            r#"{
+                "packageManager": "yarn@4.1.0",
+                "workspaces": ["packages/*"],
+                "scripts": { "lint": "eslint ." }
+            }"#,
+        )
+        .unwrap();
+        for (name, scripts) in [
+            ("a", r#"{"build": "tsc", "test": "jest"}"#),
+            ("b", r#"{"test": "vitest", "dev": "vite"}"#),
+        ] {
+            let pkg = root.join("packages").join(name);
+            fs::create_dir_all(&pkg).unwrap();
+            fs::write(
+                pkg.join("package.json"),
+                format!(r#"{{"scripts": {}}}"#, scripts),
+            )
+            .unwrap();
+        }
+
+        let runner = PackageJsonParser.parse(&path).unwrap().unwrap();
+        let names: Vec<_> = runner.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "lint",
+                "workspaces:build",
+                "workspaces:test",
+                "workspaces:dev"
+            ]
+        );
+
+        let test = &runner.tasks[2];
+        assert_eq!(test.command, "yarn workspaces foreach --all run test");
+        assert!(test.metadata.workspace);
+        assert!(!runner.tasks[0].metadata.workspace);
+
+        // Yarn 1 has no `workspaces foreach`, so the root only gets `install`
+        fs::write(
+            &path,
+            r#"{"packageManager": "yarn@1.22.19", "workspaces": ["packages/*"]}"#,
+        )
+        .unwrap();
+        let runner = PackageJsonParser.parse(&path).unwrap().unwrap();
+        let commands: Vec<_> = runner.tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(commands, ["yarn install"]);
+    }
+
+    #[test]
+    fn test_yarn_berry_workspaces_foreach_object_form() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let path = root.join("package.json");
+        fs::write(
+            &path,
+            r#"{
+                "packageManager": "yarn@4.1.0",
+                "workspaces": {"packages": ["packages/*"], "nohoist": ["**/react-native"]}
+            }"#,
+        )
+        .unwrap();
+        let pkg = root.join("packages/a");
+        fs::create_dir_all(&pkg).unwrap();
+        fs::write(pkg.join("package.json"), r#"{"scripts": {"build": "tsc"}}"#).unwrap();
+
+        let runner = PackageJsonParser.parse(&path).unwrap().unwrap();
+        let commands: Vec<_> = runner.tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(commands, ["yarn workspaces foreach --all run build"]);
+
+        // The member finds its root through the object form too
+        let member = PackageJsonParser
+            .parse(&pkg.join("package.json"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            member.workspace.as_deref(),
+            root.file_name().and_then(|n| n.to_str())
+        );
+    }
+
+    #[test]
+    fn test_workspace_member_name() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().join("monorepo");
+        fs::create_dir_all(root.join("packages/a")).unwrap();
+        fs::create_dir_all(root.join("packages/internal")).unwrap();
+        fs::create_dir_all(root.join("tools/b")).unwrap();
+        let scripts = r#"{"scripts": {"build": "tsc"}}"#;
+        for member in ["packages/a", "packages/internal", "tools/b"] {
+            fs::write(root.join(member).join("package.json"), scripts).unwrap();
+        }
+        fs::write(
+            root.join("package.json"),
+            r#"{"name": "@acme/root", "workspaces": ["packages/*", "!packages/internal"], "scripts": {"lint": "eslint ."}}"#,
+        )
+        .unwrap();
+
+        let workspace = |member: &str| {
+            PackageJsonParser
+                .parse(&root.join(member).join("package.json"))
+                .unwrap()
+                .unwrap()
+                .workspace
+        };
+        assert_eq!(workspace("packages/a").as_deref(), Some("@acme/root"));
+        assert_eq!(workspace("packages/internal"), None);
+        assert_eq!(workspace("tools/b"), None);
+        assert_eq!(workspace(""), None);
+
+        // pnpm declares its workspace in pnpm-workspace.yaml; an unnamed
+        // root is named after its folder. The cached root is read again
+        // once its files change.
+        fs::write(root.join("package.json"), scripts).unwrap();
+        fs::write(
+            root.join("pnpm-workspace.yaml"),
+            "packages:\n  - 'packages/*'\n  - tools/**\n",
+        )
+        .unwrap();
+        assert_eq!(workspace("packages/a").as_deref(), Some("monorepo"));
+        assert_eq!(workspace("tools/b").as_deref(), Some("monorepo"));
+
+        // A workspace root above the package's own repository doesn't count
+        fs::create_dir_all(root.join("tools/b/.git")).unwrap();
+        assert_eq!(workspace("tools/b"), None);
+        fs::create_dir_all(root.join("packages/.git")).unwrap();
+        assert_eq!(workspace("packages/a"), None);
+    }
+
+    #[test]
+    fn test_workspace_member_uses_root_lockfile() {
+        let dir = TempDir::new().unwrap();
+        let member = dir.path().join("packages/a");
+        fs::create_dir_all(&member).unwrap();
+        fs::write(
+            member.join("package.json"),
+            r#"{"scripts": {"build": "tsc"}}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("package.json"), "{}").unwrap();
+        fs::write(
+            dir.path().join("pnpm-workspace.yaml"),
+            "packages:\n  - 'packages/*'\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("pnpm-lock.yaml"),
+            "lockfileVersion: '9.0'\n",
+        )
+        .unwrap();
+
+        let runner = PackageJsonParser
+            .parse(&member.join("package.json"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(runner.runner_type, RunnerType::Pnpm);
+        assert_eq!(runner.tasks[0].command, "pnpm run build");
+    }
 }
@@ -1,12 +1,12 @@
 //! Parser for Maven pom.xml files
 
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use quick_xml::de::from_str;
 use serde::Deserialize;
 
-use super::Parser;
+use super::{ancestors, read_config, Parser};
 use crate::{RunnerType, ScanError, Task, TaskRunner};
 
 /// Standard Maven lifecycle phases that are always available
@@ -24,10 +24,25 @@ const LIFECYCLE_PHASES: &[(&str, &str)] = &[
 #[derive(Debug, Deserialize, Default)]
 #[serde(default)]
 struct Project {
+    parent: Option<Parent>,
+    modules: Option<Modules>,
     build: Option<Build>,
     profiles: Option<Profiles>,
 }
 
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct Parent {
+    #[serde(rename = "relativePath")]
+    relative_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct Modules {
+    module: Vec<String>,
+}
+
 #[derive(Debug, Deserialize, Default)]
 #[serde(default)]
 struct Build {
@@ -81,25 +96,110 @@ struct Profile {
 
 pub struct PomXmlParser;
 
+impl PomXmlParser {
+    /// Find the project root Maven uses for `.mvn/`: the nearest ancestor
+    /// containing a `.mvn` directory, else the pom's own directory
+    fn project_root(dir: &Path) -> PathBuf {
+        ancestors(dir)
+            .find(|d| d.join(".mvn").is_dir())
+            .unwrap_or(dir)
+            .to_path_buf()
+    }
+
+    /// Whether the pom in `pom_dir` is a module of its `<parent>` aggregator
+    /// (found at `relativePath`, `../pom.xml` by default). Its lifecycle phases
+    /// are then left to the aggregator's `-pl` tasks instead of being repeated
+    /// for every module.
+    fn is_aggregated(pom_dir: &Path, parent: &Parent) -> bool {
+        let relative = parent.relative_path.as_deref().unwrap_or("../pom.xml");
+        if relative.trim().is_empty() {
+            // An empty relativePath means the parent comes from a repository
+            return false;
+        }
+        let mut parent_pom = pom_dir.join(relative.trim());
+        if parent_pom.is_dir() {
+            parent_pom.push("pom.xml");
+        }
+        let Ok(content) = read_config(&parent_pom) else {
+            return false;
+        };
+        let Ok(parent_project) = from_str::<Project>(&content) else {
+            return false;
+        };
+        let (Some(modules), Some(aggregator_dir)) = (parent_project.modules, parent_pom.parent())
+        else {
+            return false;
+        };
+        let pom_dir = fs::canonicalize(pom_dir).unwrap_or_else(|_| pom_dir.to_path_buf());
+        modules.module.iter().any(|module| {
+            fs::canonicalize(aggregator_dir.join(module.trim())).is_ok_and(|dir| dir == pom_dir)
+        })
+    }
+
+    /// Build the command prefix: the Maven wrapper if the project has one.
+    /// Maven applies `.mvn/maven.config` itself, so its options aren't repeated.
+    fn command_prefix(pom_dir: &Path) -> String {
+        let root = Self::project_root(pom_dir);
+
+        if root.join("mvnw").is_file() {
+            let depth = pom_dir
+                .strip_prefix(&root)
+                .map(|rel| rel.components().count())
+                .unwrap_or(0);
+            if depth == 0 {
+                "./mvnw".to_string()
+            } else {
+                format!("{}mvnw", "../".repeat(depth))
+            }
+        } else {
+            "mvn".to_string()
+        }
+    }
+}
+
 impl Parser for PomXmlParser {
     fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
-        let content = fs::read_to_string(path)?;
+        let content = read_config(path)?;
 
         let project: Project = from_str(&content).map_err(|e| ScanError::ParseError {
             path: path.to_path_buf(),
             message: e.to_string(),
         })?;
 
+        let pom_dir = path.parent().unwrap_or(Path::new("."));
+        let prefix = Self::command_prefix(pom_dir);
         let mut tasks: Vec<Task> = Vec::new();
 
-        // Add standard lifecycle phases
-        for (phase, description) in LIFECYCLE_PHASES {
-            tasks.push(Task {
-                name: phase.to_string(),
-                command: format!("mvn {}", phase),
-                description: Some(description.to_string()),
-                script: None,
-            });
+        // Add standard lifecycle phases, unless an aggregator pom above
+        // already covers this module
+        let aggregated = project
+            .parent
+            .as_ref()
+            .is_some_and(|parent| Self::is_aggregated(pom_dir, parent));
+        if !aggregated {
+            for (phase, description) in LIFECYCLE_PHASES {
+                tasks.push(Task {
+                    name: phase.to_string(),
+                    command: format!("{} {}", prefix, phase),
+                    description: Some(description.to_string()),
+                    script: None,
+                    ..Default::default()
+                });
+            }
+        }
+
+        // Add per-module builds of an aggregator pom
+        if let Some(modules) = &project.modules {
+            for module in &modules.module {
+                let module = module.trim();
+                tasks.push(Task {
+                    name: format!("package -pl {}", module),
+                    command: format!("{} -pl {} package", prefix, module),
+                    description: Some(format!("Package the {} module", module)),
+                    script: None,
+                    ..Default::default()
+                });
+            }
         }
 
         // Add profile-specific tasks
@@ -108,9 +208,10 @@ impl Parser for PomXmlParser {
                 if let Some(id) = profile.id {
                     tasks.push(Task {
                         name: format!("package -P{}", id),
-                        command: format!("mvn package -P{}", id),
+                        command: format!("{} package -P{}", prefix, id),
                         description: Some(format!("Package with '{}' profile", id)),
                         script: None,
+                        ..Default::default()
                     });
                 }
             }
@@ -133,12 +234,13 @@ impl Parser for PomXmlParser {
                                     };
                                     tasks.push(Task {
                                         name: task_name.clone(),
-                                        command: format!("mvn {}", task_name),
+                                        command: format!("{} {}", prefix, task_name),
                                         description: Some(format!(
                                             "Run {} goal from {}",
                                             goal, plugin_name
                                         )),
                                         script: None,
+                                        ..Default::default()
                                     });
                                 }
                             }
@@ -156,6 +258,7 @@ impl Parser for PomXmlParser {
             config_path: path.to_path_buf(),
             runner_type: RunnerType::Maven,
             tasks,
+            ..Default::default()
         }))
     }
 }
@@ -222,4 +325,83 @@ mod tests {
         assert!(runner.tasks.iter().any(|t| t.name == "package -Pdev"));
         assert!(runner.tasks.iter().any(|t| t.name == "package -Pprod"));
     }
+
+    #[test]
+    fn test_wrapper_and_maven_config() {
+        let dir = TempDir::new().unwrap();
+        let mvn_dir = dir.path().join(".mvn");
+        fs::create_dir_all(&mvn_dir).unwrap();
+        fs::write(mvn_dir.join("maven.config"), "-T 4\n-Pci,fast\n").unwrap();
+        fs::write(dir.path().join("mvnw"), "#!/bin/sh\n").unwrap();
+
+        let module_dir = dir.path().join("core");
+        fs::create_dir_all(&module_dir).unwrap();
+        let pom = r#"<project><modelVersion>4.0.0</modelVersion></project>"#;
+        fs::write(dir.path().join("pom.xml"), pom).unwrap();
+        fs::write(module_dir.join("pom.xml"), pom).unwrap();
+
+        let parser = PomXmlParser;
+
+        let runner = parser.parse(&dir.path().join("pom.xml")).unwrap().unwrap();
+        let test = runner.tasks.iter().find(|t| t.name == "test").unwrap();
+        // Maven reads maven.config on its own; the profiles aren't repeated
+        assert_eq!(test.command, "./mvnw test");
+
+        // Modules use the wrapper from the project root
+        let runner = parser.parse(&module_dir.join("pom.xml")).unwrap().unwrap();
+        let test = runner.tasks.iter().find(|t| t.name == "test").unwrap();
+        assert_eq!(test.command, "../mvnw test");
+    }
+
+    #[test]
+    fn test_multi_module_project() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("pom.xml"),
+            r#"<project>
+    <modelVersion>4.0.0</modelVersion>
+    <artifactId>parent</artifactId>
+    <packaging>pom</packaging>
+    <modules>
+        <module>core</module>
+        <module>web</module>
+    </modules>
+</project>"#,
+        )
+        .unwrap();
+        let child = r#"<project>
+    <modelVersion>4.0.0</modelVersion>
+    <parent><artifactId>parent</artifactId></parent>
+    <profiles><profile><id>dev</id></profile></profiles>
+</project>"#;
+        for module in ["core", "web"] {
+            fs::create_dir(dir.path().join(module)).unwrap();
+            fs::write(dir.path().join(module).join("pom.xml"), child).unwrap();
+        }
+
+        let parser = PomXmlParser;
+        let root = parser.parse(&dir.path().join("pom.xml")).unwrap().unwrap();
+        let modules: Vec<_> = root
+            .tasks
+            .iter()
+            .filter(|t| t.name.starts_with("package -pl"))
+            .map(|t| (t.command.as_str(), t.description.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            modules,
+            vec![
+                ("mvn -pl core package", "Package the core module"),
+                ("mvn -pl web package", "Package the web module"),
+            ]
+        );
+        assert!(root.tasks.iter().any(|t| t.name == "compile"));
+
+        // Modules only keep what's specific to them
+        let core = parser
+            .parse(&dir.path().join("core/pom.xml"))
+            .unwrap()
+            .unwrap();
+        let names: Vec<_> = core.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["package -Pdev"]);
+    }
 }
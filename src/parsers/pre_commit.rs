@@ -0,0 +1,125 @@
+//! Parser for .pre-commit-config.yaml (pre-commit framework hooks)
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::{read_config, Parser};
+
+#[derive(Deserialize)]
+struct PreCommitConfig {
+    #[serde(default)]
+    repos: Vec<Repo>,
+}
+
+#[derive(Deserialize)]
+struct Repo {
+    #[serde(default)]
+    hooks: Vec<Hook>,
+}
+
+#[derive(Deserialize)]
+struct Hook {
+    id: String,
+    name: Option<String>,
+}
+
+pub struct PreCommitParser;
+
+impl Parser for PreCommitParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let content = read_config(path)?;
+
+        let config: PreCommitConfig =
+            serde_saphyr::from_str(&content).map_err(|e| ScanError::ParseError {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            })?;
+
+        let hooks: Vec<Hook> = config.repos.into_iter().flat_map(|r| r.hooks).collect();
+        if hooks.is_empty() {
+            return Ok(None);
+        }
+
+        let mut tasks = vec![Task {
+            name: "all".to_string(),
+            command: "pre-commit run --all-files".to_string(),
+            description: Some("Run all hooks on all files".to_string()),
+            script: None,
+            ..Default::default()
+        }];
+
+        for hook in hooks {
+            // The same hook id can appear in several repos; running it runs all of them
+            if tasks.iter().skip(1).any(|t| t.name == hook.id) {
+                continue;
+            }
+            tasks.push(Task {
+                command: format!("pre-commit run {} --all-files", hook.id),
+                name: hook.id,
+                description: hook.name,
+                script: None,
+                ..Default::default()
+            });
+        }
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::PreCommit,
+            tasks,
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_pre_commit_config() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".pre-commit-config.yaml");
+        fs::write(
+            &path,
+            r#"
+repos:
+  - repo: https://github.com/pre-commit/pre-commit-hooks
+    rev: v4.5.0
+    hooks:
+      - id: trailing-whitespace
+      - id: end-of-file-fixer
+  - repo: https://github.com/astral-sh/ruff-pre-commit
+    rev: v0.4.0
+    hooks:
+      - id: ruff
+        name: Ruff linter
+        args: [--fix]
+  - repo: local
+    hooks:
+      - id: ruff
+        name: ruff (local)
+        entry: ruff
+        language: system
+"#,
+        )
+        .unwrap();
+
+        let parser = PreCommitParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::PreCommit);
+        let names: Vec<_> = runner.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["all", "trailing-whitespace", "end-of-file-fixer", "ruff"]
+        );
+        assert_eq!(runner.tasks[0].command, "pre-commit run --all-files");
+        assert_eq!(runner.tasks[3].command, "pre-commit run ruff --all-files");
+        assert_eq!(runner.tasks[3].description.as_deref(), Some("Ruff linter"));
+    }
+}
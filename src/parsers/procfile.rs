@@ -0,0 +1,113 @@
+//! Parser for Procfile (Foreman-style process definitions)
+//!
+//! Each `name: command` line declares a process, run with `foreman start <name>`
+//! (plus `-f <file>` for files other than `Procfile`, like `Procfile.dev`).
+
+use std::path::Path;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::{read_config, Parser};
+
+pub struct ProcfileParser;
+
+impl ProcfileParser {
+    /// Parse `name: command` lines, skipping blanks, comments and malformed lines
+    fn parse_processes(content: &str) -> Vec<(String, String)> {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let (name, command) = line.split_once(':')?;
+                let (name, command) = (name.trim(), command.trim());
+                let valid_name = !name.is_empty()
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+                (valid_name && !command.is_empty()).then(|| (name.to_string(), command.to_string()))
+            })
+            .collect()
+    }
+}
+
+impl Parser for ProcfileParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let content = read_config(path)?;
+        let processes = Self::parse_processes(&content);
+
+        if processes.is_empty() {
+            return Ok(None);
+        }
+
+        // foreman reads ./Procfile unless told otherwise
+        let file_flag = match path.file_name().and_then(|n| n.to_str()) {
+            Some("Procfile") | None => String::new(),
+            Some(file_name) => format!("-f {} ", file_name),
+        };
+
+        let tasks = processes
+            .into_iter()
+            .map(|(name, command)| Task {
+                command: format!("foreman start {}{}", file_flag, name),
+                name,
+                description: None,
+                script: Some(command),
+                ..Default::default()
+            })
+            .collect();
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Foreman,
+            tasks,
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_procfile() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Procfile");
+        fs::write(
+            &path,
+            "# Processes\nweb: bundle exec puma -C config/puma.rb\n\nworker: bundle exec sidekiq\n",
+        )
+        .unwrap();
+
+        let parser = ProcfileParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::Foreman);
+        assert_eq!(runner.tasks.len(), 2);
+        assert_eq!(runner.tasks[0].name, "web");
+        assert_eq!(runner.tasks[0].command, "foreman start web");
+        assert_eq!(
+            runner.tasks[0].script.as_deref(),
+            Some("bundle exec puma -C config/puma.rb")
+        );
+        assert_eq!(runner.tasks[1].command, "foreman start worker");
+    }
+
+    #[test]
+    fn test_procfile_variant_is_passed_to_foreman() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Procfile.dev");
+        fs::write(
+            &path,
+            "web: bin/rails server -p 3000\ncss: bin/rails tailwindcss:watch\n",
+        )
+        .unwrap();
+
+        let runner = ProcfileParser.parse(&path).unwrap().unwrap();
+        assert_eq!(runner.tasks[0].command, "foreman start -f Procfile.dev web");
+        assert_eq!(runner.tasks[1].command, "foreman start -f Procfile.dev css");
+    }
+}
@@ -1,29 +1,30 @@
 //! Parser for pubspec.yaml (Flutter/Dart projects)
 
 use std::collections::HashMap;
-use std::fs;
 use std::path::Path;
 
+use indexmap::IndexMap;
 use serde::de::IgnoredAny;
 use serde::Deserialize;
 
 use crate::{RunnerType, ScanError, Task, TaskRunner};
 
-use super::Parser;
+use super::{read_config, summary_line, Parser};
 
 /// We only care about the presence of keys, not their values
 /// Using IgnoredAny allows any YAML value without deserializing it
 #[derive(Deserialize)]
 struct PubspecYaml {
     name: Option<String>,
+    description: Option<String>,
     #[serde(default)]
     dependencies: HashMap<String, IgnoredAny>,
     #[serde(default)]
     dev_dependencies: HashMap<String, IgnoredAny>,
     #[serde(default)]
-    executables: HashMap<String, String>,
+    executables: IndexMap<String, String>,
     #[serde(default)]
-    scripts: HashMap<String, String>, // For derry or similar
+    scripts: IndexMap<String, String>, // For derry or similar
 }
 
 pub struct PubspecYamlParser;
@@ -37,7 +38,7 @@ impl PubspecYamlParser {
 
 impl Parser for PubspecYamlParser {
     fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
-        let content = fs::read_to_string(path)?;
+        let content = read_config(path)?;
 
         let pubspec: PubspecYaml =
             serde_saphyr::from_str(&content).map_err(|e| ScanError::ParseError {
@@ -61,6 +62,7 @@ impl Parser for PubspecYamlParser {
                 command: format!("derry {}", name),
                 description: Some(command.clone()),
                 script: Some(command.clone()),
+                ..Default::default()
             });
         }
 
@@ -71,6 +73,7 @@ impl Parser for PubspecYamlParser {
                 command: format!("dart run {}", name),
                 description: Some(format!("Run the {} executable", name)),
                 script: None,
+                ..Default::default()
             });
         }
 
@@ -84,30 +87,35 @@ impl Parser for PubspecYamlParser {
                 command: "flutter run".to_string(),
                 description: Some("Run the Flutter app".to_string()),
                 script: None,
+                ..Default::default()
             });
             tasks.push(Task {
                 name: "test".to_string(),
                 command: "flutter test".to_string(),
                 description: Some("Run Flutter tests".to_string()),
                 script: None,
+                ..Default::default()
             });
             tasks.push(Task {
                 name: "build-apk".to_string(),
                 command: "flutter build apk".to_string(),
                 description: Some("Build Android APK".to_string()),
                 script: None,
+                ..Default::default()
             });
             tasks.push(Task {
                 name: "build-ios".to_string(),
                 command: "flutter build ios".to_string(),
                 description: Some("Build iOS app".to_string()),
                 script: None,
+                ..Default::default()
             });
             tasks.push(Task {
                 name: "analyze".to_string(),
                 command: "flutter analyze".to_string(),
                 description: Some("Analyze Dart code".to_string()),
                 script: None,
+                ..Default::default()
             });
 
             if has_build_runner {
@@ -116,12 +124,14 @@ impl Parser for PubspecYamlParser {
                     command: "dart run build_runner build".to_string(),
                     description: Some("Run code generation".to_string()),
                     script: None,
+                    ..Default::default()
                 });
                 tasks.push(Task {
                     name: "build_runner-watch".to_string(),
                     command: "dart run build_runner watch".to_string(),
                     description: Some("Watch and regenerate code".to_string()),
                     script: None,
+                    ..Default::default()
                 });
             }
         } else if pubspec.name.is_some() {
@@ -131,18 +141,21 @@ impl Parser for PubspecYamlParser {
                 command: "dart run".to_string(),
                 description: Some("Run the Dart app".to_string()),
                 script: None,
+                ..Default::default()
             });
             tasks.push(Task {
                 name: "test".to_string(),
                 command: "dart test".to_string(),
                 description: Some("Run Dart tests".to_string()),
                 script: None,
+                ..Default::default()
             });
             tasks.push(Task {
                 name: "analyze".to_string(),
                 command: "dart analyze".to_string(),
                 description: Some("Analyze Dart code".to_string()),
                 script: None,
+                ..Default::default()
             });
         }
 
@@ -153,7 +166,9 @@ impl Parser for PubspecYamlParser {
         Ok(Some(TaskRunner {
             config_path: path.to_path_buf(),
             runner_type,
+            description: pubspec.description.as_deref().and_then(summary_line),
             tasks,
+            ..Default::default()
         }))
     }
 }
@@ -197,6 +212,7 @@ dev_dependencies:
             &path,
             r#"
 name: my_dart_cli
+description: A command-line tool.
 executables:
   mycli: main
 "#,
@@ -207,6 +223,7 @@ executables:
         let runner = parser.parse(&path).unwrap().unwrap();
 
         assert_eq!(runner.runner_type, RunnerType::Dart);
+        assert_eq!(runner.description.as_deref(), Some("A command-line tool."));
         assert!(runner.tasks.iter().any(|t| t.name == "mycli"));
     }
 }
@@ -1,15 +1,14 @@
-//! Parser for pyproject.toml (Poetry, PDM, PEP 621)
+//! Parser for pyproject.toml (Poetry, PDM, Hatch, PEP 621)
 
-use std::collections::HashMap;
-use std::fs;
 use std::path::Path;
 
+use indexmap::IndexMap;
 use serde::Deserialize;
 use toml::Value;
 
 use crate::{RunnerType, ScanError, Task, TaskRunner};
 
-use super::Parser;
+use super::{read_config, Parser};
 
 #[derive(Deserialize)]
 struct PyprojectToml {
@@ -21,21 +20,33 @@ struct PyprojectToml {
 struct Tool {
     poetry: Option<PoetryConfig>,
     pdm: Option<PdmConfig>,
+    hatch: Option<HatchConfig>,
+    uv: Option<Value>,
 }
 
 #[derive(Deserialize)]
 struct PoetryConfig {
-    scripts: Option<HashMap<String, Value>>,
+    scripts: Option<IndexMap<String, Value>>,
 }
 
 #[derive(Deserialize)]
 struct PdmConfig {
-    scripts: Option<HashMap<String, Value>>,
+    scripts: Option<IndexMap<String, Value>>,
+}
+
+#[derive(Deserialize)]
+struct HatchConfig {
+    envs: Option<IndexMap<String, HatchEnv>>,
+}
+
+#[derive(Deserialize)]
+struct HatchEnv {
+    scripts: Option<IndexMap<String, Value>>,
 }
 
 #[derive(Deserialize)]
 struct Project {
-    scripts: Option<HashMap<String, String>>,
+    scripts: Option<IndexMap<String, String>>,
 }
 
 pub struct PyprojectTomlParser;
@@ -45,20 +56,39 @@ impl PyprojectTomlParser {
         match value {
             Value::String(s) => Some(s.clone()),
             Value::Table(t) => {
-                // Handle {call = "module:func"} or {cmd = "command"} format
+                // Handle {call = "module:func"}, {cmd = "command"} or {shell = "..."} format
                 t.get("call")
                     .or_else(|| t.get("cmd"))
+                    .or_else(|| t.get("shell"))
                     .and_then(|v| v.as_str())
                     .map(|s| s.to_string())
             }
             _ => None,
         }
     }
+
+    /// A Hatch script: one command, or a list run in order (stopping at the
+    /// first failure)
+    fn extract_hatch_command(value: &Value) -> Option<String> {
+        match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Array(commands) => {
+                let commands: Vec<&str> = commands.iter().filter_map(Value::as_str).collect();
+                (!commands.is_empty()).then(|| commands.join(" && "))
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the `help` text of a table-form script (PDM), if declared
+    fn extract_script_help(value: &Value) -> Option<String> {
+        value.get("help")?.as_str().map(|s| s.to_string())
+    }
 }
 
 impl Parser for PyprojectTomlParser {
     fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
-        let content = fs::read_to_string(path)?;
+        let content = read_config(path)?;
 
         let pyproject: PyprojectToml =
             toml::from_str(&content).map_err(|e| ScanError::ParseError {
@@ -81,6 +111,7 @@ impl Parser for PyprojectTomlParser {
                                 command: format!("poetry run {}", name),
                                 description: Some(cmd.clone()),
                                 script: Some(cmd),
+                                ..Default::default()
                             });
                         }
                     }
@@ -97,8 +128,40 @@ impl Parser for PyprojectTomlParser {
                             tasks.push(Task {
                                 name: name.clone(),
                                 command: format!("pdm run {}", name),
+                                description: Self::extract_script_help(value)
+                                    .or_else(|| Some(cmd.clone())),
+                                script: Some(cmd),
+                                ..Default::default()
+                            });
+                        }
+                    }
+                }
+            }
+
+            // Hatch scripts, per environment, unless Poetry/PDM already has scripts
+            if let Some(envs) = tool.hatch.as_ref().and_then(|hatch| hatch.envs.as_ref()) {
+                if tasks.is_empty() {
+                    for (env, config) in envs {
+                        let Some(scripts) = &config.scripts else {
+                            continue;
+                        };
+                        runner_type = RunnerType::Hatch;
+                        for (name, value) in scripts {
+                            let Some(cmd) = Self::extract_hatch_command(value) else {
+                                continue;
+                            };
+                            // Scripts of the default env run without an env prefix
+                            let name = if env == "default" {
+                                name.clone()
+                            } else {
+                                format!("{}:{}", env, name)
+                            };
+                            tasks.push(Task {
+                                command: format!("hatch run {}", name),
+                                name,
                                 description: Some(cmd.clone()),
                                 script: Some(cmd),
+                                ..Default::default()
                             });
                         }
                     }
@@ -106,15 +169,40 @@ impl Parser for PyprojectTomlParser {
             }
         }
 
-        // Check for PEP 621 project.scripts (entry points)
+        // Check for PEP 621 project.scripts (entry points), run in the
+        // environment of the tool managing the project: the one whose scripts
+        // were found, else the one with a `[tool.*]` section. Without one,
+        // uv projects use `uv run` and anything else the installed script.
+        let manager = match &pyproject.tool {
+            _ if !tasks.is_empty() => Some(runner_type),
+            Some(tool) if tool.pdm.is_some() => Some(RunnerType::Pdm),
+            Some(tool) if tool.poetry.is_some() => Some(RunnerType::Poetry),
+            Some(tool) if tool.hatch.is_some() => Some(RunnerType::Hatch),
+            _ => None,
+        };
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let uv = pyproject
+            .tool
+            .as_ref()
+            .is_some_and(|tool| tool.uv.is_some())
+            || dir.join("uv.lock").is_file();
         if let Some(project) = &pyproject.project {
             if let Some(scripts) = &project.scripts {
+                if let Some(manager) = manager {
+                    runner_type = manager;
+                }
                 for (name, entry_point) in scripts {
+                    let command = match manager {
+                        Some(manager) => format!("{} run {}", manager.binary(), name),
+                        None if uv => format!("uv run {}", name),
+                        None => name.clone(),
+                    };
                     tasks.push(Task {
                         name: name.clone(),
-                        command: name.clone(), // Entry points are installed as commands
+                        command,
                         description: Some(format!("Entry point: {}", entry_point)),
-                        script: None,
+                        script: Some(entry_point.clone()),
+                        ..Default::default()
                     });
                 }
             }
@@ -128,6 +216,7 @@ impl Parser for PyprojectTomlParser {
             config_path: path.to_path_buf(),
             runner_type,
             tasks,
+            ..Default::default()
         }))
     }
 }
@@ -159,7 +248,9 @@ lint = "ruff check ."
         let runner = parser.parse(&path).unwrap().unwrap();
 
         assert_eq!(runner.runner_type, RunnerType::Poetry);
-        assert_eq!(runner.tasks.len(), 2);
+        // Scripts keep the order they're declared in
+        let names: Vec<_> = runner.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["test", "lint"]);
 
         let test_task = runner.tasks.iter().find(|t| t.name == "test").unwrap();
         assert_eq!(test_task.command, "poetry run test");
@@ -186,6 +277,30 @@ test = { cmd = "pytest -v" }
         assert!(runner.tasks.iter().any(|t| t.name == "start"));
     }
 
+    #[test]
+    fn test_parse_pdm_script_help() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("pyproject.toml");
+        fs::write(
+            &path,
+            r#"
+[tool.pdm.scripts]
+lint = { shell = "ruff check . && mypy .", help = "Lint and type-check" }
+fmt = { cmd = "ruff format" }
+"#,
+        )
+        .unwrap();
+
+        let parser = PyprojectTomlParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        let lint = runner.tasks.iter().find(|t| t.name == "lint").unwrap();
+        assert_eq!(lint.description.as_deref(), Some("Lint and type-check"));
+        assert_eq!(lint.script.as_deref(), Some("ruff check . && mypy ."));
+        let fmt = runner.tasks.iter().find(|t| t.name == "fmt").unwrap();
+        assert_eq!(fmt.description.as_deref(), Some("ruff format"));
+    }
+
     #[test]
     fn test_parse_pep621_scripts() {
         let dir = TempDir::new().unwrap();
@@ -205,6 +320,95 @@ mycli = "myproject.cli:main"
         let parser = PyprojectTomlParser;
         let runner = parser.parse(&path).unwrap().unwrap();
 
-        assert!(runner.tasks.iter().any(|t| t.name == "mycli"));
+        // No tool manages the project, so the installed script runs as is
+        let mycli = runner.tasks.iter().find(|t| t.name == "mycli").unwrap();
+        assert_eq!(mycli.command, "mycli");
+        assert_eq!(mycli.script.as_deref(), Some("myproject.cli:main"));
+
+        fs::write(dir.path().join("uv.lock"), "version = 1\n").unwrap();
+        let runner = parser.parse(&path).unwrap().unwrap();
+        assert_eq!(runner.tasks[0].command, "uv run mycli");
+
+        // A tool section without scripts still means the tool runs it
+        fs::write(
+            &path,
+            "[project.scripts]\nmycli = \"myproject.cli:main\"\n\n[tool.pdm]\ndistribution = true\n",
+        )
+        .unwrap();
+        let runner = parser.parse(&path).unwrap().unwrap();
+        assert_eq!(runner.runner_type, RunnerType::Pdm);
+        assert_eq!(runner.tasks[0].command, "pdm run mycli");
+    }
+
+    #[test]
+    fn test_parse_hatch_env_scripts() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("pyproject.toml");
+        fs::write(
+            &path,
+            r#"
+[project]
+name = "app"
+
+[project.scripts]
+app = "app.cli:main"
+
+[tool.hatch.envs.default.scripts]
+lint = "ruff check ."
+test = ["pytest", "coverage report"]
+
+[tool.hatch.envs.docs]
+dependencies = ["mkdocs"]
+
+[tool.hatch.envs.docs.scripts]
+lint = "markdownlint docs"
+"#,
+        )
+        .unwrap();
+
+        let parser = PyprojectTomlParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::Hatch);
+        let commands: Vec<_> = runner.tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            vec![
+                "hatch run lint",
+                "hatch run test",
+                "hatch run docs:lint",
+                "hatch run app",
+            ]
+        );
+        assert_eq!(runner.tasks[2].name, "docs:lint");
+        assert_eq!(
+            runner.tasks[2].description.as_deref(),
+            Some("markdownlint docs")
+        );
+        assert_eq!(
+            runner.tasks[1].script.as_deref(),
+            Some("pytest && coverage report")
+        );
+    }
+
+    #[test]
+    fn test_poetry_scripts_take_precedence_over_hatch() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("pyproject.toml");
+        fs::write(
+            &path,
+            r#"
+[tool.poetry.scripts]
+serve = "app:serve"
+
+[tool.hatch.envs.default.scripts]
+lint = "ruff check ."
+"#,
+        )
+        .unwrap();
+
+        let runner = PyprojectTomlParser.parse(&path).unwrap().unwrap();
+        assert_eq!(runner.runner_type, RunnerType::Poetry);
+        assert_eq!(runner.tasks.len(), 1);
     }
 }
@@ -0,0 +1,220 @@
+//! Simple parser for Ruby Rakefiles (`Rakefile`, `rakefile`, `Rakefile.rb`)
+//!
+//! Line-based: picks up `task :name`, `task name: deps` and `task "name"`
+//! declarations, prefixing them with any enclosing `namespace :ns do` blocks.
+//! A `desc "..."` line describes the task declared after it.
+
+use std::path::Path;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::{read_config, Parser};
+
+/// Keywords that open a block closed by `end`
+const BLOCK_KEYWORDS: &[&str] = &[
+    "def", "class", "module", "if", "unless", "case", "begin", "while", "until",
+];
+
+pub struct RakefileParser;
+
+impl RakefileParser {
+    /// Parse `(name, description)` pairs from Rakefile content, in declaration order
+    fn parse_tasks(content: &str) -> Vec<(String, Option<String>)> {
+        let mut tasks: Vec<(String, Option<String>)> = Vec::new();
+        // Open `do`/keyword blocks, with the namespace each one opened (if any)
+        let mut blocks: Vec<Option<String>> = Vec::new();
+        let mut description: Option<String> = None;
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line == "end" || line.starts_with("end ") || line.starts_with("end.") {
+                blocks.pop();
+                continue;
+            }
+
+            if let Some(rest) = keyword_args(line, "desc") {
+                description = quoted(rest).map(str::to_string);
+                continue;
+            }
+
+            let opens_block = opens_block(line);
+            if let Some(rest) = keyword_args(line, "namespace") {
+                let ns = Self::task_name(rest);
+                if opens_block {
+                    blocks.push(ns);
+                }
+                continue;
+            }
+
+            if let Some(name) = keyword_args(line, "task").and_then(Self::task_name) {
+                let full_name = blocks
+                    .iter()
+                    .flatten()
+                    .map(String::as_str)
+                    .chain([name.as_str()])
+                    .collect::<Vec<_>>()
+                    .join(":");
+                let description = description.take();
+                if !tasks.iter().any(|(n, _)| *n == full_name) {
+                    tasks.push((full_name, description));
+                }
+            }
+
+            if opens_block {
+                blocks.push(None);
+            }
+        }
+        tasks
+    }
+
+    /// Name from a task/namespace declaration: `:name`, `"name"`, or `name: deps`
+    fn task_name(args: &str) -> Option<String> {
+        let args = args.trim_start_matches('(').trim_start();
+        let name = if let Some(symbol) = args.strip_prefix(':') {
+            symbol
+                .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .next()
+                .unwrap_or_default()
+        } else if let Some(name) = quoted(args) {
+            name
+        } else {
+            let (name, _) = args.split_once(':')?;
+            name
+        };
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | ':'));
+        valid.then(|| name.to_string())
+    }
+}
+
+/// The arguments after a leading `keyword` (followed by a space or `(`)
+fn keyword_args<'a>(line: &'a str, keyword: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(keyword)?;
+    rest.starts_with([' ', '(']).then(|| rest.trim_start())
+}
+
+/// The contents of a leading single- or double-quoted string
+fn quoted(text: &str) -> Option<&str> {
+    let text = text.trim_start_matches('(').trim_start();
+    let quote = text.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    let rest = &text[1..];
+    rest.find(quote).map(|end| &rest[..end])
+}
+
+/// Whether a line opens a block that a later `end` closes
+fn opens_block(line: &str) -> bool {
+    // Ignore a trailing comment; the block may take parameters (`do |t, args|`)
+    let code = line.split(" #").next().unwrap_or(line).trim_end();
+    if code.ends_with(" do") || (code.ends_with('|') && code.contains(" do |")) {
+        return true;
+    }
+    let first = code.split_whitespace().next().unwrap_or_default();
+    BLOCK_KEYWORDS.contains(&first)
+}
+
+impl Parser for RakefileParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let content = read_config(path)?;
+        let tasks: Vec<Task> = Self::parse_tasks(&content)
+            .into_iter()
+            .map(|(name, description)| Task {
+                command: format!("rake {}", name),
+                name,
+                description,
+                script: None,
+                ..Default::default()
+            })
+            .collect();
+
+        if tasks.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Rake,
+            tasks,
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_rakefile() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Rakefile");
+        fs::write(
+            &path,
+            r#"
+require_relative "config/application"
+
+desc "Run the test suite"
+task :test do
+  if ENV["CI"]
+    sh "bin/rails test --fail-fast"
+  end
+end
+
+task default: [:test]
+
+namespace :db do
+  desc 'Seed the database'
+  task seed: :environment do
+    Seed.run
+  end
+
+  namespace :cache do
+    task :clear, [:scope] => :environment do |t, args|
+      Rails.cache.clear
+    end
+  end
+
+  task "reset" => ["db:drop", "db:setup"]
+end
+
+task :lint
+"#,
+        )
+        .unwrap();
+
+        let parser = RakefileParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::Rake);
+        let names: Vec<_> = runner.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "test",
+                "default",
+                "db:seed",
+                "db:cache:clear",
+                "db:reset",
+                "lint"
+            ]
+        );
+        assert_eq!(runner.tasks[2].command, "rake db:seed");
+        assert_eq!(
+            runner.tasks[0].description.as_deref(),
+            Some("Run the test suite")
+        );
+        assert_eq!(
+            runner.tasks[2].description.as_deref(),
+            Some("Seed the database")
+        );
+        assert_eq!(runner.tasks[1].description, None);
+        assert_eq!(runner.tasks[3].description, None);
+    }
+}
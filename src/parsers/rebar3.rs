@@ -0,0 +1,181 @@
+//! Parser for rebar.config (Rebar3, Erlang)
+//!
+//! The standard rebar3 commands are always listed. Custom aliases come from
+//! the `{alias, [...]}` term; that's a focused best-effort scan of the one
+//! tuple rather than a full Erlang term parser, so unusual layouts (macros,
+//! aliases built elsewhere) may be missed.
+
+use std::path::Path;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::{read_config, Parser};
+
+/// Standard commands listed for every project
+const STANDARD_COMMANDS: &[(&str, &str)] = &[
+    ("compile", "Compile the project"),
+    ("eunit", "Run EUnit tests"),
+    ("ct", "Run Common Test suites"),
+    ("shell", "Start a shell with the project loaded"),
+    ("release", "Build a release"),
+];
+
+pub struct Rebar3Parser;
+
+impl Rebar3Parser {
+    /// Drop `%` comments, leaving string contents alone
+    fn strip_comments(content: &str) -> String {
+        content
+            .lines()
+            .map(|line| {
+                let mut in_string = false;
+                let end = line
+                    .char_indices()
+                    .find(|&(_, c)| {
+                        if c == '"' {
+                            in_string = !in_string;
+                        }
+                        c == '%' && !in_string
+                    })
+                    .map_or(line.len(), |(i, _)| i);
+                &line[..end]
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// `(name, expansion)` for each `{name, [...]}` entry of the alias list
+    fn parse_aliases(content: &str) -> Vec<(String, String)> {
+        let content = Self::strip_comments(content);
+        let Some(start) = content.find("{alias,") else {
+            return Vec::new();
+        };
+        let Some(list_start) = content[start..].find('[').map(|i| start + i + 1) else {
+            return Vec::new();
+        };
+
+        let mut aliases = Vec::new();
+        // Nesting inside the alias list; entries open at depth 0
+        let mut depth = 0;
+        let mut entry_start = None;
+        for (i, c) in content[list_start..].char_indices() {
+            let i = list_start + i;
+            match c {
+                '{' | '[' => {
+                    if depth == 0 && c == '{' {
+                        entry_start = Some(i + 1);
+                    }
+                    depth += 1;
+                }
+                '}' | ']' => {
+                    if depth == 0 {
+                        // End of the alias list
+                        break;
+                    }
+                    depth -= 1;
+                    if depth == 0 && c == '}' {
+                        if let Some(entry) = entry_start.take().map(|s| &content[s..i]) {
+                            if let Some((name, expansion)) = entry.split_once(',') {
+                                let expansion =
+                                    expansion.split_whitespace().collect::<Vec<_>>().join(" ");
+                                aliases.push((name.trim().to_string(), expansion));
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        aliases
+    }
+}
+
+impl Parser for Rebar3Parser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let content = read_config(path)?;
+
+        let mut tasks: Vec<Task> = STANDARD_COMMANDS
+            .iter()
+            .map(|(name, description)| Task {
+                name: name.to_string(),
+                command: format!("rebar3 {}", name),
+                description: Some(description.to_string()),
+                script: None,
+                ..Default::default()
+            })
+            .collect();
+
+        for (name, expansion) in Self::parse_aliases(&content) {
+            tasks.push(Task {
+                command: format!("rebar3 {}", name),
+                name,
+                description: None,
+                script: Some(expansion),
+                ..Default::default()
+            });
+        }
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Rebar3,
+            tasks,
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_rebar_config() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("rebar.config");
+        fs::write(
+            &path,
+            r#"
+{erl_opts, [debug_info]}.
+{deps, [cowboy]}.
+
+%% {alias, [{ignored, [eunit]}]}.
+{alias, [
+    {check, [xref, dialyzer,
+             {proper, "--regressions"}]},  % property tests too
+    {test, [eunit, ct]}
+]}.
+"#,
+        )
+        .unwrap();
+
+        let parser = Rebar3Parser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::Rebar3);
+        let commands: Vec<_> = runner.tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            vec![
+                "rebar3 compile",
+                "rebar3 eunit",
+                "rebar3 ct",
+                "rebar3 shell",
+                "rebar3 release",
+                "rebar3 check",
+                "rebar3 test",
+            ]
+        );
+        let check = runner.tasks.iter().find(|t| t.name == "check").unwrap();
+        assert_eq!(
+            check.script.as_deref(),
+            Some(r#"[xref, dialyzer, {proper, "--regressions"}]"#)
+        );
+    }
+
+    #[test]
+    fn test_no_aliases() {
+        assert!(Rebar3Parser::parse_aliases("{deps, []}.\n").is_empty());
+    }
+}
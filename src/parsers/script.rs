@@ -0,0 +1,57 @@
+//! Generic parser for ad-hoc script files (run.sh, dev.sh, ...)
+//!
+//! Unlike the other parsers this one doesn't read the file at all: any file
+//! the scanner hands it is treated as a single runnable task. The scanner only
+//! dispatches here when generic script detection is enabled.
+
+use std::path::Path;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::Parser;
+
+pub struct ScriptParser;
+
+impl Parser for ScriptParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let name = match path.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => return Ok(None),
+        };
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Script,
+            tasks: vec![Task {
+                command: format!("./{}", name),
+                name,
+                description: None,
+                script: None,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_script() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("dev.sh");
+        fs::write(&path, "#!/bin/sh\necho dev\n").unwrap();
+
+        let parser = ScriptParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::Script);
+        assert_eq!(runner.tasks.len(), 1);
+        assert_eq!(runner.tasks[0].name, "dev.sh");
+        assert_eq!(runner.tasks[0].command, "./dev.sh");
+    }
+}
@@ -0,0 +1,145 @@
+//! Simple parser for Snakemake workflows (`Snakefile`, `*.smk`)
+//!
+//! Line-based: picks up top-level `rule <name>:` and `checkpoint <name>:`
+//! declarations. Snakemake runs the first rule when no target is given
+//! (conventionally `all`), so that rule is flagged as the default target.
+
+use std::path::Path;
+
+use crate::{RunnerType, ScanError, Task, TaskRunner};
+
+use super::{read_config, Parser};
+
+pub struct SnakemakeParser;
+
+impl SnakemakeParser {
+    /// Parse rule names from workflow content, in declaration order
+    fn parse_rules(content: &str) -> Vec<String> {
+        let mut rules = Vec::new();
+        for line in content.lines() {
+            // Rules are only declared at the top level
+            if line.starts_with(|c: char| c.is_whitespace()) {
+                continue;
+            }
+            let rest = match line
+                .strip_prefix("rule ")
+                .or_else(|| line.strip_prefix("checkpoint "))
+            {
+                Some(rest) => rest.trim_start(),
+                None => continue,
+            };
+            let Some((name, _)) = rest.split_once(':') else {
+                continue;
+            };
+            let name = name.trim();
+            let valid = !name.is_empty()
+                && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+                && !rules.iter().any(|r| r == name);
+            if valid {
+                rules.push(name.to_string());
+            }
+        }
+        rules
+    }
+
+    /// Base command for a workflow file: plain `snakemake` picks up
+    /// `Snakefile` on its own, other files need `-s`. Snakemake refuses to
+    /// run without `--cores`, so a single core is asked for explicitly.
+    fn base_command(path: &Path) -> String {
+        match path.file_name().and_then(|n| n.to_str()) {
+            Some("Snakefile") | None => "snakemake --cores 1".to_string(),
+            Some(name) => format!("snakemake -s {} --cores 1", name),
+        }
+    }
+}
+
+impl Parser for SnakemakeParser {
+    fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
+        let content = read_config(path)?;
+        let rules = Self::parse_rules(&content);
+
+        if rules.is_empty() {
+            return Ok(None);
+        }
+
+        let base = Self::base_command(path);
+        let tasks = rules
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| Task {
+                command: format!("{} {}", base, name),
+                name,
+                description: (i == 0).then(|| "Default target".to_string()),
+                script: None,
+                ..Default::default()
+            })
+            .collect();
+
+        Ok(Some(TaskRunner {
+            config_path: path.to_path_buf(),
+            runner_type: RunnerType::Snakemake,
+            tasks,
+            ..Default::default()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_snakefile() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Snakefile");
+        fs::write(
+            &path,
+            r#"
+configfile: "config.yaml"
+
+rule all:
+    input: "results/summary.txt"
+
+rule align:
+    input: "reads/{sample}.fq"
+    output: "aligned/{sample}.bam"
+    shell: "bwa mem ref.fa {input} > {output}"
+
+checkpoint split_reads:
+    output: directory("split")
+    shell: "split.sh"
+"#,
+        )
+        .unwrap();
+
+        let parser = SnakemakeParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(runner.runner_type, RunnerType::Snakemake);
+        let names: Vec<_> = runner.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["all", "align", "split_reads"]);
+        assert_eq!(runner.tasks[1].command, "snakemake --cores 1 align");
+        assert_eq!(
+            runner.tasks[0].description.as_deref(),
+            Some("Default target")
+        );
+        assert_eq!(runner.tasks[1].description, None);
+    }
+
+    #[test]
+    fn test_parse_smk_uses_snakefile_flag() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("qc.smk");
+        fs::write(&path, "rule fastqc:\n    shell: \"fastqc\"\n").unwrap();
+
+        let parser = SnakemakeParser;
+        let runner = parser.parse(&path).unwrap().unwrap();
+
+        assert_eq!(
+            runner.tasks[0].command,
+            "snakemake -s qc.smk --cores 1 fastqc"
+        );
+    }
+}
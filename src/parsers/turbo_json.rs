@@ -1,34 +1,60 @@
-//! Parser for turbo.json (Turborepo)
+//! Parser for turbo.json (Turborepo), and for Turborepo config inlined
+//! under the `turbo` key of package.json
 
-use std::collections::HashMap;
-use std::fs;
 use std::path::Path;
 
+use indexmap::IndexMap;
 use serde::Deserialize;
 
-use crate::{RunnerType, ScanError, Task, TaskRunner};
+use crate::{RunnerType, ScanError, Task, TaskMetadata, TaskRunner};
 
-use super::Parser;
+use super::{read_config, Parser};
 
 #[derive(Deserialize)]
 struct TurboJson {
     // v2 format
-    tasks: Option<HashMap<String, serde_json::Value>>,
+    tasks: Option<IndexMap<String, serde_json::Value>>,
     // v1 format (legacy)
-    pipeline: Option<HashMap<String, serde_json::Value>>,
+    pipeline: Option<IndexMap<String, serde_json::Value>>,
+}
+
+#[derive(Deserialize)]
+struct PackageJson {
+    turbo: Option<TurboJson>,
 }
 
 pub struct TurboJsonParser;
 
+impl TurboJsonParser {
+    /// The task's `dependsOn` list (`^build` means `build` in the workspace's
+    /// dependencies first)
+    fn depends_on(config: &serde_json::Value) -> Vec<String> {
+        config
+            .get("dependsOn")
+            .and_then(|deps| deps.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|dep| dep.as_str().map(str::to_string))
+            .collect()
+    }
+}
+
 impl Parser for TurboJsonParser {
     fn parse(&self, path: &Path) -> Result<Option<TaskRunner>, ScanError> {
-        let content = fs::read_to_string(path)?;
-
-        let turbo: TurboJson =
+        let content = read_config(path)?;
+
+        let turbo: TurboJson = if path.file_name().is_some_and(|name| name == "package.json") {
+            // Malformed package.json is reported by the package.json parser
+            match serde_json::from_str::<PackageJson>(&content) {
+                Ok(PackageJson { turbo: Some(turbo) }) => turbo,
+                _ => return Ok(None),
+            }
+        } else {
             serde_json::from_str(&content).map_err(|e| ScanError::ParseError {
                 path: path.to_path_buf(),
                 message: e.to_string(),
-            })?;
+            })?
+        };
 
         // Prefer v2 tasks, fall back to v1 pipeline
         let task_map = turbo.tasks.or(turbo.pipeline);
@@ -39,13 +65,17 @@ impl Parser for TurboJsonParser {
         };
 
         let tasks: Vec<Task> = task_map
-            .keys()
-            .filter(|name| !name.starts_with('/')) // Skip workspace-specific tasks
-            .map(|name| Task {
+            .iter()
+            .filter(|(name, _)| !name.starts_with('/')) // Skip workspace-specific tasks
+            .map(|(name, config)| Task {
                 name: name.clone(),
                 command: format!("turbo run {}", name),
                 description: Some("Turborepo task (runs across workspaces)".to_string()),
                 script: None,
+                metadata: TaskMetadata {
+                    depends_on: Self::depends_on(config),
+                    ..Default::default()
+                },
             })
             .collect();
 
@@ -57,6 +87,7 @@ impl Parser for TurboJsonParser {
             config_path: path.to_path_buf(),
             runner_type: RunnerType::Turbo,
             tasks,
+            ..Default::default()
         }))
     }
 }
@@ -88,10 +119,15 @@ mod tests {
         let runner = parser.parse(&path).unwrap().unwrap();
 
         assert_eq!(runner.runner_type, RunnerType::Turbo);
-        assert_eq!(runner.tasks.len(), 3);
+        // Tasks keep the order they're declared in
+        let names: Vec<_> = runner.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "test", "lint"]);
 
         let build_task = runner.tasks.iter().find(|t| t.name == "build").unwrap();
         assert_eq!(build_task.command, "turbo run build");
+        assert_eq!(build_task.metadata.depends_on, ["^build"]);
+        assert_eq!(runner.tasks[1].metadata.depends_on, ["build"]);
+        assert!(runner.tasks[2].metadata.depends_on.is_empty());
     }
 
     #[test]
@@ -114,4 +150,34 @@ mod tests {
 
         assert_eq!(runner.tasks.len(), 2);
     }
+
+    #[test]
+    fn test_parse_package_json_turbo_field() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("package.json");
+        fs::write(
+            &path,
+            r#"{
+                "scripts": { "build": "turbo run build" },
+                "turbo": {
+                    "tasks": {
+                        "build": { "dependsOn": ["^build"] },
+                        "lint": {}
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let runner = TurboJsonParser.parse(&path).unwrap().unwrap();
+        assert_eq!(runner.runner_type, RunnerType::Turbo);
+        let commands: Vec<_> = runner.tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(commands, vec!["turbo run build", "turbo run lint"]);
+
+        // Plain package.json: no turbo runner, and no error for the scripts
+        fs::write(&path, r#"{"scripts": {"build": "tsc"}}"#).unwrap();
+        assert!(TurboJsonParser.parse(&path).unwrap().is_none());
+        fs::write(&path, "{ not json").unwrap();
+        assert!(TurboJsonParser.parse(&path).unwrap().is_none());
+    }
 }
@@ -0,0 +1,222 @@
+//! Output pipeline for the non-interactive modes
+//!
+//! Scan results always go through the same stages in the same order, whatever
+//! combination of flags is given: filter by runner → filter by query → sort.
+//! Formatting is up to each output mode.
+
+use std::path::Path;
+
+use clap::ValueEnum;
+use nucleo::{Config, Matcher, Utf32Str};
+use task_runner_detector::{RunnerType, TaskRunner};
+
+use crate::matching::{self, MatchOptions};
+use task_runner_detector::folder_display;
+
+/// Order of the runners (and their tasks) in the output
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SortOrder {
+    /// By folder, then runner; tasks in each runner by name
+    Name,
+    /// By folder, then runner; tasks keep the order they're declared in
+    Folder,
+    /// By runner, then folder
+    Runner,
+}
+
+/// Filters and ordering applied to scan results
+#[derive(Clone, Copy, Default)]
+pub struct Pipeline<'a> {
+    /// Only keep these runner types (empty = all)
+    pub runner_types: &'a [RunnerType],
+    /// Only keep tasks matching this fuzzy query
+    pub query: Option<&'a str>,
+    /// Case/normalization settings for the query
+    pub match_options: MatchOptions,
+    /// Output order (None = scan order)
+    pub sort: Option<SortOrder>,
+}
+
+impl Pipeline<'_> {
+    /// The filter stages for one runner at a time, for output that streams.
+    /// Runners left without tasks are dropped.
+    pub fn filter<'r>(&self, root: &'r Path) -> impl FnMut(TaskRunner) -> Option<TaskRunner> + 'r {
+        let runner_types = self.runner_types.to_vec();
+        let pattern = self.query.map(|q| self.match_options.pattern(q));
+        let mut matcher = Matcher::new(Config::DEFAULT);
+
+        move |mut runner| {
+            if !runner_types.is_empty() && !runner_types.contains(&runner.runner_type) {
+                return None;
+            }
+            if let Some(pattern) = &pattern {
+                let folder = folder_display(&runner.config_path, root);
+                let runner_type = runner.runner_type;
+                runner.tasks.retain(|task| {
+                    let search_text = matching::search_text(&folder, &task.command, runner_type);
+                    let mut buf = Vec::new();
+                    let haystack = Utf32Str::new(&search_text, &mut buf);
+                    pattern.score(haystack, &mut matcher).is_some()
+                });
+            }
+            (!runner.tasks.is_empty()).then_some(runner)
+        }
+    }
+
+    /// Run every stage over complete scan results
+    pub fn run(&self, runners: Vec<TaskRunner>, root: &Path) -> Vec<TaskRunner> {
+        let mut runners: Vec<_> = runners.into_iter().filter_map(self.filter(root)).collect();
+
+        match self.sort {
+            None => {}
+            Some(SortOrder::Name | SortOrder::Folder) => {
+                runners.sort_by_cached_key(|r| {
+                    (
+                        folder_display(&r.config_path, root),
+                        r.runner_type.display_name(),
+                    )
+                });
+            }
+            Some(SortOrder::Runner) => {
+                runners.sort_by_cached_key(|r| {
+                    (
+                        r.runner_type.display_name(),
+                        folder_display(&r.config_path, root),
+                    )
+                });
+            }
+        }
+        if self.sort == Some(SortOrder::Name) {
+            for runner in &mut runners {
+                runner.tasks.sort_by(|a, b| a.name.cmp(&b.name));
+            }
+        }
+        runners
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use task_runner_detector::Task;
+
+    fn runner(config_path: &str, runner_type: RunnerType, names: &[&str]) -> TaskRunner {
+        TaskRunner {
+            config_path: config_path.into(),
+            runner_type,
+            tasks: names
+                .iter()
+                .map(|name| Task {
+                    name: name.to_string(),
+                    command: format!("{} run {}", runner_type, name),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn runners() -> Vec<TaskRunner> {
+        vec![
+            runner(
+                "/repo/web/package.json",
+                RunnerType::Npm,
+                &["test", "build"],
+            ),
+            runner("/repo/web/Makefile", RunnerType::Make, &["build", "clean"]),
+            runner(
+                "/repo/api/package.json",
+                RunnerType::Npm,
+                &["build:prod", "lint"],
+            ),
+        ]
+    }
+
+    fn summary(runners: &[TaskRunner]) -> Vec<String> {
+        runners
+            .iter()
+            .flat_map(|r| r.tasks.iter().map(|t| t.command.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_no_stages_keep_scan_order() {
+        let root = Path::new("/repo");
+        let output = Pipeline::default().run(runners(), root);
+        assert_eq!(summary(&output), summary(&runners()));
+    }
+
+    #[test]
+    fn test_runner_query_and_sort_combine() {
+        let root = Path::new("/repo");
+        let pipeline = Pipeline {
+            runner_types: &[RunnerType::Npm],
+            query: Some("build"),
+            sort: Some(SortOrder::Name),
+            ..Pipeline::default()
+        };
+        assert_eq!(
+            summary(&pipeline.run(runners(), root)),
+            vec!["npm run build:prod", "npm run build"]
+        );
+
+        // Sorting doesn't change what's kept
+        let by_runner = Pipeline {
+            sort: Some(SortOrder::Runner),
+            ..pipeline
+        };
+        assert_eq!(
+            summary(&by_runner.run(runners(), root)),
+            vec!["npm run build:prod", "npm run build"]
+        );
+    }
+
+    #[test]
+    fn test_sort_orders() {
+        let root = Path::new("/repo");
+        let sorted = |sort| {
+            Pipeline {
+                sort: Some(sort),
+                ..Pipeline::default()
+            }
+            .run(runners(), root)
+        };
+
+        assert_eq!(
+            summary(&sorted(SortOrder::Name)),
+            vec![
+                "npm run build:prod",
+                "npm run lint",
+                "make run build",
+                "make run clean",
+                "npm run build",
+                "npm run test",
+            ]
+        );
+        assert_eq!(
+            summary(&sorted(SortOrder::Folder))[4..],
+            ["npm run test", "npm run build"]
+        );
+        let folders: Vec<_> = sorted(SortOrder::Runner)
+            .iter()
+            .map(|r| folder_display(&r.config_path, root))
+            .collect();
+        assert_eq!(folders, vec!["web", "api", "web"]);
+    }
+
+    #[test]
+    fn test_streaming_filter_keeps_description() {
+        let root = Path::new("/repo");
+        let mut web = runners().remove(0);
+        web.description = Some("Web app".to_string());
+        let pipeline = Pipeline {
+            query: Some("test"),
+            ..Pipeline::default()
+        };
+
+        let filtered = pipeline.filter(root)(web).unwrap();
+        assert_eq!(filtered.description.as_deref(), Some("Web app"));
+        assert_eq!(filtered.tasks.len(), 1);
+        assert!(pipeline.filter(root)(runners().remove(1)).is_none());
+    }
+}
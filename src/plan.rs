@@ -0,0 +1,110 @@
+//! Dependency-ordered run plans (`--plan`)
+//!
+//! Resolves a task's declared dependencies (`TaskMetadata::depends_on`)
+//! against the other tasks of the same runner and orders them so every
+//! task comes after the tasks it depends on. Nothing is run.
+
+use task_runner_detector::{Task, TaskRunner};
+
+/// Tasks of `runner` to run for `task`, dependencies first and `task` last.
+/// On a cycle, returns the task names along it (`a`, `b`, `a`).
+pub fn run_order<'a>(runner: &'a TaskRunner, task: &'a Task) -> Result<Vec<&'a Task>, Vec<String>> {
+    let mut order = Vec::new();
+    let mut path = Vec::new();
+    visit(runner, task, &mut path, &mut order)?;
+    Ok(order)
+}
+
+/// Depth-first visit: `path` holds the tasks being resolved, so meeting one
+/// of them again is a cycle
+fn visit<'a>(
+    runner: &'a TaskRunner,
+    task: &'a Task,
+    path: &mut Vec<&'a str>,
+    order: &mut Vec<&'a Task>,
+) -> Result<(), Vec<String>> {
+    if order.iter().any(|done| done.name == task.name) {
+        return Ok(());
+    }
+    if let Some(start) = path.iter().position(|name| *name == task.name) {
+        let mut cycle: Vec<String> = path[start..].iter().map(|name| name.to_string()).collect();
+        cycle.push(task.name.clone());
+        return Err(cycle);
+    }
+
+    path.push(&task.name);
+    for dep in dependencies(runner, task) {
+        visit(runner, dep, path, order)?;
+    }
+    path.pop();
+    order.push(task);
+    Ok(())
+}
+
+/// The tasks `task` depends on within its runner. Turborepo's `^build`
+/// (`build` in upstream workspaces) resolves to the same-named task unless
+/// that's `task` itself; references to other packages (`web#build`) and
+/// unknown names are left out.
+fn dependencies<'a>(runner: &'a TaskRunner, task: &'a Task) -> impl Iterator<Item = &'a Task> {
+    task.metadata.depends_on.iter().filter_map(move |dep| {
+        let name = dep.strip_prefix('^').unwrap_or(dep);
+        if name == task.name && dep.starts_with('^') {
+            return None;
+        }
+        runner.tasks.iter().find(|t| t.name == name)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use task_runner_detector::{RunnerType, TaskMetadata};
+
+    fn runner(tasks: &[(&str, &[&str])]) -> TaskRunner {
+        TaskRunner {
+            config_path: PathBuf::from("turbo.json"),
+            runner_type: RunnerType::Turbo,
+            tasks: tasks
+                .iter()
+                .map(|(name, deps)| Task {
+                    name: name.to_string(),
+                    command: format!("turbo run {}", name),
+                    metadata: TaskMetadata {
+                        depends_on: deps.iter().map(|d| d.to_string()).collect(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn names(runner: &TaskRunner, name: &str) -> Result<Vec<String>, Vec<String>> {
+        let task = runner.tasks.iter().find(|t| t.name == name).unwrap();
+        run_order(runner, task).map(|order| order.iter().map(|t| t.name.clone()).collect())
+    }
+
+    #[test]
+    fn test_run_order() {
+        let runner = runner(&[
+            ("deploy", &["build", "test"]),
+            ("test", &["build", "lint"]),
+            ("build", &["^build", "codegen", "web#build"]),
+            ("codegen", &[]),
+            ("lint", &[]),
+        ]);
+        assert_eq!(
+            names(&runner, "deploy").unwrap(),
+            ["codegen", "build", "lint", "test", "deploy"]
+        );
+        assert_eq!(names(&runner, "lint").unwrap(), ["lint"]);
+    }
+
+    #[test]
+    fn test_run_order_reports_cycles() {
+        let runner = runner(&[("a", &["b"]), ("b", &["c"]), ("c", &["a"])]);
+        assert_eq!(names(&runner, "a").unwrap_err(), ["a", "b", "c", "a"]);
+    }
+}
@@ -0,0 +1,18 @@
+//! The types and functions most frontends need, in one import
+//!
+//! ```no_run
+//! use task_runner_detector::prelude::*;
+//!
+//! let root = std::path::Path::new(".");
+//! let runners = scan_with_options(root, ScanOptions::default()).unwrap();
+//! for (runner, task) in ordered_tasks(&runners, root) {
+//!     println!("{}: {}", folder_display(&runner.config_path, root), task.command);
+//! }
+//! ```
+
+pub use crate::{
+    folder_display, ordered_tasks, scan, scan_report, scan_streaming, scan_streaming_cancellable,
+    scan_streaming_items, scan_with_options, scan_with_progress, Ecosystem, RunnerType, ScanError,
+    ScanHandle, ScanItem, ScanOptions, ScanReport, ScanResult, ScanStats, Task, TaskMetadata,
+    TaskRunner,
+};
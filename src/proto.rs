@@ -0,0 +1,138 @@
+//! Run commands through proto (moonrepo's toolchain manager) when it pins the tool
+//!
+//! proto reads tool versions from `.prototools` files in the working directory
+//! and its ancestors (`node = "20.10.0"`, `pnpm = "8"`). When one of those files
+//! pins the program a task invokes, the command is rewritten to
+//! `proto run <tool> -- <args>` so the pinned version runs even without shims.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::{parsers, TaskRunner};
+
+const PROTOTOOLS: &str = ".prototools";
+
+/// Prefix the runner's commands with `proto run` for tools pinned in a
+/// `.prototools` above its config file
+pub(crate) fn apply_proto(runner: &mut TaskRunner) {
+    let Some(dir) = runner.config_path.parent() else {
+        return;
+    };
+
+    let tools = pinned_tools(dir);
+    if tools.is_empty() {
+        return;
+    }
+
+    for task in &mut runner.tasks {
+        if let Some(command) = proto_command(&task.command, &tools) {
+            task.command = command;
+        }
+    }
+}
+
+/// Tools pinned by every `.prototools` from `dir` upwards. Only top-level
+/// string entries are tools; tables like `[plugins]` and `[settings]` aren't.
+fn pinned_tools(dir: &Path) -> HashSet<String> {
+    let mut tools = HashSet::new();
+    for ancestor in parsers::ancestors(dir) {
+        let Ok(content) = fs::read_to_string(ancestor.join(PROTOTOOLS)) else {
+            continue;
+        };
+        let Ok(table) = content.parse::<toml::Table>() else {
+            continue;
+        };
+        tools.extend(
+            table
+                .into_iter()
+                .filter(|(_, value)| value.is_str())
+                .map(|(tool, _)| tool),
+        );
+    }
+    tools
+}
+
+/// `npm run build` → `proto run npm -- run build`, if the program is pinned
+fn proto_command(command: &str, tools: &HashSet<String>) -> Option<String> {
+    let command = command.trim_start();
+    let (program, args) = match command.split_once(char::is_whitespace) {
+        Some((program, args)) => (program, args.trim_start()),
+        None => (command, ""),
+    };
+    if !tools.contains(program) {
+        return None;
+    }
+
+    Some(if args.is_empty() {
+        format!("proto run {}", program)
+    } else {
+        format!("proto run {} -- {}", program, args)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RunnerType, Task};
+    use tempfile::TempDir;
+
+    fn task(command: &str) -> Task {
+        Task {
+            name: command.to_string(),
+            command: command.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_proto_command() {
+        let tools = HashSet::from(["pnpm".to_string(), "node".to_string()]);
+        assert_eq!(
+            proto_command("pnpm run build", &tools).as_deref(),
+            Some("proto run pnpm -- run build")
+        );
+        assert_eq!(
+            proto_command("node", &tools).as_deref(),
+            Some("proto run node")
+        );
+        assert_eq!(proto_command("make build", &tools), None);
+    }
+
+    #[test]
+    fn test_apply_proto_from_ancestor() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join(PROTOTOOLS),
+            "node = \"20.10.0\"\npnpm = \"8\"\n\n[settings]\nauto-install = true\n",
+        )
+        .unwrap();
+        let web = dir.path().join("apps/web");
+        fs::create_dir_all(&web).unwrap();
+
+        let mut runner = TaskRunner {
+            config_path: web.join("package.json"),
+            runner_type: RunnerType::Pnpm,
+            tasks: vec![task("pnpm run dev"), task("settings")],
+            ..Default::default()
+        };
+        apply_proto(&mut runner);
+
+        assert_eq!(runner.tasks[0].command, "proto run pnpm -- run dev");
+        // Tables aren't tools
+        assert_eq!(runner.tasks[1].command, "settings");
+    }
+
+    #[test]
+    fn test_no_prototools() {
+        let dir = TempDir::new().unwrap();
+        let mut runner = TaskRunner {
+            config_path: dir.path().join("Makefile"),
+            runner_type: RunnerType::Make,
+            tasks: vec![task("make build")],
+            ..Default::default()
+        };
+        apply_proto(&mut runner);
+        assert_eq!(runner.tasks[0].command, "make build");
+    }
+}
@@ -0,0 +1,214 @@
+//! Task descriptions from markdown tables in a sibling TASKS.md / README.md
+//!
+//! Only well-formed pipe tables are considered: a header row, a `---`
+//! separator row, then body rows. The task column is the one headed
+//! "task", "name", "command", "script" or "target" (else the first column),
+//! the description column is headed "description" (else the second column).
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::TaskRunner;
+
+/// Files checked next to the config file, in priority order
+const DOC_FILES: &[&str] = &["TASKS.md", "README.md"];
+
+/// Fill in missing task descriptions from a sibling markdown task table
+pub(crate) fn apply_readme_descriptions(runner: &mut TaskRunner) {
+    if runner.tasks.iter().all(|t| t.description.is_some()) {
+        return;
+    }
+    let Some(dir) = runner.config_path.parent() else {
+        return;
+    };
+
+    let descriptions = read_descriptions(dir);
+    if descriptions.is_empty() {
+        return;
+    }
+
+    for task in &mut runner.tasks {
+        if task.description.is_some() {
+            continue;
+        }
+        let found = descriptions
+            .get(&task.name)
+            .or_else(|| descriptions.get(&task.command));
+        if let Some(description) = found {
+            task.description = Some(description.clone());
+        }
+    }
+}
+
+/// Collect name→description pairs from all doc files in a directory.
+/// Earlier files win on conflicts.
+fn read_descriptions(dir: &Path) -> HashMap<String, String> {
+    let mut descriptions = HashMap::new();
+    for file in DOC_FILES {
+        let Ok(content) = fs::read_to_string(dir.join(file)) else {
+            continue;
+        };
+        for (name, description) in parse_tables(&content) {
+            descriptions.entry(name).or_insert(description);
+        }
+    }
+    descriptions
+}
+
+/// Split a table row into trimmed cells, or None if it isn't a pipe row
+fn split_row(line: &str) -> Option<Vec<String>> {
+    let line = line.trim();
+    if !line.starts_with('|') {
+        return None;
+    }
+    let inner = line.trim_start_matches('|').trim_end_matches('|');
+    Some(
+        inner
+            .split('|')
+            .map(|cell| cell.trim().trim_matches('`').trim().to_string())
+            .collect(),
+    )
+}
+
+/// Check if a row is a header separator like `|---|:---:|`
+fn is_separator(cells: &[String]) -> bool {
+    !cells.is_empty()
+        && cells
+            .iter()
+            .all(|cell| cell.contains('-') && cell.chars().all(|c| matches!(c, '-' | ':' | ' ')))
+}
+
+/// Extract (task, description) pairs from every pipe table in a document
+fn parse_tables(content: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let lines: Vec<&str> = content.lines().collect();
+    let mut i = 0;
+
+    while i + 1 < lines.len() {
+        let (Some(header), Some(separator)) = (split_row(lines[i]), split_row(lines[i + 1])) else {
+            i += 1;
+            continue;
+        };
+        if header.len() < 2 || !is_separator(&separator) || separator.len() != header.len() {
+            i += 1;
+            continue;
+        }
+
+        let lower: Vec<String> = header.iter().map(|h| h.to_lowercase()).collect();
+        let name_col = lower
+            .iter()
+            .position(|h| {
+                matches!(
+                    h.as_str(),
+                    "task" | "name" | "command" | "script" | "target"
+                )
+            })
+            .unwrap_or(0);
+        let desc_col = lower
+            .iter()
+            .position(|h| h == "description")
+            .unwrap_or(if name_col == 0 { 1 } else { 0 });
+
+        i += 2;
+        while i < lines.len() {
+            let Some(row) = split_row(lines[i]) else {
+                break;
+            };
+            if row.len() == header.len() {
+                let name = &row[name_col];
+                let description = &row[desc_col];
+                if !name.is_empty() && !description.is_empty() {
+                    pairs.push((name.clone(), description.clone()));
+                }
+            }
+            i += 1;
+        }
+    }
+
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RunnerType, Task};
+    use tempfile::TempDir;
+
+    fn task(name: &str, command: &str, description: Option<&str>) -> Task {
+        Task {
+            name: name.to_string(),
+            command: command.to_string(),
+            description: description.map(|d| d.to_string()),
+            script: None,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_tables() {
+        let content = r#"
+# Project
+
+| Task | Description |
+|------|:------------|
+| `build` | Build everything |
+| test | Run the test suite |
+
+Not a table | just text
+
+| Name | Owner |
+| broken | no separator |
+"#;
+        let pairs = parse_tables(content);
+        assert_eq!(
+            pairs,
+            vec![
+                ("build".to_string(), "Build everything".to_string()),
+                ("test".to_string(), "Run the test suite".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_readme_descriptions() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("README.md"),
+            "| Description | Command |\n|---|---|\n| Ship it | npm run deploy |\n| Build | build |\n",
+        )
+        .unwrap();
+
+        let mut runner = TaskRunner {
+            config_path: dir.path().join("package.json"),
+            runner_type: RunnerType::Npm,
+            tasks: vec![
+                task("build", "npm run build", Some("tsc")),
+                task("deploy", "npm run deploy", None),
+                task("lint", "npm run lint", None),
+            ],
+            ..Default::default()
+        };
+        apply_readme_descriptions(&mut runner);
+
+        // Existing descriptions are kept
+        assert_eq!(runner.tasks[0].description.as_deref(), Some("tsc"));
+        // Matched by full command
+        assert_eq!(runner.tasks[1].description.as_deref(), Some("Ship it"));
+        // No entry in the table
+        assert_eq!(runner.tasks[2].description, None);
+    }
+
+    #[test]
+    fn test_missing_readme_is_ignored() {
+        let dir = TempDir::new().unwrap();
+        let mut runner = TaskRunner {
+            config_path: dir.path().join("Makefile"),
+            runner_type: RunnerType::Make,
+            tasks: vec![task("build", "make build", None)],
+            ..Default::default()
+        };
+        apply_readme_descriptions(&mut runner);
+        assert_eq!(runner.tasks[0].description, None);
+    }
+}
@@ -12,20 +12,28 @@ use std::path::{Path, PathBuf};
 pub struct TaskKey(String);
 
 impl TaskKey {
-    pub fn new(config_path: &Path, runner_type: RunnerType, name: &str) -> Self {
+    pub fn new(config_path: &Path, runner_type: RunnerType, name: &str, rank: usize) -> Self {
         let folder = config_path
             .parent()
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_default();
-        // Sort by folder, then runner display name, then task name
+        // Sort by folder, then runner priority rank and display name, then task name
         // Use \x00 as separator so parent tasks sort before child folders
         Self(format!(
-            "{}\x00{}\x00{}",
+            "{}\x00{:04}{}\x00{}",
             folder,
+            rank,
             runner_type.display_name(),
             name
         ))
     }
+
+    /// The folder + runner part of the key (everything but the task name)
+    fn group(&self) -> &str {
+        self.0
+            .rsplit_once('\x00')
+            .map_or(&self.0, |(group, _)| group)
+    }
 }
 
 impl Borrow<str> for TaskKey {
@@ -49,20 +57,7 @@ pub struct Task {
 impl Task {
     /// Get the folder path relative to root for display
     pub fn folder_display(&self, root: &Path) -> String {
-        let relative = self
-            .config_path
-            .strip_prefix(root)
-            .unwrap_or(&self.config_path);
-        let path_str = relative.to_string_lossy();
-
-        if !path_str.contains('/') && !path_str.contains('\\') {
-            ".".to_string()
-        } else {
-            relative
-                .parent()
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_else(|| ".".to_string())
-        }
+        task_runner_detector::folder_display(&self.config_path, root)
     }
 }
 
@@ -76,6 +71,8 @@ pub struct Registry {
     by_folder: BTreeMap<PathBuf, Vec<TaskId>>,
     /// Folder discovery order (preserved for deterministic rendering)
     folder_order: Vec<PathBuf>,
+    /// Runners that sort first within a folder, in order
+    runner_priority: Vec<RunnerType>,
 }
 
 impl Default for Registry {
@@ -91,12 +88,31 @@ impl Registry {
             tasks: Vec::new(),
             by_folder: BTreeMap::new(),
             folder_order: Vec::new(),
+            runner_priority: Vec::new(),
         }
     }
 
+    /// Create a registry that lists the given runners first within each
+    /// folder. Unlisted runners follow alphabetically.
+    pub fn with_runner_priority(runner_priority: Vec<RunnerType>) -> Self {
+        Self {
+            runner_priority,
+            ..Self::new()
+        }
+    }
+
+    /// Sort rank of a runner: its position in the priority list, or after all of them
+    fn runner_rank(&self, runner_type: RunnerType) -> usize {
+        self.runner_priority
+            .iter()
+            .position(|&listed| listed == runner_type)
+            .unwrap_or(self.runner_priority.len())
+    }
+
     /// Insert a task, returning its ID. Returns existing ID if duplicate.
     pub fn insert(&mut self, task: Task) -> TaskId {
-        let key = TaskKey::new(&task.config_path, task.runner_type, &task.name);
+        let rank = self.runner_rank(task.runner_type);
+        let key = TaskKey::new(&task.config_path, task.runner_type, &task.name, rank);
 
         // Check for existing task with same key
         if let Some(&existing) = self.index.get(&key) {
@@ -130,6 +146,20 @@ impl Registry {
     pub fn sorted_ids(&self) -> Vec<TaskId> {
         self.index.values().copied().collect()
     }
+
+    /// Get all task IDs sorted by folder/runner, keeping the order each
+    /// runner's tasks were declared in (their insertion order)
+    pub fn declaration_ordered_ids(&self) -> Vec<TaskId> {
+        let mut entries: Vec<(&str, TaskId)> = self
+            .index
+            .iter()
+            .map(|(key, &id)| (key.group(), id))
+            .collect();
+        entries.sort_by(|(group_a, id_a), (group_b, id_b)| {
+            group_a.cmp(group_b).then(id_a.0.cmp(&id_b.0))
+        });
+        entries.into_iter().map(|(_, id)| id).collect()
+    }
 }
 
 #[cfg(test)]
@@ -151,6 +181,26 @@ mod tests {
         assert_eq!(registry.len(), 1);
     }
 
+    #[test]
+    fn test_folder_display() {
+        let task = |path: &str| Task {
+            name: "build".to_string(),
+            runner_type: RunnerType::Make,
+            config_path: PathBuf::from(path),
+        };
+        let root = Path::new("/project");
+
+        assert_eq!(task("/project/Makefile").folder_display(root), ".");
+        assert_eq!(
+            task("/project/apps/web/Makefile").folder_display(root),
+            "apps/web"
+        );
+        assert_eq!(
+            task("/home/me/.config/task-runner/tasks.toml").folder_display(root),
+            "global"
+        );
+    }
+
     #[test]
     fn test_registry_dedup() {
         let mut registry = Registry::new();
@@ -197,4 +247,51 @@ mod tests {
         assert_eq!(sorted[0], TaskId(1));
         assert_eq!(sorted[1], TaskId(0));
     }
+
+    #[test]
+    fn test_declaration_ordered_ids() {
+        let mut registry = Registry::new();
+        let task = |name: &str, runner_type, config_path: &str| Task {
+            name: name.to_string(),
+            runner_type,
+            config_path: PathBuf::from(config_path),
+        };
+
+        let deploy = registry.insert(task("deploy", RunnerType::Make, "/p/Makefile"));
+        let build = registry.insert(task("build", RunnerType::Make, "/p/Makefile"));
+        let lint = registry.insert(task("lint", RunnerType::Npm, "/p/package.json"));
+        let sub = registry.insert(task("all", RunnerType::Make, "/p/sub/Makefile"));
+
+        assert_eq!(registry.sorted_ids(), vec![build, deploy, lint, sub]);
+        assert_eq!(
+            registry.declaration_ordered_ids(),
+            vec![deploy, build, lint, sub]
+        );
+    }
+
+    #[test]
+    fn test_runner_priority() {
+        let task = |name: &str, runner_type, config_path: &str| Task {
+            name: name.to_string(),
+            runner_type,
+            config_path: PathBuf::from(config_path),
+        };
+        let insert_all = |registry: &mut Registry| {
+            [
+                registry.insert(task("build", RunnerType::Cargo, "/p/Cargo.toml")),
+                registry.insert(task("build", RunnerType::Make, "/p/Makefile")),
+                registry.insert(task("build", RunnerType::Npm, "/p/package.json")),
+            ]
+        };
+
+        // Default stays alphabetical by runner
+        let mut registry = Registry::new();
+        let [cargo, make, npm] = insert_all(&mut registry);
+        assert_eq!(registry.sorted_ids(), vec![cargo, make, npm]);
+
+        // Listed runners come first, the rest keep alphabetical order
+        let mut registry = Registry::with_runner_priority(vec![RunnerType::Npm]);
+        let [cargo, make, npm] = insert_all(&mut registry);
+        assert_eq!(registry.sorted_ids(), vec![npm, cargo, make]);
+    }
 }
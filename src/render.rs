@@ -1,10 +1,13 @@
 //! ANSI rendering for the terminal UI
 
 use crate::backend::SharedTasks;
+use crate::matching::{self, MatchOptions};
 use crate::messages::{SearchResponse, TaskItem};
 use crate::ui::{Mode, UIState};
-use nucleo::pattern::{Atom, CaseMatching, Normalization, Pattern};
+use nucleo::pattern::Atom;
 use nucleo::{Config, Matcher, Utf32Str};
+use std::collections::HashMap;
+use std::path::Path;
 
 /// Compute match indices for a short text (like folder name) against pattern atoms.
 /// Tries each atom individually and collects all matching indices.
@@ -31,6 +34,67 @@ fn compute_short_text_matches(
     all_indices
 }
 
+/// Colors and glyphs used by the renderer
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// SGR code for secondary text (tree branches, hints, counts)
+    pub muted: &'static str,
+    /// SGR code for the prompt, header and selection accent
+    pub accent: &'static str,
+    /// Marker shown in front of the selected task and the search input
+    pub marker: &'static str,
+    /// Show emoji icons for folders and runners
+    pub emoji: bool,
+    /// Use the terminal's dim attribute for secondary text printed outside
+    /// the picker (run banners and summaries)
+    pub dim: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            muted: "90",
+            accent: "36",
+            marker: "❯",
+            emoji: true,
+            dim: true,
+        }
+    }
+}
+
+impl Theme {
+    /// High-contrast theme: no dim gray, bold accent and a plain `>` marker
+    pub fn accessible() -> Self {
+        Self {
+            muted: "37",
+            accent: "1;97",
+            marker: ">",
+            emoji: true,
+            dim: false,
+        }
+    }
+
+    /// Style secondary text printed outside the picker: dim, or plain white
+    /// in the high-contrast theme
+    pub fn muted<D>(&self, text: D) -> console::StyledObject<D> {
+        let styled = console::style(text);
+        if self.dim {
+            styled.dim()
+        } else {
+            styled.white()
+        }
+    }
+
+    /// Icon for a folder header ("" when emoji are disabled)
+    fn folder_icon(&self) -> &'static str {
+        if self.emoji {
+            "📁 "
+        } else {
+            ""
+        }
+    }
+}
+
 /// Display item for rendering
 pub enum DisplayItem<'a> {
     Folder {
@@ -40,9 +104,17 @@ pub enum DisplayItem<'a> {
         parent_is_last: Vec<bool>,
         /// Match indices for highlighting (relative to folder name)
         match_indices: Vec<u32>,
+        /// Shown only as context for a deeper folder (no tasks of its own here)
+        ancestor: bool,
+        /// Collapsed: its tasks are hidden and the row itself is selectable
+        collapsed: bool,
+        /// Absolute directory of the tasks listed under this row
+        dir: Option<&'a Path>,
     },
     Task {
         task: &'a TaskItem,
+        /// Index of the task in shared storage
+        id: u32,
         depth: usize,
         is_last: bool,
         parent_is_last: Vec<bool>,
@@ -51,12 +123,30 @@ pub enum DisplayItem<'a> {
     },
 }
 
-/// Build display items from matched indices and shared tasks
+impl DisplayItem<'_> {
+    /// Tasks and collapsed folders can be selected
+    fn is_selectable(&self) -> bool {
+        matches!(
+            self,
+            DisplayItem::Task { .. }
+                | DisplayItem::Folder {
+                    collapsed: true,
+                    ..
+                }
+        )
+    }
+}
+
+/// Build display items from matched indices and shared tasks.
+/// Indices in `collapsed` stand in for a collapsed folder and are rendered
+/// as that folder's row instead of as a task.
 pub fn build_display_items<'a>(
     tasks: &'a [TaskItem],
     matched_indices: &[u32],
+    collapsed: &'a HashMap<u32, String>,
     root_name: &'a str,
     query: &str,
+    match_options: MatchOptions,
 ) -> Vec<DisplayItem<'a>> {
     if matched_indices.is_empty() {
         return vec![];
@@ -64,11 +154,7 @@ pub fn build_display_items<'a>(
 
     // Create pattern and matcher for highlighting only when there's a query
     let pattern = if !query.is_empty() {
-        Some(Pattern::parse(
-            query,
-            CaseMatching::Ignore,
-            Normalization::Smart,
-        ))
+        Some(match_options.pattern(query))
     } else {
         None
     };
@@ -79,9 +165,14 @@ pub fn build_display_items<'a>(
     let mut current_folder: Option<&str> = None;
     let mut folder_stack: Vec<(&str, bool)> = vec![]; // (folder_segment, is_last)
 
-    // Group indices by folder to determine last items
+    // Group indices by folder to determine last items. A collapsed folder
+    // is a group of its own without task rows.
     let mut folder_groups: Vec<(&str, Vec<u32>)> = Vec::new();
     for &idx in matched_indices {
+        if let Some(folder) = collapsed.get(&idx) {
+            folder_groups.push((folder.as_str(), vec![]));
+            continue;
+        }
         let task = &tasks[idx as usize];
         let folder = task.folder.as_str();
         if folder_groups.last().map(|(f, _)| *f) != Some(folder) {
@@ -103,6 +194,9 @@ pub fn build_display_items<'a>(
         is_last: true,
         parent_is_last: vec![],
         match_indices: root_match_indices,
+        ancestor: false,
+        collapsed: false,
+        dir: None,
     });
 
     for (group_idx, (folder, task_indices)) in folder_groups.iter().enumerate() {
@@ -174,6 +268,14 @@ pub fn build_display_items<'a>(
                     is_last: is_last_at_depth,
                     parent_is_last,
                     match_indices: folder_match_indices,
+                    ancestor: depth < segments.len(),
+                    collapsed: depth == segments.len() && task_indices.is_empty(),
+                    dir: match task_indices.first() {
+                        Some(&idx) if depth == segments.len() => {
+                            tasks[idx as usize].config_path.parent()
+                        }
+                        _ => None,
+                    },
                 });
             }
 
@@ -197,24 +299,22 @@ pub fn build_display_items<'a>(
             // Compute match indices for this task's command
             let match_indices = if let Some(ref pattern) = pattern {
                 indices_buf.clear();
-                // Match against same search text as backend: "{folder} {command}"
-                let search_text = format!("{} {}", task.folder, task.command);
+                // Match against same search text as backend
+                let search_text =
+                    matching::search_text(&task.folder, &task.command, task.runner_type);
                 let mut buf = Vec::new();
                 let haystack = Utf32Str::new(&search_text, &mut buf);
                 pattern.indices(haystack, &mut matcher, &mut indices_buf);
-
-                // Convert indices: subtract folder prefix length to get command-relative indices
-                let prefix_len = (task.folder.len() + 1) as u32; // +1 for space
-                indices_buf
-                    .iter()
-                    .filter_map(|&i| i.checked_sub(prefix_len))
-                    .collect()
+                indices_buf.sort_unstable();
+                indices_buf.dedup();
+                matching::command_indices(&task.folder, &task.command, &indices_buf)
             } else {
                 vec![]
             };
 
             items.push(DisplayItem::Task {
                 task,
+                id: idx,
                 depth: task_depth,
                 is_last: is_last_task,
                 parent_is_last,
@@ -240,9 +340,25 @@ fn tree_prefix(depth: usize, is_last: bool, parent_is_last: &[bool]) -> String {
     prefix
 }
 
+/// Lines the preview pane takes below the task list, its separator included
+pub const PREVIEW_LINES: usize = 6;
+
+/// Rows left for the task list below the header and above the status line
+pub fn list_height(state: &UIState, terminal_height: usize) -> usize {
+    let height = terminal_height.saturating_sub(8);
+    if state.show_preview {
+        height.saturating_sub(PREVIEW_LINES)
+    } else {
+        height
+    }
+}
+
 /// Render result containing the output string
 pub struct RenderResult {
     pub output: String,
+    /// What each screen row shows: the absolute index of a selectable row
+    /// (as in `UIState::selected_index`), or None for anything else
+    pub rows: Vec<Option<usize>>,
 }
 
 /// Render the entire UI to a string
@@ -251,19 +367,25 @@ pub fn render(
     response: &SearchResponse,
     tasks: &SharedTasks,
     root_name: &str,
+    terminal_width: usize,
     terminal_height: usize,
 ) -> RenderResult {
     let mut output = String::new();
 
+    let theme = &state.theme;
+
     // Header
-    output.push_str("\x1b[36m  Task Runner Detector\x1b[0m");
+    output.push_str(&format!(
+        "\x1b[{}m  Task Runner Detector\x1b[0m",
+        theme.accent
+    ));
     if !response.scanning_done {
         output.push_str(" \x1b[33m(scanning...)\x1b[0m");
     }
     output.push_str("\x1b[K\r\n");
     output.push_str(&format!(
-        "\x1b[90m  {} tasks found\x1b[0m\x1b[K\r\n",
-        response.total_tasks
+        "\x1b[{}m  {} tasks found\x1b[0m\x1b[K\r\n",
+        theme.muted, response.total_tasks
     ));
     output.push_str("\x1b[K\r\n");
 
@@ -279,11 +401,14 @@ pub fn render(
 
     if state.mode == Mode::Select {
         output.push_str(&format!(
-            "\x1b[36m❯ \x1b[0m{}\x1b[7m{}\x1b[0m{}\x1b[K\r\n",
-            input_before, input_char, input_after
+            "\x1b[{}m{} \x1b[0m{}\x1b[7m{}\x1b[0m{}\x1b[K\r\n",
+            theme.accent, theme.marker, input_before, input_char, input_after
         ));
     } else {
-        output.push_str(&format!("\x1b[90m❯ {}\x1b[0m\x1b[K\r\n", state.query));
+        output.push_str(&format!(
+            "\x1b[{}m{} {}\x1b[0m\x1b[K\r\n",
+            theme.muted, theme.marker, state.query
+        ));
     }
     output.push_str("\x1b[K\r\n");
 
@@ -293,27 +418,54 @@ pub fn render(
     let display_items = build_display_items(
         &tasks_guard,
         &response.matched_indices,
+        &response.collapsed,
         root_name,
         &state.query,
+        state.match_options,
     );
 
     // The selected_index is absolute, convert to relative within this slice
     let relative_selected = state.selected_index.saturating_sub(response.offset);
 
     // Render all display items (they're already the viewport slice from backend)
-    let list_height = terminal_height.saturating_sub(8);
+    let list_height = list_height(state, terminal_height);
+    let list_start = output.matches("\r\n").count();
+    if state.show_help {
+        output.push_str(&render_help(list_height, theme));
+    }
+    let mut rows = vec![None; output.matches("\r\n").count()];
     let mut task_idx = 0;
     for (rendered_lines, item) in display_items.iter().enumerate() {
-        if rendered_lines >= list_height {
+        if rendered_lines >= list_height || state.show_help {
             break;
         }
-        let is_selected = matches!(item, DisplayItem::Task { .. }) && task_idx == relative_selected;
+        let is_selected = item.is_selectable() && task_idx == relative_selected;
         output.push_str(&render_item(item, is_selected, state));
-        if matches!(item, DisplayItem::Task { .. }) {
+        if item.is_selectable() {
+            rows.push(Some(response.offset + task_idx));
             task_idx += 1;
+        } else {
+            rows.push(None);
         }
     }
 
+    if state.show_preview {
+        // Pad the list so the preview stays at the bottom
+        while rows.len() < list_start + list_height {
+            output.push_str("\x1b[K\r\n");
+            rows.push(None);
+        }
+        let selected = display_items
+            .iter()
+            .filter(|item| item.is_selectable())
+            .nth(relative_selected)
+            .and_then(|item| match item {
+                DisplayItem::Task { task, .. } => Some(*task),
+                DisplayItem::Folder { .. } => None,
+            });
+        output.push_str(&render_preview(selected, terminal_width, theme));
+    }
+
     // Status line
     output.push_str("\x1b[K\r\n");
     let task_count = response.matched_tasks;
@@ -324,20 +476,150 @@ pub fn render(
     };
 
     match state.mode {
+        _ if state.flash.is_some() => output.push_str(&format!(
+            "\x1b[32m  ✓ {}\x1b[0m\x1b[K",
+            state.flash.as_deref().unwrap_or_default()
+        )),
+        Mode::Select if !state.marked.is_empty() => output.push_str(&format!(
+            "\x1b[{}m  {}/{} │ {} marked │ space toggle │ enter run marked │ esc cancel │ ? help\x1b[0m\x1b[K",
+            theme.muted,
+            current_task_num,
+            task_count,
+            state.marked.len()
+        )),
+        Mode::Select if state.vim_keys && !state.searching => output.push_str(&format!(
+            "\x1b[{}m  {}/{} │ j/k navigate │ / search │ enter run │ esc cancel │ ? help\x1b[0m\x1b[K",
+            theme.muted, current_task_num, task_count
+        )),
         Mode::Select => output.push_str(&format!(
-            "\x1b[90m  {}/{} │ ↑↓ navigate │ tab edit │ enter run │ esc cancel\x1b[0m\x1b[K",
-            current_task_num, task_count
+            "\x1b[{}m  {}/{} │ ↑↓ navigate │ tab edit │ enter run │ esc cancel │ ? help\x1b[0m\x1b[K",
+            theme.muted, current_task_num, task_count
+        )),
+        Mode::Edit => output.push_str(&format!(
+            "\x1b[{}m  edit mode │ ↑↓ back to select │ tab expand │ enter run │ esc cancel\x1b[0m\x1b[K",
+            theme.muted
+        )),
+        Mode::Expanded => output.push_str(&format!(
+            "\x1b[{}m  expanded │ ↑↓ back to select │ tab back │ enter run │ esc cancel\x1b[0m\x1b[K",
+            theme.muted
         )),
-        Mode::Edit => output.push_str(
-            "\x1b[90m  edit mode │ ↑↓ back to select │ tab expand │ enter run │ esc cancel\x1b[0m\x1b[K",
-        ),
-        Mode::Expanded => output.push_str(
-            "\x1b[90m  expanded │ ↑↓ back to select │ tab back │ enter run │ esc cancel\x1b[0m\x1b[K",
-        ),
     }
 
     output.push_str("\x1b[J");
-    RenderResult { output }
+    RenderResult { output, rows }
+}
+
+/// Keybindings listed in the help overlay: (keys, description)
+const HELP_LINES: &[(&str, &str)] = &[
+    ("↑ / ↓", "Move selection (returns to select mode)"),
+    ("Enter", "Run the selected task (or the edited command)"),
+    ("Tab", "Cycle select → edit → expanded script"),
+    ("→ / Enter", "Expand the selected folder"),
+    ("←", "Collapse the selected task's folder (empty query)"),
+    ("Ctrl+T", "Toggle showing script bodies instead of commands"),
+    ("Esc", "Go back one step (expanded → edit → select → exit)"),
+    ("Ctrl+C", "Exit immediately"),
+    ("← / →", "Move cursor"),
+    ("Alt/Ctrl+← →", "Move cursor by word"),
+    ("Home / End", "Jump to start/end"),
+    ("Ctrl+A / Ctrl+E", "Jump to start/end"),
+    ("Ctrl+W", "Delete previous word"),
+    ("Ctrl+U", "Delete to start of line"),
+    ("Ctrl+K", "Delete to end of line"),
+    ("Ctrl+P", "Toggle the description/script preview pane"),
+    ("Ctrl+Y", "Copy the selected (or edited) command"),
+    (
+        "j/k g/G",
+        "With --vim: move, jump to top/bottom (/ to search)",
+    ),
+    (
+        "Space/Ctrl+Space",
+        "Mark a task to run marked ones in order (Ctrl while searching)",
+    ),
+    (
+        "Wheel / Click",
+        "Move / select a task (double-click runs it)",
+    ),
+    ("?", "Toggle this help (any key closes it)"),
+];
+
+/// Query syntax listed in the help overlay (fzf-style): (example, description)
+const QUERY_SYNTAX_LINES: &[(&str, &str)] = &[
+    ("web build", "Tasks matching every term (fuzzy)"),
+    ("'exact", "Contains \"exact\" as is"),
+    ("^prefix", "Starts with \"prefix\""),
+    ("suffix$", "Ends with \"suffix\""),
+    ("!term", "Doesn't match \"term\" (combine: !^pre)"),
+];
+
+/// Render the help overlay in place of the task list: keybindings, then
+/// query syntax, cut off at `height` lines
+fn render_help(height: usize, theme: &Theme) -> String {
+    let section = |title: &str| format!("  \x1b[{}m{}\x1b[0m", theme.accent, title);
+    let entry = |(keys, description): &(&str, &str)| {
+        format!(
+            "    \x1b[1m{:<16}\x1b[0m \x1b[{}m{}\x1b[0m",
+            keys, theme.muted, description
+        )
+    };
+
+    let lines = std::iter::once(section("Keybindings"))
+        .chain(HELP_LINES.iter().map(entry))
+        .chain([String::new(), section("Query syntax")])
+        .chain(QUERY_SYNTAX_LINES.iter().map(entry));
+    let mut output = String::new();
+    for line in lines.take(height) {
+        output.push_str(&line);
+        output.push_str("\x1b[K\r\n");
+    }
+    output
+}
+
+/// Render the preview pane: a separator, then the task's description and
+/// its script (or command), wrapped to the terminal width. Always takes
+/// exactly `PREVIEW_LINES` lines so the layout doesn't jump.
+fn render_preview(task: Option<&TaskItem>, width: usize, theme: &Theme) -> String {
+    let mut output = format!(
+        "\x1b[{}m{}\x1b[0m\x1b[K\r\n",
+        theme.muted,
+        "─".repeat(width.saturating_sub(1))
+    );
+
+    let mut lines = Vec::new();
+    if let Some(task) = task {
+        if let Some(description) = &task.description {
+            for line in wrap(description, width.saturating_sub(2)) {
+                lines.push(format!("  \x1b[1m{}\x1b[0m", line));
+            }
+        }
+        let body = task.script.as_deref().unwrap_or(&task.command);
+        for line in body.lines().filter(|line| !line.trim().is_empty()) {
+            for line in wrap(&line.replace('\t', "    "), width.saturating_sub(2)) {
+                lines.push(format!("  {}", line));
+            }
+        }
+    }
+
+    let height = PREVIEW_LINES - 1;
+    if lines.len() > height {
+        lines.truncate(height - 1);
+        lines.push(format!("  \x1b[{}m…\x1b[0m", theme.muted));
+    }
+    lines.resize(height, String::new());
+    for line in lines {
+        output.push_str(&line);
+        output.push_str("\x1b[K\r\n");
+    }
+    output
+}
+
+/// Split a line into chunks of at most `width` characters
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    chars
+        .chunks(width.max(1))
+        .map(|chunk| chunk.iter().collect())
+        .collect()
 }
 
 /// Render input with cursor highlight
@@ -354,6 +636,7 @@ fn render_input_cursor(value: &str, cursor: usize) -> (String, char, String) {
 
 /// Render a single display item
 fn render_item(item: &DisplayItem, is_selected: bool, state: &UIState) -> String {
+    let theme = &state.theme;
     match item {
         DisplayItem::Folder {
             name,
@@ -361,20 +644,59 @@ fn render_item(item: &DisplayItem, is_selected: bool, state: &UIState) -> String
             is_last,
             parent_is_last,
             match_indices,
+            ancestor,
+            collapsed,
+            dir,
         } => {
             let prefix = tree_prefix(*depth, *is_last, parent_is_last);
-            let highlighted_name = render_folder_highlighted(name, match_indices);
-            if *depth == 0 {
-                format!("  📁 {}\x1b[K\r\n", highlighted_name)
+            let mut highlighted_name = render_folder_highlighted(name, match_indices, *ancestor);
+            // With --abs-paths, follow the name with the directory it stands for
+            let abs_dir = match (&state.abs_root, depth) {
+                (Some(root), 0) => Some(root.as_path()),
+                (Some(_), _) => *dir,
+                (None, _) => None,
+            };
+            if let Some(abs_dir) = abs_dir {
+                highlighted_name.push_str(&format!(
+                    " \x1b[{}m{}\x1b[0m",
+                    theme.muted,
+                    abs_dir.display()
+                ));
+            }
+            if *collapsed {
+                // Laid out like a task row so it can carry the selection marker
+                let (branch_color, marker) = if is_selected {
+                    (
+                        theme.accent,
+                        format!("\x1b[{}m{}\x1b[0m", theme.accent, theme.marker),
+                    )
+                } else {
+                    (theme.muted, " ".repeat(theme.marker.chars().count()))
+                };
+                format!(
+                    "\x1b[{}m{}\x1b[0m {} {}{} \x1b[{}m▸\x1b[0m\x1b[K\r\n",
+                    branch_color,
+                    prefix,
+                    marker,
+                    theme.folder_icon(),
+                    highlighted_name,
+                    theme.muted
+                )
+            } else if *depth == 0 {
+                format!("  {}{}\x1b[K\r\n", theme.folder_icon(), highlighted_name)
             } else {
                 format!(
-                    "\x1b[90m{}\x1b[0m 📁 {}\x1b[K\r\n",
-                    prefix, highlighted_name
+                    "\x1b[{}m{}\x1b[0m {}{}\x1b[K\r\n",
+                    theme.muted,
+                    prefix,
+                    theme.folder_icon(),
+                    highlighted_name
                 )
             }
         }
         DisplayItem::Task {
             task,
+            id,
             depth,
             is_last,
             parent_is_last,
@@ -384,59 +706,92 @@ fn render_item(item: &DisplayItem, is_selected: bool, state: &UIState) -> String
             let is_editing = is_selected && matches!(state.mode, Mode::Edit | Mode::Expanded);
             let is_dimmed = matches!(state.mode, Mode::Edit | Mode::Expanded) && !is_selected;
             let marker = if is_selected {
-                "\x1b[36m❯\x1b[0m"
+                format!("\x1b[{}m{}\x1b[0m", theme.accent, theme.marker)
             } else {
-                " "
+                " ".repeat(theme.marker.chars().count())
             };
 
+            // Match indices only apply to the command, so scripts aren't highlighted
+            let (text, match_indices) = match (&task.script, state.show_script) {
+                (Some(script), true) => (inline_script(script), &[][..]),
+                _ => (task.command.clone(), &match_indices[..]),
+            };
             let cmd = if is_editing {
                 let (b, c, a) = render_input_cursor(&state.edit_buffer, state.edit_cursor);
                 format!("{}\x1b[7m{}\x1b[0m{}", b, c, a)
             } else if is_dimmed {
-                format!("\x1b[90m{}\x1b[0m", task.command)
+                format!("\x1b[{}m{}\x1b[0m", theme.muted, text)
             } else {
-                render_command_highlighted(&task.command, match_indices)
+                render_command_highlighted(&text, match_indices, theme)
             };
 
-            let branch_color = if is_selected { "36" } else { "90" };
-            let icon = task.runner_icon();
+            let branch_color = if is_selected {
+                theme.accent
+            } else {
+                theme.muted
+            };
+            let icon = if theme.emoji { task.runner_icon() } else { "" };
+            // Marked tasks show their place in the run order
+            let mark = match state.marked.iter().position(|(marked, _)| marked == id) {
+                Some(pos) => format!("\x1b[32m✓{}\x1b[0m ", pos + 1),
+                None => String::new(),
+            };
+            let missing = match task.command.split_whitespace().next() {
+                Some(program) if !task.available => {
+                    format!(" \x1b[33m({} not installed)\x1b[0m", program)
+                }
+                _ => String::new(),
+            };
 
             if is_dimmed {
                 format!(
-                    "\x1b[90m{}\x1b[0m {} \x1b[90m{}\x1b[0m  {}\x1b[K\r\n",
-                    prefix, marker, icon, cmd
+                    "\x1b[{}m{}\x1b[0m {} \x1b[{}m{}\x1b[0m  {}{}{}\x1b[K\r\n",
+                    theme.muted, prefix, marker, theme.muted, icon, mark, cmd, missing
                 )
             } else {
                 format!(
-                    "\x1b[{}m{}\x1b[0m {} {}  {}\x1b[K\r\n",
-                    branch_color, prefix, marker, icon, cmd
+                    "\x1b[{}m{}\x1b[0m {} {}  {}{}{}\x1b[K\r\n",
+                    branch_color, prefix, marker, icon, mark, cmd, missing
                 )
             }
         }
     }
 }
 
-/// Render folder name with match highlighting (underline matched chars)
-fn render_folder_highlighted(name: &str, match_indices: &[u32]) -> String {
+/// Fit a (possibly multi-line) script body on one row
+fn inline_script(script: &str) -> String {
+    script
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Render folder name with match highlighting (underline matched chars).
+/// Folders holding tasks are bold; ancestor folders shown only for context
+/// use regular weight so the folder that owns the tasks stands out.
+fn render_folder_highlighted(name: &str, match_indices: &[u32], ancestor: bool) -> String {
+    let style = if ancestor { "37" } else { "1;37" };
     if match_indices.is_empty() {
-        return format!("\x1b[1;37m{}\x1b[0m", name);
+        return format!("\x1b[{}m{}\x1b[0m", style, name);
     }
 
     let mut result = String::new();
     for (i, c) in name.chars().enumerate() {
         let is_match = match_indices.contains(&(i as u32));
         if is_match {
-            // Bold + underline for matches
-            result.push_str(&format!("\x1b[1;37;4m{}\x1b[0m", c));
+            // Underline for matches
+            result.push_str(&format!("\x1b[{};4m{}\x1b[0m", style, c));
         } else {
-            result.push_str(&format!("\x1b[1;37m{}\x1b[0m", c));
+            result.push_str(&format!("\x1b[{}m{}\x1b[0m", style, c));
         }
     }
     result
 }
 
 /// Render command with match highlighting (underline matched chars)
-fn render_command_highlighted(command: &str, match_indices: &[u32]) -> String {
+fn render_command_highlighted(command: &str, match_indices: &[u32], theme: &Theme) -> String {
     // Parse command structure: "runner [run/task] args..."
     let parts: Vec<&str> = command.split_whitespace().collect();
     if parts.is_empty() {
@@ -455,9 +810,9 @@ fn render_command_highlighted(command: &str, match_indices: &[u32]) -> String {
 
         // Determine base color for this part
         let base_color = if part_idx == 0 {
-            "36" // Cyan for runner (npm, cargo, make, etc.)
+            theme.accent // Cyan for runner (npm, cargo, make, etc.)
         } else if part_idx == 1 && (*part == "run" || *part == "task") {
-            "90" // Gray for "run"/"task"
+            theme.muted // Gray for "run"/"task"
         } else {
             "37" // White for task name/args
         };
@@ -500,13 +855,260 @@ mod tests {
 
     #[test]
     fn test_render_command_highlighted() {
-        let result = render_command_highlighted("npm run build", &[]);
+        let result = render_command_highlighted("npm run build", &[], &Theme::default());
         // Should contain color codes
         assert!(result.contains("\x1b[36m")); // Cyan for npm
         assert!(result.contains("\x1b[90m")); // Gray for run
         assert!(result.contains("\x1b[37m")); // White for build
     }
 
+    #[test]
+    fn test_render_command_accessible_has_no_dim() {
+        let result = render_command_highlighted("npm run build", &[], &Theme::accessible());
+        assert!(!result.contains("\x1b[90m"));
+    }
+
+    #[test]
+    fn test_muted_text_is_not_dim_when_accessible() {
+        console::set_colors_enabled(true);
+        assert_eq!(Theme::default().muted("in").to_string(), "\x1b[2min\x1b[0m");
+        assert_eq!(
+            Theme::accessible().muted("in").to_string(),
+            "\x1b[37min\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_inline_script() {
+        assert_eq!(inline_script("tsc -p ."), "tsc -p .");
+        assert_eq!(
+            inline_script("cargo build\n\n    cargo test\n"),
+            "cargo build; cargo test"
+        );
+    }
+
+    #[test]
+    fn test_ancestor_folders_render_regular_weight() {
+        let task = |folder: &str| TaskItem {
+            folder: folder.to_string(),
+            command: "make build".to_string(),
+            script: None,
+            description: None,
+            runner_type: task_runner_detector::RunnerType::Make,
+            config_path: std::path::PathBuf::from(format!("/repo/{}/Makefile", folder)),
+            available: true,
+        };
+        let tasks = vec![task("apps/web"), task("libs")];
+        let collapsed = HashMap::new();
+        let items = build_display_items(
+            &tasks,
+            &[0, 1],
+            &collapsed,
+            "repo",
+            "",
+            MatchOptions::default(),
+        );
+
+        let folders: Vec<_> = items
+            .iter()
+            .filter_map(|item| match item {
+                DisplayItem::Folder { name, ancestor, .. } => Some((*name, *ancestor)),
+                DisplayItem::Task { .. } => None,
+            })
+            .collect();
+        assert_eq!(
+            folders,
+            vec![
+                ("repo", false),
+                ("apps", true),
+                ("web", false),
+                ("libs", false)
+            ]
+        );
+
+        assert_eq!(
+            render_folder_highlighted("apps", &[], true),
+            "\x1b[37mapps\x1b[0m"
+        );
+        assert_eq!(
+            render_folder_highlighted("web", &[], false),
+            "\x1b[1;37mweb\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_collapsed_folder_replaces_its_tasks() {
+        let task = |folder: &str| TaskItem {
+            folder: folder.to_string(),
+            command: "make build".to_string(),
+            script: None,
+            description: None,
+            runner_type: task_runner_detector::RunnerType::Make,
+            config_path: std::path::PathBuf::from(format!("/repo/{}/Makefile", folder)),
+            available: true,
+        };
+        let tasks = vec![task("."), task("apps/web")];
+        let collapsed = HashMap::from([(1, "apps".to_string())]);
+        let items = build_display_items(
+            &tasks,
+            &[0, 1],
+            &collapsed,
+            "repo",
+            "",
+            MatchOptions::default(),
+        );
+
+        let rows: Vec<_> = items
+            .iter()
+            .map(|item| match item {
+                DisplayItem::Folder {
+                    name, collapsed, ..
+                } => format!("{}{}", name, if *collapsed { " ▸" } else { "" }),
+                DisplayItem::Task { task, .. } => task.command.clone(),
+            })
+            .collect();
+        assert_eq!(rows, vec!["repo", "make build", "apps ▸"]);
+        assert_eq!(items.iter().filter(|i| i.is_selectable()).count(), 2);
+    }
+
+    #[test]
+    fn test_rows_map_screen_lines_to_tasks() {
+        let task = |folder: &str| TaskItem {
+            folder: folder.to_string(),
+            command: "make build".to_string(),
+            script: None,
+            description: None,
+            runner_type: task_runner_detector::RunnerType::Make,
+            config_path: std::path::PathBuf::from(format!("/repo/{}/Makefile", folder)),
+            available: true,
+        };
+        let tasks: SharedTasks =
+            std::sync::Arc::new(std::sync::RwLock::new(vec![task("."), task("apps/web")]));
+        let response = SearchResponse {
+            matched_indices: vec![0, 1],
+            offset: 0,
+            total_tasks: 2,
+            matched_tasks: 2,
+            scanning_done: true,
+            selected_index: 0,
+            collapsed: HashMap::new(),
+        };
+
+        let result = render(&UIState::default(), &response, &tasks, "repo", 80, 30);
+        // Five header lines, then: repo, make build, apps, web, make build
+        assert_eq!(
+            result.rows,
+            [
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(0),
+                None,
+                None,
+                Some(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_help_lists_query_syntax() {
+        let help = render_help(100, &Theme::default());
+        assert!(help.contains("Keybindings"));
+        assert!(help.contains("Query syntax"));
+        assert!(help.contains("^prefix"));
+        assert_eq!(
+            help.matches("\r\n").count(),
+            HELP_LINES.len() + QUERY_SYNTAX_LINES.len() + 3
+        );
+        // Cut off at the space the list has
+        assert_eq!(render_help(5, &Theme::default()).matches("\r\n").count(), 5);
+    }
+
+    #[test]
+    fn test_preview_pane() {
+        let task = TaskItem {
+            folder: ".".to_string(),
+            command: "npm run build".to_string(),
+            script: Some("tsc -p .\n\nvite build --mode production".to_string()),
+            description: Some("Build the app".to_string()),
+            runner_type: task_runner_detector::RunnerType::Npm,
+            config_path: std::path::PathBuf::from("/repo/package.json"),
+            available: true,
+        };
+        let preview = render_preview(Some(&task), 20, &Theme::default());
+        let lines: Vec<_> = preview.split("\x1b[K\r\n").collect();
+        assert_eq!(lines.len(), PREVIEW_LINES + 1);
+        assert_eq!(
+            lines[1..],
+            [
+                "  \x1b[1mBuild the app\x1b[0m",
+                "  tsc -p .",
+                "  vite build --mode ",
+                "  production",
+                "",
+                "",
+            ]
+        );
+
+        // The list gives up its rows to the pane
+        let state = UIState {
+            show_preview: true,
+            ..UIState::default()
+        };
+        assert_eq!(
+            list_height(&state, 30),
+            list_height(&UIState::default(), 30) - PREVIEW_LINES
+        );
+    }
+
+    #[test]
+    fn test_abs_paths_follow_folder_names() {
+        let task = |folder: &str| TaskItem {
+            folder: folder.to_string(),
+            command: "make build".to_string(),
+            script: None,
+            description: None,
+            runner_type: task_runner_detector::RunnerType::Make,
+            config_path: std::path::PathBuf::from(format!("/repo/{}/Makefile", folder)),
+            available: true,
+        };
+        let tasks = vec![task("apps/web")];
+        let collapsed = HashMap::new();
+        let items = build_display_items(
+            &tasks,
+            &[0],
+            &collapsed,
+            "repo",
+            "",
+            MatchOptions::default(),
+        );
+        let rows = |state: &UIState| -> Vec<String> {
+            items
+                .iter()
+                .filter(|item| matches!(item, DisplayItem::Folder { .. }))
+                .map(|item| render_item(item, false, state))
+                .collect()
+        };
+
+        // Relative by default
+        assert!(rows(&UIState::default())
+            .iter()
+            .all(|row| !row.contains("/repo")));
+
+        // Ancestor folders only give context, so they don't get a path
+        let state = UIState {
+            abs_root: Some(std::path::PathBuf::from("/repo")),
+            ..UIState::default()
+        };
+        let rows = rows(&state);
+        assert!(rows[0].contains(" \x1b[90m/repo\x1b[0m"));
+        assert!(!rows[1].contains("/repo"));
+        assert!(rows[2].contains(" \x1b[90m/repo/apps/web\x1b[0m"));
+    }
+
     #[test]
     fn test_tree_prefix() {
         // Root level
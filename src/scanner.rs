@@ -1,21 +1,332 @@
 //! Directory scanner for task runner config files
 
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::Sender;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
+use std::time::Duration;
 
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
 use ignore::{WalkBuilder, WalkState};
 
 use crate::parsers::{self, Parser};
-use crate::{ScanResult, TaskRunner};
+use crate::{
+    global_tasks_path, load_global_tasks, proto, readme, services, RunnerType, ScanError,
+    ScanResult, TaskRunner,
+};
+
+/// Filename patterns used for generic script detection when
+/// `ScanOptions::script_patterns` is empty
+pub const DEFAULT_SCRIPT_PATTERNS: &[&str] = &[
+    "run.sh",
+    "dev.sh",
+    "build.sh",
+    "test.sh",
+    "start.sh",
+    "setup.sh",
+    "bootstrap.sh",
+    "deploy.sh",
+];
+
+/// How long a single file may take to parse when `ScanOptions::parse_timeout`
+/// isn't set
+pub const DEFAULT_PARSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Hidden config files that are still scanned while other hidden entries are skipped
+const HIDDEN_CONFIG_FILES: &[&str] =
+    &[".justfile", ".pre-commit-config.yaml", ".devcontainer.json"];
+
+/// Walker depth covered by the first pass of a `shallow_first` scan: files in
+/// the root (depth 1) and in its immediate subfolders (depth 2)
+const SHALLOW_DEPTH: usize = 2;
+
+/// Hidden folders that are still descended into (they hold config files)
+const HIDDEN_CONFIG_DIRS: &[&str] = &[".devcontainer", ".github"];
 
 /// Options for customizing the scan behavior
 #[derive(Debug, Clone, Default)]
 pub struct ScanOptions {
-    /// Maximum depth to traverse (None = unlimited)
+    /// How many folders deep to look below the root: 0 only reads config
+    /// files in the root itself (None = unlimited)
     pub max_depth: Option<usize>,
     /// If true, ignore .gitignore and scan all files
     pub no_ignore: bool,
+    /// If true, list ad-hoc script files (e.g. `run.sh`) as `./<file>` tasks
+    pub generic_scripts: bool,
+    /// Filename globs for generic script detection (empty = DEFAULT_SCRIPT_PATTERNS)
+    pub script_patterns: Vec<String>,
+    /// If true, fill missing task descriptions from a task table in a
+    /// sibling TASKS.md or README.md
+    pub readme_descriptions: bool,
+    /// If true, Makefile targets that only invoke another runner
+    /// (`build:` → `npm run build`) are listed as the underlying command
+    pub flatten_make_wrappers: bool,
+    /// Maximum time to spend parsing one file (None = DEFAULT_PARSE_TIMEOUT,
+    /// `Duration::ZERO` = no limit). Files that take longer are skipped
+    /// (reported by `scan_report`) so one pathological file can't stall the scan.
+    pub parse_timeout: Option<Duration>,
+    /// If true, also list the user's global tasks (see [`global_tasks_path`]),
+    /// shown under a "global" folder
+    pub global_tasks: bool,
+    /// Task name globs to leave out across all runners (e.g. `postinstall`, `pre*`).
+    /// Runners left without tasks are dropped.
+    pub exclude_task_names: Vec<String>,
+    /// If true, ESLint/Prettier/Biome config files imply a lint/format task
+    /// (`npx eslint .`, ...) when the sibling package.json has no script for it
+    pub implied_tasks: bool,
+    /// Only list these runner types (None = all of them)
+    pub include_runners: Option<Vec<RunnerType>>,
+    /// Runner types to leave out. Config files that can only produce
+    /// filtered-out runners aren't parsed at all.
+    pub exclude_runners: Vec<RunnerType>,
+    /// If true, commands whose program is pinned in a `.prototools` file
+    /// above the config file are run through `proto run`
+    pub proto: bool,
+    /// If true, config files in the root and its immediate subfolders are
+    /// scanned (and streamed) before anything deeper, so the usually most
+    /// wanted tasks show up first. This walks the top of the tree twice, so
+    /// total scan time goes up slightly.
+    pub shallow_first: bool,
+    /// If true, tasks sharing a config file, runner type and name are
+    /// collapsed into the first one before results are returned. Only
+    /// applies to the collecting scans (`scan_with_options`, `scan_report`),
+    /// not to `scan_streaming`.
+    pub dedup: bool,
+    /// If true, [`scan_streaming_items`] also sends parse and walk errors
+    /// (as [`ScanItem::Error`]) instead of skipping them
+    pub report_errors: bool,
+    /// Config file path globs, relative to the scan root (e.g.
+    /// `apps/*/package.json`). When set, only matching files are parsed;
+    /// `*` doesn't cross folders, `**` does.
+    pub only_paths: Vec<String>,
+    /// If true, tasks that look like they need a running service (named
+    /// like `e2e` / `integration`, or depending on a task that starts
+    /// `docker compose`) get a note in their description
+    pub service_notes: bool,
+    /// If true, event hooks (see [`crate::TaskMetadata::hook`]) are listed too.
+    /// Otherwise they're only listed for config files that define nothing else.
+    pub include_hooks: bool,
+}
+
+impl ScanOptions {
+    /// Build the glob set for generic script detection, if enabled.
+    /// Invalid patterns are skipped.
+    fn script_globs(&self) -> Option<GlobSet> {
+        if !self.generic_scripts {
+            return None;
+        }
+
+        let mut builder = GlobSetBuilder::new();
+        if self.script_patterns.is_empty() {
+            for pattern in DEFAULT_SCRIPT_PATTERNS {
+                if let Ok(glob) = Glob::new(pattern) {
+                    builder.add(glob);
+                }
+            }
+        } else {
+            for pattern in &self.script_patterns {
+                if let Ok(glob) = Glob::new(pattern) {
+                    builder.add(glob);
+                }
+            }
+        }
+        builder.build().ok()
+    }
+
+    /// Build the glob set for excluded task names, if any. Invalid patterns are skipped.
+    fn exclude_task_globs(&self) -> Option<GlobSet> {
+        if self.exclude_task_names.is_empty() {
+            return None;
+        }
+
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &self.exclude_task_names {
+            if let Ok(glob) = Glob::new(pattern) {
+                builder.add(glob);
+            }
+        }
+        builder.build().ok()
+    }
+
+    /// Build the glob set for `only_paths`, if any. Invalid patterns are skipped.
+    fn only_path_globs(&self) -> Option<GlobSet> {
+        if self.only_paths.is_empty() {
+            return None;
+        }
+
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &self.only_paths {
+            if let Ok(glob) = GlobBuilder::new(pattern).literal_separator(true).build() {
+                builder.add(glob);
+            }
+        }
+        builder.build().ok()
+    }
+
+    /// Whether a runner type passes `include_runners` and `exclude_runners`
+    pub fn allows_runner(&self, runner_type: RunnerType) -> bool {
+        self.include_runners
+            .as_ref()
+            .map_or(true, |include| include.contains(&runner_type))
+            && !self.exclude_runners.contains(&runner_type)
+    }
+
+    /// Whether a file with this [`runner_type_hint`] could produce an allowed
+    /// runner. A hint that heads a [`RUNNER_FAMILIES`] entry stands for the
+    /// whole family.
+    fn allows_hint(&self, hint: RunnerType) -> bool {
+        let single = [hint];
+        let family = RUNNER_FAMILIES
+            .iter()
+            .find(|family| family[0] == hint)
+            .map_or(&single[..], |family| family);
+        family
+            .iter()
+            .any(|&runner_type| self.allows_runner(runner_type))
+    }
+}
+
+/// Runner types one config file name can't tell apart, headed by the type
+/// [`runner_type_hint`] reports for it. package.json also holds inline
+/// Turborepo config.
+const RUNNER_FAMILIES: &[&[RunnerType]] = &[
+    &[
+        RunnerType::Npm,
+        RunnerType::Yarn,
+        RunnerType::Pnpm,
+        RunnerType::Bun,
+        RunnerType::Turbo,
+    ],
+    &[RunnerType::Poetry, RunnerType::Pdm, RunnerType::Hatch],
+    &[RunnerType::Flutter, RunnerType::Dart],
+];
+
+/// The runner type a config file produces, so files whose runner is
+/// filtered out can be skipped without reading them. Files shared by
+/// several runners report the head of their [`RUNNER_FAMILIES`] entry.
+fn runner_type_hint(path: &Path) -> Option<RunnerType> {
+    config_kind(path).map(|&(_, runner_type, _)| runner_type)
+}
+
+/// Drop event hooks from a runner that also has tasks meant to be run by hand
+fn hide_hooks(runner: &mut TaskRunner) {
+    if runner.tasks.iter().any(|task| !task.metadata.hook) {
+        runner.tasks.retain(|task| !task.metadata.hook);
+    }
+}
+
+/// Drop a runner's tasks whose name matches an exclude glob
+fn exclude_tasks(runner: &mut TaskRunner, exclude: Option<&GlobSet>) {
+    if let Some(exclude) = exclude {
+        runner.tasks.retain(|task| !exclude.is_match(&task.name));
+    }
+}
+
+/// Collapse tasks that share `(config_path, runner_type, name)`, keeping the
+/// first. Runners for the same config file and type are merged, and runners
+/// left without tasks are dropped.
+fn dedup_runners(runners: Vec<TaskRunner>) -> Vec<TaskRunner> {
+    let mut merged: Vec<TaskRunner> = Vec::new();
+    let mut runner_index: HashMap<(PathBuf, RunnerType), usize> = HashMap::new();
+    let mut seen: HashSet<(PathBuf, RunnerType, String)> = HashSet::new();
+
+    for mut runner in runners {
+        runner.tasks.retain(|task| {
+            seen.insert((
+                runner.config_path.clone(),
+                runner.runner_type,
+                task.name.clone(),
+            ))
+        });
+        if runner.tasks.is_empty() {
+            continue;
+        }
+        match runner_index.entry((runner.config_path.clone(), runner.runner_type)) {
+            Entry::Occupied(entry) => merged[*entry.get()].tasks.append(&mut runner.tasks),
+            Entry::Vacant(entry) => {
+                entry.insert(merged.len());
+                merged.push(runner);
+            }
+        }
+    }
+    merged
+}
+
+/// Outcome of a full scan, including what went wrong along the way
+#[derive(Debug, Default)]
+pub struct ScanReport {
+    /// Runners that parsed successfully and have at least one task
+    pub runners: Vec<TaskRunner>,
+    /// Parse and walk errors, which the other scan functions drop
+    pub errors: Vec<ScanError>,
+    /// Number of recognized config files that were handed to a parser
+    pub files_scanned: usize,
+}
+
+impl ScanReport {
+    /// True when config files were found but none of them yielded tasks
+    /// because parsing failed, as opposed to a tree with no tasks at all
+    pub fn parse_failed(&self) -> bool {
+        self.files_scanned > 0 && self.runners.is_empty() && !self.errors.is_empty()
+    }
+}
+
+/// Progress counters for [`scan_with_progress`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScanStats {
+    /// Files the walker visited
+    pub files_considered: usize,
+    /// Recognized config files that were handed to a parser
+    pub files_parsed: usize,
+    /// Config files that failed to parse (or timed out)
+    pub parse_errors: usize,
+    /// Runners with at least one task
+    pub runners_found: usize,
+}
+
+/// How often [`scan_with_progress`] reports while the walk is running
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A message from [`scan_streaming_items`]
+#[derive(Debug)]
+pub enum ScanItem {
+    Runner(TaskRunner),
+    Error(ScanError),
+}
+
+/// Errors and counters collected during a walk for [`ScanReport`] and
+/// [`ScanStats`]. Updated from every walker thread.
+#[derive(Default)]
+struct ReportSink {
+    errors: Mutex<Vec<ScanError>>,
+    /// Where errors go instead of `errors` when streaming them
+    stream: Option<Sender<ScanItem>>,
+    files_considered: AtomicUsize,
+    files_scanned: AtomicUsize,
+    parse_errors: AtomicUsize,
+    runners_found: AtomicUsize,
+}
+
+impl ReportSink {
+    fn error(&self, error: ScanError) {
+        if let Some(stream) = &self.stream {
+            stream.send(ScanItem::Error(error)).ok();
+        } else if let Ok(mut errors) = self.errors.lock() {
+            errors.push(error);
+        }
+    }
+
+    fn stats(&self) -> ScanStats {
+        ScanStats {
+            files_considered: self.files_considered.load(Ordering::Relaxed),
+            files_parsed: self.files_scanned.load(Ordering::Relaxed),
+            parse_errors: self.parse_errors.load(Ordering::Relaxed),
+            runners_found: self.runners_found.load(Ordering::Relaxed),
+        }
+    }
 }
 
 /// Scan a directory tree for task runners using default options
@@ -29,10 +340,9 @@ pub fn scan_with_options(
     root: impl AsRef<Path>,
     options: ScanOptions,
 ) -> ScanResult<Vec<TaskRunner>> {
-    use std::sync::mpsc;
-
     let root = root.as_ref().to_path_buf();
     let (tx, rx) = mpsc::channel();
+    let dedup = options.dedup;
 
     let handle = scan_streaming(root, options, tx);
 
@@ -42,7 +352,74 @@ pub fn scan_with_options(
     // Wait for scanner to finish
     handle.join().ok();
 
-    Ok(runners)
+    Ok(if dedup {
+        dedup_runners(runners)
+    } else {
+        runners
+    })
+}
+
+/// Scan a directory tree and report errors alongside the runners found, so
+/// callers can tell an empty tree from one where every config failed to parse
+pub fn scan_report(root: impl AsRef<Path>, options: ScanOptions) -> ScanReport {
+    let (tx, rx) = mpsc::channel();
+    let sink = ReportSink::default();
+
+    walk(
+        walk_builder(root.as_ref(), &options),
+        &options,
+        tx,
+        Some(&sink),
+        &AtomicBool::new(false),
+    );
+
+    let runners: Vec<TaskRunner> = rx.into_iter().collect();
+    ScanReport {
+        runners: if options.dedup {
+            dedup_runners(runners)
+        } else {
+            runners
+        },
+        errors: sink.errors.into_inner().unwrap_or_default(),
+        files_scanned: sink.files_scanned.into_inner(),
+    }
+}
+
+/// Scan a directory tree, calling `on_progress` with running totals every
+/// [`PROGRESS_INTERVAL`] while the walk is in progress and once more at the
+/// end. Returns the runners along with the final totals.
+pub fn scan_with_progress(
+    root: impl AsRef<Path>,
+    options: ScanOptions,
+    mut on_progress: impl FnMut(&ScanStats) + Send,
+) -> (Vec<TaskRunner>, ScanStats) {
+    let (tx, rx) = mpsc::channel();
+    let sink = ReportSink::default();
+    let builder = walk_builder(root.as_ref(), &options);
+
+    let mut runners = Vec::new();
+    thread::scope(|scope| {
+        scope.spawn(|| walk(builder, &options, tx, Some(&sink), &AtomicBool::new(false)));
+        loop {
+            match rx.recv_timeout(PROGRESS_INTERVAL) {
+                Ok(runner) => {
+                    runners.push(runner);
+                    continue;
+                }
+                Err(RecvTimeoutError::Timeout) => on_progress(&sink.stats()),
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+
+    let stats = sink.stats();
+    on_progress(&stats);
+    let runners = if options.dedup {
+        dedup_runners(runners)
+    } else {
+        runners
+    };
+    (runners, stats)
 }
 
 /// Scan a directory tree for task runners, streaming results through a channel.
@@ -53,68 +430,602 @@ pub fn scan_streaming(
     options: ScanOptions,
     tx: Sender<TaskRunner>,
 ) -> JoinHandle<()> {
+    let builder = walk_builder(&root, &options);
+    thread::spawn(move || walk(builder, &options, tx, None, &AtomicBool::new(false)))
+}
+
+/// Like [`scan_streaming`], but runners arrive as [`ScanItem::Runner`] and,
+/// with `report_errors` set, files that failed to parse (or to be read) as
+/// [`ScanItem::Error`] alongside them
+pub fn scan_streaming_items(
+    root: PathBuf,
+    options: ScanOptions,
+    tx: Sender<ScanItem>,
+) -> JoinHandle<()> {
+    let builder = walk_builder(&root, &options);
     thread::spawn(move || {
-        let mut builder = WalkBuilder::new(&root);
-        builder.follow_links(false);
-        builder.standard_filters(!options.no_ignore);
+        let (runner_tx, runner_rx) = mpsc::channel();
+        let sink = ReportSink {
+            stream: options.report_errors.then(|| tx.clone()),
+            ..Default::default()
+        };
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                walk(
+                    builder,
+                    &options,
+                    runner_tx,
+                    Some(&sink),
+                    &AtomicBool::new(false),
+                )
+            });
+            // Dropping the receiver early stops the walk once it next sends
+            for runner in runner_rx {
+                if tx.send(ScanItem::Runner(runner)).is_err() {
+                    break;
+                }
+            }
+        });
+    })
+}
 
-        if let Some(max_depth) = options.max_depth {
-            builder.max_depth(Some(max_depth));
-        }
+/// Stops a scan started with [`scan_streaming_cancellable`]
+#[derive(Debug, Clone, Default)]
+pub struct ScanHandle {
+    cancelled: Arc<AtomicBool>,
+}
 
-        builder.build_parallel().run(|| {
-            let tx = tx.clone();
-            Box::new(move |result| {
-                let entry = match result {
-                    Ok(e) => e,
-                    Err(_) => return WalkState::Continue,
-                };
+impl ScanHandle {
+    /// Stop walking and parsing. Runners already sent stay in the channel;
+    /// the scan thread finishes shortly after.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
 
-                if !entry.file_type().map(|ft| ft.is_file()).unwrap_or(false) {
-                    return WalkState::Continue;
+    /// Whether [`cancel`](Self::cancel) was called
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Like [`scan_streaming`], but also returns a [`ScanHandle`] to stop the scan
+/// early (e.g. once the user has picked a task)
+pub fn scan_streaming_cancellable(
+    root: PathBuf,
+    options: ScanOptions,
+    tx: Sender<TaskRunner>,
+) -> (JoinHandle<()>, ScanHandle) {
+    let handle = ScanHandle::default();
+    let cancelled = handle.cancelled.clone();
+    let builder = walk_builder(&root, &options);
+    let thread = thread::spawn(move || walk(builder, &options, tx, None, &cancelled));
+    (thread, handle)
+}
+
+/// Build the walker used by [`scan_streaming`] from the walk-related options
+fn walk_builder(root: &Path, options: &ScanOptions) -> WalkBuilder {
+    let mut builder = WalkBuilder::new(root);
+    builder.follow_links(false);
+    builder.standard_filters(!options.no_ignore);
+    if !options.no_ignore {
+        // Skip hidden entries ourselves so known dotfile configs still get through
+        builder.hidden(false);
+        let implied_tasks = options.implied_tasks;
+        builder.filter_entry(move |entry| {
+            let name = entry.file_name().to_string_lossy();
+            let allowed = match entry.file_type() {
+                Some(ft) if ft.is_dir() => HIDDEN_CONFIG_DIRS.contains(&name.as_ref()),
+                Some(ft) if ft.is_file() => {
+                    HIDDEN_CONFIG_FILES.contains(&name.as_ref())
+                        || (implied_tasks && parsers::is_lint_config(&name))
                 }
+                _ => false,
+            };
+            entry.depth() == 0 || !name.starts_with('.') || allowed
+        });
+    }
 
-                let path = entry.path();
-                let file_name = match path.file_name() {
-                    Some(name) => name.to_string_lossy(),
-                    None => return WalkState::Continue,
-                };
+    if let Some(max_depth) = options.max_depth {
+        // The walker counts the files in the root as depth 1
+        builder.max_depth(Some(max_depth + 1));
+    }
 
-                let parser: Option<Box<dyn Parser>> = match file_name.as_ref() {
-                    "package.json" => Some(Box::new(parsers::PackageJsonParser)),
-                    "Makefile" | "makefile" | "GNUmakefile" => {
-                        Some(Box::new(parsers::MakefileParser))
+    builder
+}
+
+/// Scan using a caller-configured `WalkBuilder`, streaming results through a
+/// channel. This runs the same parser dispatch as [`scan_streaming`] but gives
+/// full control over the walk (custom ignore files, overrides, ...).
+///
+/// The walk-related fields of `options` (`max_depth`, `no_ignore`,
+/// `shallow_first`) are not applied; configure them on the builder instead. The remaining fields still
+/// control which files are parsed and how results are post-processed.
+pub fn scan_with_walker(
+    builder: WalkBuilder,
+    options: ScanOptions,
+    tx: Sender<TaskRunner>,
+) -> JoinHandle<()> {
+    let options = ScanOptions {
+        shallow_first: false,
+        ..options
+    };
+    thread::spawn(move || walk(builder, &options, tx, None, &AtomicBool::new(false)))
+}
+
+/// Walk and parse config files, sending runners through `tx`. Blocks until
+/// the walk is done. Errors are only kept when a report sink is given.
+fn walk(
+    builder: WalkBuilder,
+    options: &ScanOptions,
+    tx: Sender<TaskRunner>,
+    sink: Option<&ReportSink>,
+    cancelled: &AtomicBool,
+) {
+    if options.global_tasks {
+        if let Some(path) = global_tasks_path() {
+            match load_global_tasks(&path) {
+                Ok(Some(mut runner)) if options.allows_runner(runner.runner_type) => {
+                    exclude_tasks(&mut runner, options.exclude_task_globs().as_ref());
+                    if !runner.tasks.is_empty() {
+                        if let Some(sink) = sink {
+                            sink.runners_found.fetch_add(1, Ordering::Relaxed);
+                        }
+                        if tx.send(runner).is_err() {
+                            return;
+                        }
                     }
-                    "Cargo.toml" => Some(Box::new(parsers::CargoTomlParser)),
-                    "pubspec.yaml" => Some(Box::new(parsers::PubspecYamlParser)),
-                    "turbo.json" => Some(Box::new(parsers::TurboJsonParser)),
-                    "pyproject.toml" => Some(Box::new(parsers::PyprojectTomlParser)),
-                    "justfile" | "Justfile" | ".justfile" => {
-                        Some(Box::new(parsers::JustfileParser))
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    if let Some(sink) = sink {
+                        sink.error(e);
                     }
-                    "deno.json" | "deno.jsonc" => Some(Box::new(parsers::DenoJsonParser)),
-                    "pom.xml" => Some(Box::new(parsers::PomXmlParser)),
-                    name if name.ends_with(".csproj")
-                        || name.ends_with(".fsproj")
-                        || name.ends_with(".vbproj") =>
-                    {
-                        Some(Box::new(parsers::CsprojParser))
+                }
+            }
+        }
+    }
+
+    // Walks to run in order, each skipping files shallower than its minimum
+    // depth (those were covered by an earlier pass)
+    let passes = if options.shallow_first {
+        let shallow_depth = options.max_depth.map_or(SHALLOW_DEPTH, |max_depth| {
+            (max_depth + 1).min(SHALLOW_DEPTH)
+        });
+        let mut shallow = builder.clone();
+        shallow.max_depth(Some(shallow_depth));
+        vec![(shallow, 0), (builder, shallow_depth + 1)]
+    } else {
+        vec![(builder, 0)]
+    };
+    let closed = AtomicBool::new(false);
+
+    for (builder, min_depth) in passes {
+        if cancelled.load(Ordering::Relaxed) || closed.load(Ordering::Relaxed) {
+            return;
+        }
+        walk_pass(builder, min_depth, options, &tx, sink, cancelled, &closed);
+    }
+}
+
+/// One parallel walk of [`walk`], parsing config files at `min_depth` or
+/// deeper. Sets `closed` when the receiver has gone away.
+fn walk_pass(
+    builder: WalkBuilder,
+    min_depth: usize,
+    options: &ScanOptions,
+    tx: &Sender<TaskRunner>,
+    sink: Option<&ReportSink>,
+    cancelled: &AtomicBool,
+    closed: &AtomicBool,
+) {
+    let script_globs = options.script_globs();
+    let parse_timeout = options.parse_timeout.unwrap_or(DEFAULT_PARSE_TIMEOUT);
+    let pool = (!parse_timeout.is_zero()).then(|| ParsePool::new(parse_timeout, parse_workers()));
+    let readme_descriptions = options.readme_descriptions;
+    let flatten_make_wrappers = options.flatten_make_wrappers;
+    let exclude = options.exclude_task_globs();
+    let only_paths = options.only_path_globs();
+    let implied_tasks = options.implied_tasks;
+    let proto = options.proto;
+    let service_notes = options.service_notes;
+    let include_hooks = options.include_hooks;
+
+    builder.build_parallel().run(|| {
+        let tx = tx.clone();
+        let script_globs = script_globs.clone();
+        let exclude = exclude.clone();
+        let only_paths = only_paths.clone();
+        let pool = pool.as_ref();
+        Box::new(move |result| {
+            if cancelled.load(Ordering::Relaxed) {
+                return WalkState::Quit;
+            }
+
+            let entry = match result {
+                Ok(e) => e,
+                Err(e) => {
+                    if let Some(sink) = sink {
+                        sink.error(e.into());
                     }
-                    _ => None,
-                };
+                    return WalkState::Continue;
+                }
+            };
+
+            if !entry.file_type().map(|ft| ft.is_file()).unwrap_or(false)
+                || entry.depth() < min_depth
+            {
+                return WalkState::Continue;
+            }
+            if let Some(sink) = sink {
+                sink.files_considered.fetch_add(1, Ordering::Relaxed);
+            }
+
+            let path = entry.path();
+            let file_name = match path.file_name() {
+                Some(name) => name.to_string_lossy(),
+                None => return WalkState::Continue,
+            };
+
+            if only_paths
+                .as_ref()
+                .is_some_and(|only| !only.is_match(relative_path(path, entry.depth())))
+            {
+                return WalkState::Continue;
+            }
 
-                if let Some(parser) = parser {
-                    if let Ok(Some(runner)) = parser.parse(path) {
-                        if !runner.tasks.is_empty() && tx.send(runner).is_err() {
-                            return WalkState::Quit;
+            if runner_type_hint(path).is_some_and(|hint| !options.allows_hint(hint)) {
+                return WalkState::Continue;
+            }
+
+            let mut file_parsers = parsers_for(path);
+            if file_parsers.is_empty() {
+                if implied_tasks && parsers::is_lint_config(&file_name) {
+                    file_parsers.push(Box::new(parsers::LintConfigParser));
+                } else if script_globs
+                    .as_ref()
+                    .is_some_and(|g| g.is_match(file_name.as_ref()))
+                {
+                    file_parsers.push(Box::new(parsers::ScriptParser));
+                }
+            }
+
+            if !file_parsers.is_empty() {
+                if let Some(sink) = sink {
+                    sink.files_scanned.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            for parser in file_parsers {
+                let parsed = match pool {
+                    Some(pool) => pool.parse(parser, path),
+                    None => parser.parse(path),
+                };
+                let parsed = match parsed {
+                    Ok(parsed) => parsed,
+                    Err(e) => {
+                        if let Some(sink) = sink {
+                            sink.parse_errors.fetch_add(1, Ordering::Relaxed);
+                            sink.error(e);
                         }
+                        None
+                    }
+                };
+                if let Some(mut runner) = parsed.filter(|r| options.allows_runner(r.runner_type)) {
+                    if flatten_make_wrappers && runner.runner_type == RunnerType::Make {
+                        parsers::flatten_make_wrappers(&mut runner);
+                    }
+                    if readme_descriptions {
+                        readme::apply_readme_descriptions(&mut runner);
+                    }
+                    if proto {
+                        proto::apply_proto(&mut runner);
+                    }
+                    if service_notes {
+                        services::apply_service_notes(&mut runner);
+                    }
+                    if !include_hooks {
+                        hide_hooks(&mut runner);
+                    }
+                    exclude_tasks(&mut runner, exclude.as_ref());
+                    if runner.tasks.is_empty() {
+                        continue;
+                    }
+                    if let Some(sink) = sink {
+                        sink.runners_found.fetch_add(1, Ordering::Relaxed);
+                    }
+                    if tx.send(runner).is_err() {
+                        closed.store(true, Ordering::Relaxed);
+                        return WalkState::Quit;
                     }
                 }
+            }
 
-                WalkState::Continue
-            })
-        });
-    })
+            WalkState::Continue
+        })
+    });
+}
+
+/// The last `depth` components of `path`: its path relative to the walk root
+fn relative_path(path: &Path, depth: usize) -> PathBuf {
+    let components: Vec<_> = path.components().collect();
+    components[components.len().saturating_sub(depth)..]
+        .iter()
+        .collect()
+}
+
+/// A config file kind: which file names it covers, the runner type
+/// [`runner_type_hint`] reports for them, and the parsers that read them
+type ConfigKind = (
+    fn(&str) -> bool,
+    RunnerType,
+    fn() -> Vec<Box<dyn Parser + Send>>,
+);
+
+/// Config files picked by name. A file can hold several runners
+/// (package.json with inline Turborepo config). Opt-in runners (generic
+/// scripts, implied lint tasks) aren't included.
+const CONFIG_KINDS: &[ConfigKind] = &[
+    (
+        |name| name == "package.json",
+        RunnerType::Npm,
+        || {
+            vec![
+                Box::new(parsers::PackageJsonParser),
+                Box::new(parsers::TurboJsonParser),
+            ]
+        },
+    ),
+    (
+        |name| matches!(name, "Makefile" | "makefile" | "GNUmakefile"),
+        RunnerType::Make,
+        || vec![Box::new(parsers::MakefileParser)],
+    ),
+    (
+        |name| name == "Cargo.toml",
+        RunnerType::Cargo,
+        || vec![Box::new(parsers::CargoTomlParser)],
+    ),
+    (
+        |name| name == "composer.json",
+        RunnerType::Composer,
+        || vec![Box::new(parsers::ComposerJsonParser)],
+    ),
+    (
+        |name| name == "pubspec.yaml",
+        RunnerType::Flutter,
+        || vec![Box::new(parsers::PubspecYamlParser)],
+    ),
+    (
+        |name| matches!(name, "melos.yaml" | "melos.yml"),
+        RunnerType::Melos,
+        || vec![Box::new(parsers::MelosYamlParser)],
+    ),
+    (
+        |name| name == "turbo.json",
+        RunnerType::Turbo,
+        || vec![Box::new(parsers::TurboJsonParser)],
+    ),
+    (
+        |name| name == "project.json",
+        RunnerType::Nx,
+        || vec![Box::new(parsers::NxParser)],
+    ),
+    (
+        |name| name == "pyproject.toml",
+        RunnerType::Poetry,
+        || vec![Box::new(parsers::PyprojectTomlParser)],
+    ),
+    (
+        |name| matches!(name, "justfile" | "Justfile" | ".justfile"),
+        RunnerType::Just,
+        || vec![Box::new(parsers::JustfileParser)],
+    ),
+    (
+        |name| matches!(name, "deno.json" | "deno.jsonc"),
+        RunnerType::Deno,
+        || vec![Box::new(parsers::DenoJsonParser)],
+    ),
+    (
+        |name| name == "pom.xml",
+        RunnerType::Maven,
+        || vec![Box::new(parsers::PomXmlParser)],
+    ),
+    (
+        |name| matches!(name, "build.gradle" | "build.gradle.kts"),
+        RunnerType::Gradle,
+        || vec![Box::new(parsers::GradleParser)],
+    ),
+    (
+        |name| {
+            [".csproj", ".fsproj", ".vbproj"]
+                .iter()
+                .any(|ext| name.ends_with(ext))
+        },
+        RunnerType::DotNet,
+        || vec![Box::new(parsers::CsprojParser)],
+    ),
+    (
+        |name| matches!(name, "devcontainer.json" | ".devcontainer.json"),
+        RunnerType::Devcontainer,
+        || vec![Box::new(parsers::DevcontainerParser)],
+    ),
+    (
+        |name| name == "Brewfile",
+        RunnerType::Brew,
+        || vec![Box::new(parsers::BrewfileParser)],
+    ),
+    (
+        |name| matches!(name, "Procfile" | "Procfile.dev" | "procfile"),
+        RunnerType::Foreman,
+        || vec![Box::new(parsers::ProcfileParser)],
+    ),
+    (
+        |name| name == ".pre-commit-config.yaml",
+        RunnerType::PreCommit,
+        || vec![Box::new(parsers::PreCommitParser)],
+    ),
+    (
+        |name| name == "toolkit.nu",
+        RunnerType::Nu,
+        || vec![Box::new(parsers::NuParser)],
+    ),
+    (
+        |name| matches!(name, "Rakefile" | "rakefile" | "Rakefile.rb"),
+        RunnerType::Rake,
+        || vec![Box::new(parsers::RakefileParser)],
+    ),
+    (
+        |name| name == "rebar.config",
+        RunnerType::Rebar3,
+        || vec![Box::new(parsers::Rebar3Parser)],
+    ),
+    (
+        |name| name == "maskfile.md",
+        RunnerType::Mask,
+        || vec![Box::new(parsers::MaskfileParser)],
+    ),
+    (
+        |name| name == "dune-project",
+        RunnerType::Dune,
+        || vec![Box::new(parsers::DuneParser)],
+    ),
+    (
+        |name| name == "Snakefile" || name.ends_with(".smk"),
+        RunnerType::Snakemake,
+        || vec![Box::new(parsers::SnakemakeParser)],
+    ),
+];
+
+/// GitHub workflows, which only mean something in a particular folder
+static WORKFLOW: ConfigKind = (
+    |_| true,
+    RunnerType::GithubActions,
+    || vec![Box::new(parsers::GithubActionsParser)],
+);
+
+/// The [`ConfigKind`] of a file, picked by its name or, for workflows, by
+/// its path
+fn config_kind(path: &Path) -> Option<&'static ConfigKind> {
+    if parsers::is_workflow_file(path) {
+        return Some(&WORKFLOW);
+    }
+    let file_name = path.file_name()?.to_string_lossy();
+    CONFIG_KINDS
+        .iter()
+        .find(|(matches, _, _)| matches(&file_name))
+}
+
+/// Parsers for a config file, from its [`ConfigKind`]
+pub(crate) fn parsers_for(path: &Path) -> Vec<Box<dyn Parser + Send>> {
+    config_kind(path).map_or_else(Vec::new, |(_, _, parsers)| parsers())
+}
+
+/// Parse a single config file, picking the parser by file name like a scan
+/// does. Returns `Ok(None)` for file names no parser handles. For a file that
+/// can hold several runners, the first one found is returned.
+pub fn parse_file(path: impl AsRef<Path>) -> ScanResult<Option<TaskRunner>> {
+    let path = path.as_ref();
+    for parser in parsers_for(path) {
+        if let Some(runner) = parser.parse(path)? {
+            return Ok(Some(runner));
+        }
+    }
+    Ok(None)
+}
+
+/// A file for [`ParsePool`] to parse, where to signal that a worker picked
+/// it up, and where to send the result
+type ParseJob = (
+    Box<dyn Parser + Send>,
+    PathBuf,
+    Sender<()>,
+    Sender<ScanResult<Option<TaskRunner>>>,
+);
+
+/// Threads parsing files on behalf of the walker, which gives up waiting
+/// after the timeout. A parser that hangs keeps one worker busy until it
+/// finishes, so a scan never runs more threads than the pool has; once the
+/// pool is dropped, workers exit after their current file.
+struct ParsePool {
+    jobs: Sender<ParseJob>,
+    timeout: Duration,
+}
+
+impl ParsePool {
+    fn new(timeout: Duration, workers: usize) -> Self {
+        let (jobs, queue) = mpsc::channel::<ParseJob>();
+        let queue = Arc::new(Mutex::new(queue));
+        for _ in 0..workers {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || loop {
+                let job = match queue.lock() {
+                    Ok(queue) => queue.recv(),
+                    Err(_) => return,
+                };
+                let Ok((parser, path, started, reply)) = job else {
+                    return;
+                };
+                // The walker already gave up on a file left queued too long
+                if started.send(()).is_err() {
+                    continue;
+                }
+                // A panicking parser drops `reply`; the worker itself carries on
+                let parsed =
+                    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| parser.parse(&path)));
+                if let Ok(result) = parsed {
+                    reply.send(result).ok();
+                }
+            });
+        }
+        Self { jobs, timeout }
+    }
+
+    /// Parse a file on the pool, giving up once it has been parsing for
+    /// longer than the timeout; the result is then discarded. Time spent
+    /// queued behind other files doesn't count, but a file still queued
+    /// after another timeout (every worker stuck on a slow parser) is given
+    /// up on as well, and the pool skips it.
+    fn parse(&self, parser: Box<dyn Parser + Send>, path: &Path) -> ScanResult<Option<TaskRunner>> {
+        let (started, picked_up) = mpsc::channel();
+        let (reply, result) = mpsc::channel();
+        let stopped = || ScanError::ParseError {
+            path: path.to_path_buf(),
+            message: "parse pool stopped".to_string(),
+        };
+        if self
+            .jobs
+            .send((parser, path.to_path_buf(), started, reply))
+            .is_err()
+        {
+            return Err(stopped());
+        }
+
+        match picked_up.recv_timeout(self.timeout) {
+            Ok(()) => {}
+            Err(RecvTimeoutError::Timeout) => {
+                return Err(ScanError::ParseTimeout {
+                    path: path.to_path_buf(),
+                    timeout: self.timeout,
+                })
+            }
+            Err(RecvTimeoutError::Disconnected) => return Err(stopped()),
+        }
+        match result.recv_timeout(self.timeout) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => Err(ScanError::ParseTimeout {
+                path: path.to_path_buf(),
+                timeout: self.timeout,
+            }),
+            Err(RecvTimeoutError::Disconnected) => Err(ScanError::ParseError {
+                path: path.to_path_buf(),
+                message: "parser panicked".to_string(),
+            }),
+        }
+    }
+}
+
+/// Size of a walk's [`ParsePool`]: one worker per core, like the walker
+fn parse_workers() -> usize {
+    thread::available_parallelism().map_or(4, |n| n.get())
 }
 
 #[cfg(test)]
@@ -201,4 +1112,602 @@ mod tests {
         let runners = scan_with_options(dir.path(), options).unwrap();
         assert_eq!(runners.len(), 2);
     }
+
+    #[test]
+    fn test_scan_hidden_config_files() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join(".pre-commit-config.yaml"),
+            "repos:\n  - repo: local\n    hooks:\n      - id: fmt\n",
+        )
+        .unwrap();
+
+        // Other hidden directories are still skipped
+        let hidden_dir = dir.path().join(".cache");
+        fs::create_dir_all(&hidden_dir).unwrap();
+        fs::write(hidden_dir.join("Makefile"), "build:\n\techo build\n").unwrap();
+
+        // .devcontainer is allowed through for devcontainer.json
+        let devcontainer_dir = dir.path().join(".devcontainer");
+        fs::create_dir_all(&devcontainer_dir).unwrap();
+        fs::write(
+            devcontainer_dir.join("devcontainer.json"),
+            r#"{"postCreateCommand": "npm ci"}"#,
+        )
+        .unwrap();
+
+        let mut runner_types: Vec<_> = scan(dir.path())
+            .unwrap()
+            .iter()
+            .map(|r| r.runner_type.display_name())
+            .collect();
+        runner_types.sort();
+        assert_eq!(runner_types, vec!["devcontainer", "pre-commit"]);
+    }
+
+    #[test]
+    fn test_scan_with_walker() {
+        use std::sync::mpsc;
+
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(".taskignore"), "vendor/\n").unwrap();
+        let vendor = dir.path().join("vendor");
+        fs::create_dir_all(&vendor).unwrap();
+        fs::write(vendor.join("Makefile"), "build:\n\techo vendor\n").unwrap();
+        fs::write(dir.path().join("Makefile"), "build:\n\techo root\n").unwrap();
+
+        let mut builder = WalkBuilder::new(dir.path());
+        builder.add_custom_ignore_filename(".taskignore");
+
+        let (tx, rx) = mpsc::channel();
+        let handle = scan_with_walker(builder, ScanOptions::default(), tx);
+        let runners: Vec<TaskRunner> = rx.into_iter().collect();
+        handle.join().unwrap();
+
+        assert_eq!(runners.len(), 1);
+        assert_eq!(runners[0].config_path, dir.path().join("Makefile"));
+    }
+
+    #[test]
+    fn test_dedup_collapses_identical_tasks() {
+        let task = |name: &str| crate::Task {
+            name: name.to_string(),
+            command: format!("mvn {}", name),
+            ..Default::default()
+        };
+        let runner = |path: &str, tasks| TaskRunner {
+            config_path: PathBuf::from(path),
+            runner_type: RunnerType::Maven,
+            tasks,
+            ..Default::default()
+        };
+
+        let runners = dedup_runners(vec![
+            runner("app/pom.xml", vec![task("test"), task("test")]),
+            runner("app/pom.xml", vec![task("test"), task("package")]),
+            runner("lib/pom.xml", vec![task("test")]),
+        ]);
+        let tasks: Vec<_> = runners
+            .iter()
+            .map(|r| {
+                let names: Vec<_> = r.tasks.iter().map(|t| t.name.as_str()).collect();
+                (r.config_path.to_str().unwrap(), names)
+            })
+            .collect();
+        assert_eq!(
+            tasks,
+            vec![
+                ("app/pom.xml", vec!["test", "package"]),
+                ("lib/pom.xml", vec!["test"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_with_progress_counts() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("Makefile"), "build:\n\techo hi\n").unwrap();
+        fs::write(dir.path().join("package.json"), "{ not json").unwrap();
+        fs::write(dir.path().join("notes.txt"), "hello").unwrap();
+
+        let mut reports = Vec::new();
+        let (runners, stats) = scan_with_progress(dir.path(), ScanOptions::default(), |stats| {
+            reports.push(*stats)
+        });
+
+        assert_eq!(runners.len(), 1);
+        assert_eq!(
+            stats,
+            ScanStats {
+                files_considered: 3,
+                files_parsed: 2,
+                parse_errors: 1,
+                runners_found: 1,
+            }
+        );
+        assert_eq!(reports.last(), Some(&stats));
+    }
+
+    #[test]
+    fn test_scan_streaming_items_reports_errors() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("Makefile"), "build:\n\techo hi\n").unwrap();
+        fs::write(dir.path().join("turbo.json"), "{ not json").unwrap();
+
+        let items = |report_errors| {
+            let (tx, rx) = mpsc::channel();
+            let options = ScanOptions {
+                report_errors,
+                ..Default::default()
+            };
+            scan_streaming_items(dir.path().to_path_buf(), options, tx);
+            let (mut runners, mut errors) = (Vec::new(), Vec::new());
+            for item in rx {
+                match item {
+                    ScanItem::Runner(runner) => runners.push(runner.runner_type),
+                    ScanItem::Error(error) => errors.push(error.path().map(Path::to_path_buf)),
+                }
+            }
+            (runners, errors)
+        };
+
+        assert_eq!(items(false), (vec![RunnerType::Make], vec![]));
+        assert_eq!(
+            items(true),
+            (
+                vec![RunnerType::Make],
+                vec![Some(dir.path().join("turbo.json"))]
+            )
+        );
+    }
+
+    #[test]
+    fn test_melos_root_keeps_its_pubspec() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("melos.yaml"),
+            "name: workspace\npackages:\n  - packages/**\nscripts:\n  analyze: dart analyze .\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("pubspec.yaml"),
+            "name: workspace\nenvironment:\n  sdk: ^3.0.0\ndev_dependencies:\n  melos: ^6.0.0\n",
+        )
+        .unwrap();
+
+        let mut runner_types: Vec<_> = scan(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|r| r.runner_type)
+            .collect();
+        runner_types.sort_by_key(|t| t.display_name());
+        assert_eq!(runner_types, [RunnerType::Dart, RunnerType::Melos]);
+    }
+
+    #[test]
+    fn test_only_paths_filter_config_files() {
+        let dir = TempDir::new().unwrap();
+        let package = |rel: &str| {
+            let path = dir.path().join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, r#"{"scripts": {"build": "tsc"}}"#).unwrap();
+        };
+        package("package.json");
+        package("apps/web/package.json");
+        package("apps/api/package.json");
+        package("apps/web/e2e/package.json");
+        package("tooling/eslint-config/package.json");
+        fs::write(
+            dir.path().join("apps/web/Makefile"),
+            "build:\n\tnpm run build\n",
+        )
+        .unwrap();
+
+        let scanned = |only_paths: &[&str]| {
+            let options = ScanOptions {
+                only_paths: only_paths.iter().map(|p| p.to_string()).collect(),
+                ..Default::default()
+            };
+            let mut paths: Vec<_> = scan_with_options(dir.path(), options)
+                .unwrap()
+                .into_iter()
+                .map(|r| {
+                    let rel = r.config_path.strip_prefix(dir.path()).unwrap();
+                    rel.to_string_lossy().replace('\\', "/")
+                })
+                .collect();
+            paths.sort();
+            paths
+        };
+
+        assert_eq!(
+            scanned(&["apps/*/package.json"]),
+            ["apps/api/package.json", "apps/web/package.json"]
+        );
+        assert_eq!(
+            scanned(&["apps/**/package.json", "package.json"]),
+            [
+                "apps/api/package.json",
+                "apps/web/e2e/package.json",
+                "apps/web/package.json",
+                "package.json"
+            ]
+        );
+        assert_eq!(
+            scanned(&["apps/web/*"]),
+            ["apps/web/Makefile", "apps/web/package.json"]
+        );
+        assert_eq!(scanned(&[]).len(), 6);
+    }
+
+    #[test]
+    fn test_scan_report_distinguishes_parse_failures() {
+        let dir = TempDir::new().unwrap();
+        let report = scan_report(dir.path(), ScanOptions::default());
+        assert_eq!(report.files_scanned, 0);
+        assert!(!report.parse_failed());
+
+        fs::write(dir.path().join("package.json"), "{ not json").unwrap();
+        let report = scan_report(dir.path(), ScanOptions::default());
+        assert_eq!(report.files_scanned, 1);
+        assert!(report.runners.is_empty());
+        assert!(matches!(
+            report.errors.as_slice(),
+            [crate::ScanError::ParseError { .. }]
+        ));
+        assert!(report.parse_failed());
+    }
+
+    #[test]
+    fn test_bom_and_non_utf8_configs() {
+        let dir = TempDir::new().unwrap();
+        // Windows editors often save package.json with a BOM
+        let web = dir.path().join("web");
+        fs::create_dir(&web).unwrap();
+        fs::write(
+            web.join("package.json"),
+            "\u{feff}{\"scripts\": {\"dev\": \"vite\"}}",
+        )
+        .unwrap();
+        fs::write(dir.path().join("Makefile"), b"build:\n\techo \xff\xfe\n").unwrap();
+
+        let report = scan_report(dir.path(), ScanOptions::default());
+        assert_eq!(report.runners.len(), 1);
+        assert_eq!(report.runners[0].tasks[0].command, "npm run dev");
+        match report.errors.as_slice() {
+            [crate::ScanError::ParseError { path, message }] => {
+                assert!(path.ends_with("Makefile"));
+                assert!(message.contains("UTF-8"));
+            }
+            errors => panic!("expected one parse error, got {:?}", errors),
+        }
+    }
+
+    #[test]
+    fn test_parse_pool_timeout() {
+        struct SlowParser;
+        impl Parser for SlowParser {
+            fn parse(&self, _path: &Path) -> ScanResult<Option<TaskRunner>> {
+                thread::sleep(Duration::from_millis(500));
+                Ok(None)
+            }
+        }
+
+        let path = Path::new("Makefile");
+        let pool = ParsePool::new(Duration::from_millis(10), 1);
+        let result = pool.parse(Box::new(SlowParser), path);
+        assert!(matches!(result, Err(ScanError::ParseTimeout { .. })));
+
+        let pool = ParsePool::new(Duration::from_secs(5), 1);
+        let result = pool.parse(Box::new(SlowParser), path);
+        assert!(matches!(result, Ok(None)));
+    }
+
+    #[test]
+    fn test_parse_pool_timeout_starts_when_picked_up() {
+        struct SlowParser;
+        impl Parser for SlowParser {
+            fn parse(&self, _path: &Path) -> ScanResult<Option<TaskRunner>> {
+                thread::sleep(Duration::from_millis(100));
+                Ok(None)
+            }
+        }
+
+        // The second file waits for the only worker, then parses in time
+        let pool = ParsePool::new(Duration::from_millis(150), 1);
+        thread::scope(|scope| {
+            let parses: Vec<_> = (0..2)
+                .map(|_| scope.spawn(|| pool.parse(Box::new(SlowParser), Path::new("Makefile"))))
+                .collect();
+            for parse in parses {
+                assert!(matches!(parse.join().unwrap(), Ok(None)));
+            }
+        });
+    }
+
+    #[test]
+    fn test_scan_without_parse_timeout() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("Makefile"), "build:\n\tcc main.c\n").unwrap();
+
+        let options = ScanOptions {
+            parse_timeout: Some(Duration::ZERO),
+            ..Default::default()
+        };
+        let runners = scan_with_options(dir.path(), options).unwrap();
+        assert_eq!(runners.len(), 1);
+        assert_eq!(runners[0].tasks[0].command, "make build");
+    }
+
+    #[test]
+    fn test_parse_pool_bounds_timed_out_parsers() {
+        static RUNNING: AtomicUsize = AtomicUsize::new(0);
+        static MOST_RUNNING: AtomicUsize = AtomicUsize::new(0);
+        struct HangingParser;
+        impl Parser for HangingParser {
+            fn parse(&self, _path: &Path) -> ScanResult<Option<TaskRunner>> {
+                let running = RUNNING.fetch_add(1, Ordering::SeqCst) + 1;
+                MOST_RUNNING.fetch_max(running, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(100));
+                RUNNING.fetch_sub(1, Ordering::SeqCst);
+                Ok(None)
+            }
+        }
+
+        let pool = ParsePool::new(Duration::from_millis(20), 2);
+        for _ in 0..8 {
+            let result = pool.parse(Box::new(HangingParser), Path::new("Makefile"));
+            assert!(matches!(result, Err(ScanError::ParseTimeout { .. })));
+        }
+        // Files are still parsed after the walker gave up on them, but never
+        // more than the pool's two at a time
+        thread::sleep(Duration::from_millis(600));
+        assert_eq!(MOST_RUNNING.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_scan_generic_scripts() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("run.sh"), "#!/bin/sh\necho run\n").unwrap();
+        fs::write(dir.path().join("notes.sh"), "#!/bin/sh\necho notes\n").unwrap();
+
+        // Off by default
+        let runners = scan(dir.path()).unwrap();
+        assert!(runners.is_empty());
+
+        // Default patterns only pick up well-known names
+        let options = ScanOptions {
+            generic_scripts: true,
+            ..Default::default()
+        };
+        let runners = scan_with_options(dir.path(), options).unwrap();
+        assert_eq!(runners.len(), 1);
+        assert_eq!(runners[0].runner_type, crate::RunnerType::Script);
+        assert_eq!(runners[0].tasks[0].command, "./run.sh");
+
+        // Custom patterns replace the defaults
+        let options = ScanOptions {
+            generic_scripts: true,
+            script_patterns: vec!["*.sh".to_string()],
+            ..Default::default()
+        };
+        let runners = scan_with_options(dir.path(), options).unwrap();
+        assert_eq!(runners.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_file() {
+        let dir = TempDir::new().unwrap();
+        let makefile = dir.path().join("Makefile");
+        fs::write(&makefile, "build:\n\tcc main.c\n").unwrap();
+        let notes = dir.path().join("notes.txt");
+        fs::write(&notes, "build: nothing\n").unwrap();
+
+        let runner = parse_file(&makefile).unwrap().unwrap();
+        assert_eq!(runner.runner_type, crate::RunnerType::Make);
+        assert_eq!(runner.tasks[0].name, "build");
+
+        assert!(parse_file(&notes).unwrap().is_none());
+        assert!(parse_file(dir.path().join("Cargo.toml")).is_err());
+    }
+
+    #[test]
+    fn test_scan_runner_filters() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"build": "tsc"}}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("Makefile"), "build:\n\tcc main.c\n").unwrap();
+        let runner_types = |options: ScanOptions| {
+            let mut types: Vec<_> = scan_with_options(dir.path(), options)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.runner_type)
+                .collect();
+            types.sort_by_key(|t| t.display_name());
+            types
+        };
+
+        assert_eq!(
+            runner_types(ScanOptions::default()),
+            vec![RunnerType::Make, RunnerType::Npm]
+        );
+
+        let options = ScanOptions {
+            exclude_runners: vec![RunnerType::Npm],
+            ..Default::default()
+        };
+        assert_eq!(runner_types(options), vec![RunnerType::Make]);
+
+        let options = ScanOptions {
+            include_runners: Some(vec![RunnerType::Npm, RunnerType::Cargo]),
+            ..Default::default()
+        };
+        assert_eq!(runner_types(options), vec![RunnerType::Npm]);
+
+        // Files whose runners are all filtered out aren't parsed
+        let options = ScanOptions {
+            include_runners: Some(vec![RunnerType::Make]),
+            ..Default::default()
+        };
+        assert_eq!(scan_report(dir.path(), options).files_scanned, 1);
+
+        // package.json stays in the scan while any of its family is allowed
+        let options = ScanOptions::default();
+        assert!(options.allows_hint(runner_type_hint(Path::new("package.json")).unwrap()));
+        let options = ScanOptions {
+            include_runners: Some(vec![RunnerType::Pnpm]),
+            ..Default::default()
+        };
+        assert!(options.allows_hint(runner_type_hint(Path::new("package.json")).unwrap()));
+        assert!(!options.allows_hint(runner_type_hint(Path::new("Makefile")).unwrap()));
+        assert_eq!(runner_type_hint(Path::new("README.md")), None);
+    }
+
+    #[test]
+    fn test_scan_max_depth() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("Makefile"), "build:\n\tcc main.c\n").unwrap();
+        let nested = dir.path().join("apps/web");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(
+            nested.join("package.json"),
+            r#"{"scripts": {"dev": "vite"}}"#,
+        )
+        .unwrap();
+        let runner_count = |max_depth| {
+            let options = ScanOptions {
+                max_depth,
+                ..Default::default()
+            };
+            scan_with_options(dir.path(), options).unwrap().len()
+        };
+
+        assert_eq!(runner_count(Some(0)), 1);
+        assert_eq!(runner_count(Some(1)), 1);
+        assert_eq!(runner_count(Some(2)), 2);
+        assert_eq!(runner_count(None), 2);
+    }
+
+    #[test]
+    fn test_shallow_first_order() {
+        let dir = TempDir::new().unwrap();
+        let deep = dir.path().join("a/b/c");
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(deep.join("Makefile"), "deep:\n\ttrue\n").unwrap();
+        fs::create_dir_all(dir.path().join("z")).unwrap();
+        fs::write(dir.path().join("z/Makefile"), "child:\n\ttrue\n").unwrap();
+        fs::write(dir.path().join("Makefile"), "root:\n\ttrue\n").unwrap();
+
+        let options = ScanOptions {
+            shallow_first: true,
+            ..Default::default()
+        };
+        let names: Vec<_> = scan_with_options(dir.path(), options)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.tasks[0].name.clone())
+            .collect();
+        assert_eq!(names.len(), 3);
+        assert_eq!(names[2], "deep");
+
+        // The first pass is limited by max_depth too
+        let options = ScanOptions {
+            shallow_first: true,
+            max_depth: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(scan_with_options(dir.path(), options).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_cancelled_scan_stops() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("Makefile"), "build:\n\tcc main.c\n").unwrap();
+        let options = ScanOptions::default();
+
+        let (tx, rx) = mpsc::channel();
+        let cancelled = AtomicBool::new(true);
+        walk(
+            walk_builder(dir.path(), &options),
+            &options,
+            tx,
+            None,
+            &cancelled,
+        );
+        assert_eq!(rx.into_iter().count(), 0);
+
+        // Cancelling through any clone of the handle stops the scan
+        let handle = ScanHandle::default();
+        handle.clone().cancel();
+        assert!(handle.is_cancelled());
+
+        let (tx, rx) = mpsc::channel();
+        let (thread, handle) = scan_streaming_cancellable(dir.path().to_path_buf(), options, tx);
+        thread.join().unwrap();
+        handle.cancel();
+        assert_eq!(rx.into_iter().count(), 1);
+    }
+
+    #[test]
+    fn test_scan_implied_tasks() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(".eslintrc.json"), "{}").unwrap();
+
+        // Off by default, and the hidden config isn't even visited
+        assert!(scan(dir.path()).unwrap().is_empty());
+
+        let options = ScanOptions {
+            implied_tasks: true,
+            ..Default::default()
+        };
+        let runners = scan_with_options(dir.path(), options).unwrap();
+        assert_eq!(runners.len(), 1);
+        assert_eq!(runners[0].tasks[0].command, "npx eslint .");
+    }
+
+    #[test]
+    fn test_scan_exclude_task_names() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"prebuild": "rm -rf dist", "prepare": "husky", "build": "tsc", "postinstall": "patch-package"}}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("Makefile"), "prepare:\n\techo prepare\n").unwrap();
+
+        let options = ScanOptions {
+            exclude_task_names: vec!["pre*".to_string(), "postinstall".to_string()],
+            ..Default::default()
+        };
+        let runners = scan_with_options(dir.path(), options).unwrap();
+
+        // The Makefile runner has nothing left and is dropped
+        assert_eq!(runners.len(), 1);
+        let names: Vec<_> = runners[0].tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["build"]);
+    }
+
+    #[test]
+    fn test_scan_hides_hooks_unless_included() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("composer.json"),
+            r#"{"scripts": {"post-install-cmd": "php artisan key:generate", "test": "phpunit"}}"#,
+        )
+        .unwrap();
+        let names = |options: ScanOptions| -> Vec<String> {
+            let runners = scan_with_options(dir.path(), options).unwrap();
+            runners[0].tasks.iter().map(|t| t.name.clone()).collect()
+        };
+
+        assert_eq!(names(ScanOptions::default()), vec!["test"]);
+        let options = ScanOptions {
+            include_hooks: true,
+            ..Default::default()
+        };
+        assert_eq!(names(options), vec!["post-install-cmd", "test"]);
+    }
 }
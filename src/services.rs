@@ -0,0 +1,129 @@
+//! Notes on tasks that likely need a running service
+//!
+//! Integration and end-to-end tests usually assume a database or dev server
+//! is already up, and just hang or time out when it isn't. Detection is
+//! heuristic: tasks named like `e2e` / `integration`, and tasks depending on
+//! a task of the same runner that brings up `docker compose`, get a note in
+//! their description.
+
+use crate::{Task, TaskRunner};
+
+/// Name fragments of tasks that usually talk to real services
+const SERVICE_NAME_HINTS: &[&str] = &["e2e", "integration"];
+
+/// Note for tasks matched by name only
+const NAME_NOTE: &str = "may require running services";
+
+/// Add a service note to the description of every task that looks like it
+/// needs one
+pub(crate) fn apply_service_notes(runner: &mut TaskRunner) {
+    let notes: Vec<Option<String>> = runner
+        .tasks
+        .iter()
+        .map(|task| service_note(runner, task))
+        .collect();
+
+    for (task, note) in runner.tasks.iter_mut().zip(notes) {
+        if let Some(note) = note {
+            task.description = Some(match task.description.take() {
+                Some(description) => format!("{} ({})", description, note),
+                None => capitalize(&note),
+            });
+        }
+    }
+}
+
+/// Why `task` probably needs a running service, if it does. A declared
+/// dependency on a task starting compose services beats the name heuristic.
+fn service_note(runner: &TaskRunner, task: &Task) -> Option<String> {
+    let compose_dep = task.metadata.depends_on.iter().find(|dep| {
+        let name = dep.strip_prefix('^').unwrap_or(dep);
+        runner
+            .tasks
+            .iter()
+            .any(|t| t.name == name && t.name != task.name && starts_compose(t))
+    });
+    if let Some(dep) = compose_dep {
+        return Some(format!("requires services from `{}`", dep));
+    }
+
+    let name = task.name.to_ascii_lowercase();
+    SERVICE_NAME_HINTS
+        .iter()
+        .any(|hint| name.contains(hint))
+        .then(|| NAME_NOTE.to_string())
+}
+
+/// Whether the task's command or body runs `docker compose up` /
+/// `docker-compose up`
+fn starts_compose(task: &Task) -> bool {
+    std::iter::once(task.command.as_str())
+        .chain(task.script.as_deref())
+        .any(|text| {
+            text.lines().any(|line| {
+                let words: Vec<&str> = line.split_whitespace().collect();
+                words.windows(2).any(|w| w == ["docker-compose", "up"])
+                    || words.windows(3).any(|w| w == ["docker", "compose", "up"])
+            })
+        })
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RunnerType, TaskMetadata};
+    use std::path::PathBuf;
+
+    fn task(name: &str, script: &str, deps: &[&str]) -> Task {
+        Task {
+            name: name.to_string(),
+            command: format!("make {}", name),
+            script: (!script.is_empty()).then(|| script.to_string()),
+            metadata: TaskMetadata {
+                depends_on: deps.iter().map(|d| d.to_string()).collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_service_notes() {
+        let mut runner = TaskRunner {
+            config_path: PathBuf::from("Makefile"),
+            runner_type: RunnerType::Make,
+            tasks: vec![
+                task("db-up", "docker compose up -d postgres", &[]),
+                task("test-db", "cargo test", &["db-up"]),
+                task("test-e2e", "", &[]),
+                task("build", "cargo build", &[]),
+            ],
+            ..Default::default()
+        };
+        runner.tasks[2].description = Some("Browser tests".to_string());
+
+        apply_service_notes(&mut runner);
+        let descriptions: Vec<_> = runner
+            .tasks
+            .iter()
+            .map(|t| t.description.as_deref())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                None,
+                Some("Requires services from `db-up`"),
+                Some("Browser tests (may require running services)"),
+                None,
+            ]
+        );
+    }
+}
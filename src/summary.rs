@@ -0,0 +1,102 @@
+//! Aggregate counts over scan results
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::{RunnerType, TaskRunner};
+
+/// Totals for a set of scanned runners
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct ScanSummary {
+    /// Number of config files with tasks
+    pub total_runners: usize,
+    /// Number of tasks across all runners
+    pub total_tasks: usize,
+    /// Number of tasks per runner type
+    pub tasks_by_runner: HashMap<RunnerType, usize>,
+    /// Number of distinct folders containing config files
+    pub folders: usize,
+    /// Deepest folder, in levels below the folder all others share
+    /// (0 when everything is in one folder)
+    pub max_depth: usize,
+}
+
+/// Summarize scan results: task counts per runner type, folder count and depth
+pub fn summarize(runners: &[TaskRunner]) -> ScanSummary {
+    let mut summary = ScanSummary {
+        total_runners: runners.len(),
+        ..Default::default()
+    };
+
+    let mut folders: HashSet<&Path> = HashSet::new();
+    for runner in runners {
+        summary.total_tasks += runner.tasks.len();
+        *summary
+            .tasks_by_runner
+            .entry(runner.runner_type)
+            .or_default() += runner.tasks.len();
+        folders.insert(runner.config_path.parent().unwrap_or(Path::new("")));
+    }
+    summary.folders = folders.len();
+
+    if let Some(base) = common_ancestor(folders.iter().copied()) {
+        let base_depth = base.components().count();
+        summary.max_depth = folders
+            .iter()
+            .map(|folder| folder.components().count() - base_depth)
+            .max()
+            .unwrap_or(0);
+    }
+
+    summary
+}
+
+/// Longest path that is an ancestor of (or equal to) every given path
+fn common_ancestor<'a>(mut paths: impl Iterator<Item = &'a Path>) -> Option<PathBuf> {
+    let mut common = paths.next()?.to_path_buf();
+    for path in paths {
+        while !path.starts_with(&common) {
+            if !common.pop() {
+                break;
+            }
+        }
+    }
+    Some(common)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Task;
+
+    fn runner(config_path: &str, runner_type: RunnerType, tasks: usize) -> TaskRunner {
+        TaskRunner {
+            config_path: PathBuf::from(config_path),
+            runner_type,
+            tasks: vec![Task::default(); tasks],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let runners = vec![
+            runner("/repo/package.json", RunnerType::Npm, 3),
+            runner("/repo/Makefile", RunnerType::Make, 2),
+            runner("/repo/apps/web/package.json", RunnerType::Npm, 4),
+        ];
+
+        let summary = summarize(&runners);
+        assert_eq!(summary.total_runners, 3);
+        assert_eq!(summary.total_tasks, 9);
+        assert_eq!(summary.tasks_by_runner[&RunnerType::Npm], 7);
+        assert_eq!(summary.tasks_by_runner[&RunnerType::Make], 2);
+        assert_eq!(summary.folders, 2);
+        assert_eq!(summary.max_depth, 2);
+    }
+
+    #[test]
+    fn test_summarize_empty() {
+        assert_eq!(summarize(&[]), ScanSummary::default());
+    }
+}
@@ -1,17 +1,29 @@
 //! UI thread for terminal interaction
 
 use crate::backend::SharedTasks;
-use crate::messages::{SearchRequest, SearchResponse, SelectedTask};
-use crate::render::render;
+use crate::clipboard;
+use crate::matching::MatchOptions;
+use crate::messages::{FolderState, SearchRequest, SearchResponse, SelectedTask};
+use crate::render::{list_height, render, Theme};
 use crossterm::{
     cursor::{Hide, MoveTo, Show},
-    event::{self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event as CrosstermEvent, KeyCode, KeyEvent,
+        KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use std::io::{self, stdout, Write};
+use std::path::PathBuf;
 use std::sync::mpsc::{Receiver, Sender, TryRecvError};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// Two clicks on the same row within this long count as a double-click
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+/// How long a status-line message (like "copied") stays up
+const FLASH: Duration = Duration::from_secs(1);
 
 /// Application mode
 #[derive(Clone, PartialEq, Debug)]
@@ -38,6 +50,31 @@ pub struct UIState {
     pub edit_buffer: String,
     /// Cursor position in edit buffer
     pub edit_cursor: usize,
+    /// Colors and glyphs used for rendering
+    pub theme: Theme,
+    /// Case/normalization settings for match highlighting
+    pub match_options: MatchOptions,
+    /// Whether the keybinding help overlay is shown
+    pub show_help: bool,
+    /// Show each task's script body instead of its command (Ctrl+T)
+    pub show_script: bool,
+    /// Show the selected task's description and script below the list (Ctrl+P)
+    pub show_preview: bool,
+    /// Tasks marked with Space (by task index), in the order they were
+    /// marked. When any are marked, Enter runs them one after another.
+    pub marked: Vec<(u32, SelectedTask)>,
+    /// Short message shown in place of the status line (e.g. after copying)
+    pub flash: Option<String>,
+    /// Vim-style navigation (j/k, g/G, Ctrl+D/Ctrl+U) while not searching
+    pub vim_keys: bool,
+    /// With `vim_keys`: typing goes to the query (after `/` or `i`) until Esc
+    pub searching: bool,
+    /// Rows the task list has on screen, for half-page moves
+    pub page_lines: usize,
+    /// Collapsed folders in the tree
+    pub folders: FolderState,
+    /// Scan root; when set, folder headers also show absolute paths
+    pub abs_root: Option<PathBuf>,
 }
 
 impl Default for UIState {
@@ -50,19 +87,74 @@ impl Default for UIState {
             scroll_offset: 0,
             edit_buffer: String::new(),
             edit_cursor: 0,
+            theme: Theme::default(),
+            match_options: MatchOptions::default(),
+            show_help: false,
+            show_script: false,
+            show_preview: false,
+            marked: Vec::new(),
+            flash: None,
+            vim_keys: false,
+            searching: false,
+            page_lines: 0,
+            folders: FolderState::default(),
+            abs_root: None,
         }
     }
 }
 
-/// Result from the picker
-pub struct PickerResult {
+/// Startup options for the picker
+#[derive(Clone, Default)]
+pub struct PickerOptions {
+    /// Query to pre-fill the search input with
+    pub query: String,
+    /// Open the best match in Edit mode once scanning completes
+    pub edit: bool,
+    /// Colors and glyphs used for rendering
+    pub theme: Theme,
+    /// Case/normalization settings for the query
+    pub match_options: MatchOptions,
+    /// Start with all folders collapsed
+    pub collapse_folders: bool,
+    /// Show absolute paths next to folder names, relative to this root
+    pub abs_root: Option<PathBuf>,
+    /// Navigate with vim keys; `/` starts searching
+    pub vim_keys: bool,
+}
+
+/// A task picked to run, with the (possibly edited) command to run it with
+pub struct PickedTask {
     pub task: SelectedTask,
     pub command: String,
 }
 
+/// Result from the picker: the tasks to run in order. Just the selected
+/// task unless several were marked.
+pub struct PickerResult {
+    pub tasks: Vec<PickedTask>,
+}
+
+/// Where the selection sits in the folder tree
+#[derive(Clone, Copy, Debug)]
+enum TreePosition<'a> {
+    /// On a task (by task index) in this folder
+    Task { id: u32, folder: &'a str },
+    /// On a collapsed folder's row
+    Collapsed(&'a str),
+}
+
+/// What the user did: a key press, or a click selecting a row
+#[derive(Debug, PartialEq)]
+enum Input {
+    Key(KeyEvent),
+    Select(usize),
+}
+
 /// Result from update
 enum UpdateResult {
     Continue(UIState),
+    /// Copy this text to the clipboard, then continue
+    Copy(UIState, String),
     Exit(Option<PickerResult>),
 }
 
@@ -72,16 +164,24 @@ pub fn run(
     response_rx: Receiver<SearchResponse>,
     tasks: SharedTasks,
     root_name: String,
+    options: PickerOptions,
 ) -> Option<PickerResult> {
     // Setup terminal
     terminal::enable_raw_mode().ok()?;
     let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen, Hide).ok()?;
+    execute!(stdout, EnterAlternateScreen, Hide, EnableMouseCapture).ok()?;
 
-    let result = run_ui_loop(request_tx, response_rx, tasks, &root_name, &mut stdout);
+    let result = run_ui_loop(
+        request_tx,
+        response_rx,
+        tasks,
+        &root_name,
+        options,
+        &mut stdout,
+    );
 
     // Restore terminal
-    execute!(stdout, Show, LeaveAlternateScreen).ok();
+    execute!(stdout, DisableMouseCapture, Show, LeaveAlternateScreen).ok();
     terminal::disable_raw_mode().ok();
 
     result
@@ -93,15 +193,52 @@ fn run_ui_loop(
     response_rx: Receiver<SearchResponse>,
     tasks: SharedTasks,
     root_name: &str,
+    options: PickerOptions,
     stdout: &mut io::Stdout,
 ) -> Option<PickerResult> {
-    let mut state = UIState::default();
+    let mut state = UIState {
+        query_cursor: options.query.chars().count(),
+        query: options.query,
+        theme: options.theme,
+        match_options: options.match_options,
+        folders: FolderState {
+            collapsed_by_default: options.collapse_folders,
+            ..FolderState::default()
+        },
+        abs_root: options.abs_root,
+        vim_keys: options.vim_keys,
+        ..UIState::default()
+    };
     let mut last_response: Option<SearchResponse> = None;
     let mut needs_search = true;
+    // Deferred until scanning is done so the best match doesn't change under us
+    let mut pending_edit = options.edit;
+    // Task to keep selected while results for a new query stream in
+    let mut follow_task: Option<u32> = None;
+    // Only redraw when the state, results or terminal size changed; idle
+    // ticks and unchanged responses while scanning leave the screen as is
+    let mut needs_render = true;
+    let mut last_size = None;
+    // Screen rows of the last render, for mapping clicks to tasks
+    let mut rows: Vec<Option<usize>> = Vec::new();
+    let mut last_click: Option<(usize, Instant)> = None;
+    // When the status-line message goes away
+    let mut flash_until: Option<Instant> = None;
 
     loop {
-        let (_, height) = terminal::size().unwrap_or((80, 24));
-        let viewport_height = (height as usize).saturating_sub(8);
+        let size = terminal::size().unwrap_or((80, 24));
+        let (width, height) = size;
+        let viewport_height = list_height(&state, height as usize);
+        state.page_lines = viewport_height;
+        if last_size != Some(size) {
+            last_size = Some(size);
+            needs_render = true;
+        }
+        if flash_until.is_some_and(|until| Instant::now() >= until) {
+            flash_until = None;
+            state.flash = None;
+            needs_render = true;
+        }
 
         // Send search request if needed
         if needs_search {
@@ -111,6 +248,8 @@ fn run_ui_loop(
                 limit: viewport_height * 2,
                 viewport_lines: viewport_height,
                 selected_index: state.selected_index,
+                follow_task,
+                folders: state.folders.clone(),
             };
             if request_tx.send(request).is_err() {
                 return None;
@@ -122,6 +261,15 @@ fn run_ui_loop(
         match response_rx.try_recv() {
             Ok(response) => {
                 let task_count = response.matched_tasks;
+                let position = (state.selected_index, state.scroll_offset);
+
+                if follow_task.is_some() {
+                    state.selected_index = response.selected_index;
+                    // Results are final once scanning is done
+                    if response.scanning_done {
+                        follow_task = None;
+                    }
+                }
 
                 // Update selection to stay within bounds
                 if task_count > 0 {
@@ -136,8 +284,20 @@ fn run_ui_loop(
                 // If scanning is still in progress, request another update
                 if !response.scanning_done {
                     needs_search = true;
+                } else if pending_edit {
+                    pending_edit = false;
+                    let relative_idx = state.selected_index.saturating_sub(response.offset);
+                    if let Some(task) = get_selected_task(&tasks, &response, relative_idx) {
+                        state = enter_edit_mode(state, &task);
+                        needs_render = true;
+                    }
                 }
 
+                if last_response.as_ref() != Some(&response)
+                    || position != (state.selected_index, state.scroll_offset)
+                {
+                    needs_render = true;
+                }
                 last_response = Some(response);
             }
             Err(TryRecvError::Empty) => {}
@@ -146,55 +306,123 @@ fn run_ui_loop(
             }
         }
 
-        // Poll for keyboard input
+        // Poll for keyboard and mouse input
         if event::poll(Duration::from_millis(50)).unwrap_or(false) {
-            if let Ok(CrosstermEvent::Key(key)) = event::read() {
+            let input = match event::read() {
+                Ok(CrosstermEvent::Key(key)) => Some(Input::Key(key)),
+                Ok(CrosstermEvent::Mouse(mouse)) => {
+                    mouse_input(mouse, &rows, &mut last_click, Instant::now())
+                }
+                _ => None,
+            };
+            if let Some(input) = input {
+                // Any input means the user has taken over
+                pending_edit = false;
+                needs_render = true;
+
                 let task_count = last_response.as_ref().map(|r| r.matched_tasks).unwrap_or(0);
 
                 let selected_task = last_response.as_ref().and_then(|r| {
                     let relative_idx = state.selected_index.saturating_sub(r.offset);
-                    get_selected_task(&tasks, &r.matched_indices, relative_idx)
+                    get_selected_task(&tasks, r, relative_idx)
                 });
+                let selected_id = last_response.as_ref().and_then(|r| {
+                    let relative_idx = state.selected_index.saturating_sub(r.offset);
+                    r.matched_indices.get(relative_idx).copied()
+                });
+                let tasks_guard = tasks.read().unwrap();
+                let position = last_response
+                    .as_ref()
+                    .zip(selected_id)
+                    .and_then(|(r, id)| match r.collapsed.get(&id) {
+                        Some(folder) => Some(TreePosition::Collapsed(folder)),
+                        None => tasks_guard.get(id as usize).map(|task| TreePosition::Task {
+                            id,
+                            folder: &task.folder,
+                        }),
+                    });
 
-                match handle_key(state.clone(), key, selected_task.as_ref(), task_count) {
+                let update = match input {
+                    Input::Key(key) => handle_key(
+                        state.clone(),
+                        key,
+                        selected_task.as_ref(),
+                        position,
+                        task_count,
+                    ),
+                    Input::Select(index) => UpdateResult::Continue(UIState {
+                        mode: Mode::Select,
+                        selected_index: index,
+                        ..state.clone()
+                    }),
+                };
+                drop(tasks_guard);
+                match update {
                     UpdateResult::Continue(new_state) => {
                         let query_changed = new_state.query != state.query;
+                        let selection_moved = new_state.selected_index != state.selected_index;
+                        let folders_changed = new_state.folders != state.folders;
                         state = new_state;
 
                         if query_changed {
+                            // Keep the current task selected if it still matches
+                            follow_task = follow_task.or(selected_id);
                             state.selected_index = 0;
                             state.scroll_offset = 0;
+                        } else if selection_moved {
+                            // User moved the selection themselves
+                            follow_task = None;
+                        } else if folders_changed {
+                            // Stay on the folder (or its first task) as rows come and go
+                            follow_task = selected_id;
                         }
                         // Request new data - backend will calculate correct scroll
                         needs_search = true;
                     }
+                    UpdateResult::Copy(new_state, text) => {
+                        clipboard::copy(&text);
+                        state = UIState {
+                            flash: Some("copied".to_string()),
+                            ..new_state
+                        };
+                        flash_until = Some(Instant::now() + FLASH);
+                    }
                     UpdateResult::Exit(result) => return result,
                 }
             }
         }
 
         // Render current state
-        if let Some(ref response) = last_response {
+        if let Some(response) = last_response.as_ref().filter(|_| needs_render) {
             execute!(stdout, MoveTo(0, 0)).ok();
-            let result = render(&state, response, &tasks, root_name, height as usize);
+            let result = render(
+                &state,
+                response,
+                &tasks,
+                root_name,
+                width as usize,
+                height as usize,
+            );
             write!(stdout, "{}", result.output).ok();
+            rows = result.rows;
             stdout.flush().ok();
+            needs_render = false;
         }
     }
 }
 
-/// Get selected task from shared storage
+/// Get selected task from shared storage (None on a collapsed folder's row)
 fn get_selected_task(
     tasks: &SharedTasks,
-    matched_indices: &[u32],
+    response: &SearchResponse,
     selected_index: usize,
 ) -> Option<SelectedTask> {
-    if selected_index >= matched_indices.len() {
+    let idx = *response.matched_indices.get(selected_index)?;
+    if response.collapsed.contains_key(&idx) {
         return None;
     }
-    let idx = matched_indices[selected_index] as usize;
     let tasks = tasks.read().ok()?;
-    tasks.get(idx).map(SelectedTask::from)
+    tasks.get(idx as usize).map(SelectedTask::from)
 }
 
 /// Handle a key event
@@ -202,14 +430,78 @@ fn handle_key(
     state: UIState,
     key: KeyEvent,
     selected_task: Option<&SelectedTask>,
+    position: Option<TreePosition>,
     task_count: usize,
 ) -> UpdateResult {
+    if state.vim_keys && !state.searching && !state.show_help && state.mode == Mode::Select {
+        if let Some(state) = vim_key(&state, key, task_count) {
+            return UpdateResult::Continue(state);
+        }
+    }
+
     match key.code {
         // Ctrl+C always exits
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             UpdateResult::Exit(None)
         }
 
+        // Any other key dismisses the help overlay
+        _ if state.show_help => UpdateResult::Continue(UIState {
+            show_help: false,
+            ..state
+        }),
+
+        // Ctrl+T: toggle between commands and script bodies
+        KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            UpdateResult::Continue(UIState {
+                show_script: !state.show_script,
+                ..state
+            })
+        }
+
+        // Ctrl+P: toggle the preview pane
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            UpdateResult::Continue(UIState {
+                show_preview: !state.show_preview,
+                ..state
+            })
+        }
+
+        // Ctrl+Y: copy the selected task's command (the edited one in Edit/Expanded mode)
+        KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            let text = match state.mode {
+                Mode::Edit | Mode::Expanded => Some(state.edit_buffer.clone()),
+                Mode::Select => selected_task.map(|task| task.command.clone()),
+            };
+            match text {
+                Some(text) => UpdateResult::Copy(state, text),
+                None => UpdateResult::Continue(state),
+            }
+        }
+
+        // Space: mark/unmark the selected task to run several in a row.
+        // While searching Space is part of the query, so it takes Ctrl+Space.
+        KeyCode::Char(' ')
+            if matches!(state.mode, Mode::Select)
+                && (state.query.is_empty() || key.modifiers.contains(KeyModifiers::CONTROL)) =>
+        {
+            match (position, selected_task) {
+                (Some(TreePosition::Task { id, .. }), Some(task)) => {
+                    UpdateResult::Continue(toggle_mark(state, id, task))
+                }
+                _ => UpdateResult::Continue(state),
+            }
+        }
+
+        // ?: show keybinding help (only in Select mode with an empty query,
+        // elsewhere it's text)
+        KeyCode::Char('?') if matches!(state.mode, Mode::Select) && state.query.is_empty() => {
+            UpdateResult::Continue(UIState {
+                show_help: true,
+                ..state
+            })
+        }
+
         // Escape: go back one step (Expanded → Edit → Select → Exit)
         KeyCode::Esc => match state.mode {
             Mode::Expanded => {
@@ -226,36 +518,53 @@ fn handle_key(
                 mode: Mode::Select,
                 ..state
             }),
+            // With vim keys Esc ends searching first
+            Mode::Select if state.vim_keys && state.searching => UpdateResult::Continue(UIState {
+                searching: false,
+                ..state
+            }),
             Mode::Select => UpdateResult::Exit(None),
         },
 
-        // Run selected task
-        KeyCode::Enter => {
-            if let Some(task) = selected_task {
-                let command = if matches!(state.mode, Mode::Edit | Mode::Expanded) {
-                    state.edit_buffer.clone()
-                } else {
-                    task.command.clone()
-                };
-                return UpdateResult::Exit(Some(PickerResult {
-                    task: task.clone(),
-                    command,
-                }));
+        // Enter/→ on a collapsed folder expands it
+        KeyCode::Enter | KeyCode::Right if matches!(state.mode, Mode::Select) => match position {
+            Some(TreePosition::Collapsed(folder)) => {
+                let mut folders = state.folders.clone();
+                folders.set_collapsed(folder, false);
+                UpdateResult::Continue(UIState { folders, ..state })
+            }
+            _ if key.code == KeyCode::Enter => run_selected(state, selected_task),
+            _ => edit_query(state, key),
+        },
+
+        // ←: collapse the selected task's folder, or the folder around a
+        // collapsed one. Only while browsing, with a query it moves the cursor.
+        KeyCode::Left if matches!(state.mode, Mode::Select) && state.query.is_empty() => {
+            let folder = match position {
+                Some(TreePosition::Task { folder, .. }) => Some(folder),
+                Some(TreePosition::Collapsed(folder)) => {
+                    folder.rsplit_once('/').map(|(parent, _)| parent)
+                }
+                None => None,
+            };
+            match folder.filter(|folder| *folder != ".") {
+                Some(folder) => {
+                    let mut folders = state.folders.clone();
+                    folders.set_collapsed(folder, true);
+                    UpdateResult::Continue(UIState { folders, ..state })
+                }
+                None => UpdateResult::Continue(state),
             }
-            UpdateResult::Continue(state)
         }
 
+        // Run selected task
+        KeyCode::Enter => run_selected(state, selected_task),
+
         // Tab: cycle through modes (Select → Edit → Expanded → Select)
         KeyCode::Tab => match state.mode {
             Mode::Select => {
                 if let Some(task) = selected_task {
-                    let command = task.command.clone();
-                    UpdateResult::Continue(UIState {
-                        mode: Mode::Edit,
-                        edit_buffer: command.clone(),
-                        edit_cursor: command.len(),
-                        ..state
-                    })
+                    UpdateResult::Continue(enter_edit_mode(state, task))
                 } else {
                     UpdateResult::Continue(state)
                 }
@@ -305,29 +614,155 @@ fn handle_key(
                     ..state
                 })
             } else {
-                let (new_query, new_cursor) =
-                    apply_input_event(&state.query, state.query_cursor, key);
-                let query_changed = new_query != state.query;
-                UpdateResult::Continue(UIState {
-                    query: new_query,
-                    query_cursor: new_cursor,
-                    selected_index: if query_changed {
-                        0
-                    } else {
-                        state.selected_index
-                    },
-                    scroll_offset: if query_changed {
-                        0
-                    } else {
-                        state.scroll_offset
-                    },
-                    ..state
-                })
+                edit_query(state, key)
             }
         }
     }
 }
 
+/// Turn a mouse event into input: the wheel moves the selection like ↑/↓,
+/// a left click selects the task on that row, and a second click on the
+/// same row within [`DOUBLE_CLICK`] runs it like Enter. `rows` maps screen
+/// rows to selectable indices, as rendered.
+fn mouse_input(
+    mouse: MouseEvent,
+    rows: &[Option<usize>],
+    last_click: &mut Option<(usize, Instant)>,
+    now: Instant,
+) -> Option<Input> {
+    let key = |code| Some(Input::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+    match mouse.kind {
+        MouseEventKind::ScrollUp => key(KeyCode::Up),
+        MouseEventKind::ScrollDown => key(KeyCode::Down),
+        MouseEventKind::Down(MouseButton::Left) => {
+            let index = (*rows.get(mouse.row as usize)?)?;
+            match last_click.take() {
+                Some((clicked, at)) if clicked == index && now - at <= DOUBLE_CLICK => {
+                    key(KeyCode::Enter)
+                }
+                _ => {
+                    *last_click = Some((index, now));
+                    Some(Input::Select(index))
+                }
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Add the task to the marked tasks, or take it out if it's already there
+fn toggle_mark(mut state: UIState, id: u32, task: &SelectedTask) -> UIState {
+    match state.marked.iter().position(|(marked, _)| *marked == id) {
+        Some(pos) => {
+            state.marked.remove(pos);
+        }
+        None => state.marked.push((id, task.clone())),
+    }
+    state
+}
+
+/// Run the marked tasks in marking order, else the selected task (with the
+/// edited command in Edit/Expanded mode, where marks don't apply)
+fn run_selected(state: UIState, selected_task: Option<&SelectedTask>) -> UpdateResult {
+    if matches!(state.mode, Mode::Select) && !state.marked.is_empty() {
+        let tasks = state
+            .marked
+            .into_iter()
+            .map(|(_, task)| PickedTask {
+                command: task.command.clone(),
+                task,
+            })
+            .collect();
+        return UpdateResult::Exit(Some(PickerResult { tasks }));
+    }
+
+    let Some(task) = selected_task else {
+        return UpdateResult::Continue(state);
+    };
+    let command = if matches!(state.mode, Mode::Edit | Mode::Expanded) {
+        state.edit_buffer
+    } else {
+        task.command.clone()
+    };
+    UpdateResult::Exit(Some(PickerResult {
+        tasks: vec![PickedTask {
+            task: task.clone(),
+            command,
+        }],
+    }))
+}
+
+/// Apply a key to the search query, resetting the selection if it changed
+fn edit_query(state: UIState, key: KeyEvent) -> UpdateResult {
+    let (new_query, new_cursor) = apply_input_event(&state.query, state.query_cursor, key);
+    let query_changed = new_query != state.query;
+    UpdateResult::Continue(UIState {
+        query: new_query,
+        query_cursor: new_cursor,
+        selected_index: if query_changed {
+            0
+        } else {
+            state.selected_index
+        },
+        scroll_offset: if query_changed {
+            0
+        } else {
+            state.scroll_offset
+        },
+        ..state
+    })
+}
+
+/// Switch to Edit mode with the task's command in the edit buffer
+fn enter_edit_mode(state: UIState, task: &SelectedTask) -> UIState {
+    let command = task.command.clone();
+    UIState {
+        mode: Mode::Edit,
+        edit_cursor: command.len(),
+        edit_buffer: command,
+        ..state
+    }
+}
+
+/// Apply a vim navigation key while not searching. Returns None for keys
+/// that aren't vim's (arrows, Enter, Tab, Space, `?` ...); other letters are
+/// swallowed rather than typed into the query.
+fn vim_key(state: &UIState, key: KeyEvent, task_count: usize) -> Option<UIState> {
+    let half_page = (state.page_lines / 2).max(1);
+    let last = task_count.saturating_sub(1);
+    let selected_index = match key.code {
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            (state.selected_index + half_page).min(last)
+        }
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.selected_index.saturating_sub(half_page)
+        }
+        _ if key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
+            return None
+        }
+        KeyCode::Char('j') => move_selection(state.selected_index, task_count, 1),
+        KeyCode::Char('k') => move_selection(state.selected_index, task_count, -1),
+        KeyCode::Char('g') => 0,
+        KeyCode::Char('G') => last,
+        KeyCode::Char('/' | 'i') => {
+            return Some(UIState {
+                searching: true,
+                ..state.clone()
+            })
+        }
+        KeyCode::Char(' ' | '?') => return None,
+        KeyCode::Char(_) => state.selected_index,
+        _ => return None,
+    };
+    Some(UIState {
+        selected_index,
+        ..state.clone()
+    })
+}
+
 /// Move selection with wrap-around
 fn move_selection(current: usize, total: usize, delta: isize) -> usize {
     if total == 0 {
@@ -412,6 +847,348 @@ fn apply_input_event(buffer: &str, cursor: usize, key: KeyEvent) -> (String, usi
 mod tests {
     use super::*;
 
+    fn test_task(command: &str) -> SelectedTask {
+        SelectedTask {
+            command: command.to_string(),
+            script: None,
+            runner_type: crate::RunnerType::Npm,
+            config_path: std::path::PathBuf::from("/test/package.json"),
+        }
+    }
+
+    #[test]
+    fn test_enter_edit_mode() {
+        let state = UIState {
+            query: "build".to_string(),
+            query_cursor: 5,
+            ..UIState::default()
+        };
+        let state = enter_edit_mode(state, &test_task("npm run build"));
+        assert_eq!(state.mode, Mode::Edit);
+        assert_eq!(state.edit_buffer, "npm run build");
+        assert_eq!(state.edit_cursor, 13);
+        // Query is kept so navigating away still shows the filtered list
+        assert_eq!(state.query, "build");
+    }
+
+    fn press(state: UIState, code: KeyCode) -> UIState {
+        let task = test_task("npm run build");
+        match handle_key(
+            state,
+            KeyEvent::new(code, KeyModifiers::NONE),
+            Some(&task),
+            None,
+            3,
+        ) {
+            UpdateResult::Continue(state) => state,
+            _ => panic!("expected to stay in the picker"),
+        }
+    }
+
+    #[test]
+    fn test_help_overlay_toggle() {
+        let state = press(UIState::default(), KeyCode::Char('?'));
+        assert!(state.show_help);
+        assert_eq!(state.query, "");
+
+        // Any key dismisses the overlay without acting on it
+        let state = press(state, KeyCode::Down);
+        assert!(!state.show_help);
+        assert_eq!(state.selected_index, 0);
+
+        // In Edit mode `?` is just text
+        let state = enter_edit_mode(UIState::default(), &test_task("npm run build"));
+        let state = press(state, KeyCode::Char('?'));
+        assert!(!state.show_help);
+        assert_eq!(state.edit_buffer, "npm run build?");
+
+        // So is it while searching
+        let state = UIState {
+            query: "web".to_string(),
+            query_cursor: 3,
+            ..UIState::default()
+        };
+        let state = press(state, KeyCode::Char('?'));
+        assert!(!state.show_help);
+        assert_eq!(state.query, "web?");
+    }
+
+    #[test]
+    fn test_toggle_script_display() {
+        let ctrl_t = |state| match handle_key(
+            state,
+            KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL),
+            None,
+            None,
+            3,
+        ) {
+            UpdateResult::Continue(state) => state,
+            _ => panic!("Ctrl+T should not exit"),
+        };
+
+        let state = ctrl_t(UIState::default());
+        assert!(state.show_script);
+        assert_eq!(state.query, "");
+        assert!(!ctrl_t(state).show_script);
+    }
+
+    #[test]
+    fn test_marked_tasks_run_in_order() {
+        let space = |state, id: u32, command: &str| {
+            let task = test_task(command);
+            match handle_key(
+                state,
+                KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE),
+                Some(&task),
+                Some(TreePosition::Task { id, folder: "." }),
+                3,
+            ) {
+                UpdateResult::Continue(state) => state,
+                _ => panic!("Space should not exit"),
+            }
+        };
+        let commands = |state| match run_selected(state, Some(&test_task("npm run lint"))) {
+            UpdateResult::Exit(Some(result)) => result
+                .tasks
+                .into_iter()
+                .map(|picked| picked.command)
+                .collect::<Vec<_>>(),
+            _ => panic!("expected tasks to run"),
+        };
+
+        // Nothing marked: Enter runs the selected task
+        assert_eq!(commands(UIState::default()), ["npm run lint"]);
+
+        let state = space(UIState::default(), 2, "npm run build");
+        let state = space(state, 0, "npm test");
+        let state = space(state, 1, "npm publish");
+        let state = space(state, 0, "npm test");
+        assert_eq!(commands(state.clone()), ["npm run build", "npm publish"]);
+
+        // While searching Space is query text
+        let searching = UIState {
+            query: "npm".to_string(),
+            query_cursor: 3,
+            ..state
+        };
+        let searching = space(searching, 0, "npm test");
+        assert_eq!(searching.query, "npm ");
+        assert_eq!(searching.marked.len(), 2);
+    }
+
+    #[test]
+    fn test_copy_command() {
+        let ctrl_y = |state| {
+            let task = test_task("npm run build");
+            match handle_key(
+                state,
+                KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL),
+                Some(&task),
+                None,
+                3,
+            ) {
+                UpdateResult::Copy(_, text) => text,
+                _ => panic!("Ctrl+Y should copy"),
+            }
+        };
+
+        assert_eq!(ctrl_y(UIState::default()), "npm run build");
+        let state = enter_edit_mode(UIState::default(), &test_task("npm run build"));
+        let state = press(state, KeyCode::Char('!'));
+        assert_eq!(ctrl_y(state), "npm run build!");
+    }
+
+    #[test]
+    fn test_vim_keys() {
+        let key = |state, code, modifiers| match handle_key(
+            state,
+            KeyEvent::new(code, modifiers),
+            None,
+            None,
+            30,
+        ) {
+            UpdateResult::Continue(state) => state,
+            _ => panic!("expected to stay in the picker"),
+        };
+        let plain = |state, c| key(state, KeyCode::Char(c), KeyModifiers::NONE);
+
+        // Off by default: letters are query input
+        let state = plain(UIState::default(), 'j');
+        assert_eq!((state.query.as_str(), state.selected_index), ("j", 0));
+
+        let normal = UIState {
+            vim_keys: true,
+            page_lines: 10,
+            ..UIState::default()
+        };
+        let state = plain(normal.clone(), 'j');
+        assert_eq!((state.query.as_str(), state.selected_index), ("", 1));
+        assert_eq!(plain(state, 'k').selected_index, 0);
+        assert_eq!(plain(normal.clone(), 'k').selected_index, 29);
+        assert_eq!(plain(normal.clone(), 'G').selected_index, 29);
+        assert_eq!(plain(plain(normal.clone(), 'G'), 'g').selected_index, 0);
+        let state = key(normal.clone(), KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(state.selected_index, 5);
+        let state = key(state, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(state.selected_index, 0);
+        // Other letters are ignored, `?` still opens help
+        assert_eq!(plain(normal.clone(), 'x').query, "");
+        assert!(plain(normal.clone(), '?').show_help);
+
+        // `/` starts searching: letters type again until Esc
+        let state = plain(normal, '/');
+        assert!(state.searching);
+        let state = plain(plain(state, 'j'), 'k');
+        assert_eq!((state.query.as_str(), state.selected_index), ("jk", 0));
+        let state = key(state, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!state.searching);
+        assert_eq!(state.query, "jk");
+        assert_eq!(plain(state, 'j').selected_index, 1);
+    }
+
+    #[test]
+    fn test_toggle_preview() {
+        let ctrl_p = |state| match handle_key(
+            state,
+            KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL),
+            None,
+            None,
+            3,
+        ) {
+            UpdateResult::Continue(state) => state,
+            _ => panic!("Ctrl+P should not exit"),
+        };
+
+        let state = ctrl_p(UIState::default());
+        assert!(state.show_preview);
+        assert_eq!(state.query, "");
+        assert!(!ctrl_p(state).show_preview);
+    }
+
+    #[test]
+    fn test_navigate_away_from_edit() {
+        let state = enter_edit_mode(UIState::default(), &test_task("npm run build"));
+        let task = test_task("npm run build");
+        match handle_key(
+            state,
+            KeyEvent::new(KeyCode::Down, KeyModifiers::NONE),
+            Some(&task),
+            None,
+            3,
+        ) {
+            UpdateResult::Continue(state) => {
+                assert_eq!(state.mode, Mode::Select);
+                assert_eq!(state.selected_index, 1);
+            }
+            _ => panic!("navigation should not exit"),
+        }
+    }
+
+    #[test]
+    fn test_expand_and_collapse_folders() {
+        let key = |state: UIState, code, position| match handle_key(
+            state,
+            KeyEvent::new(code, KeyModifiers::NONE),
+            None,
+            position,
+            3,
+        ) {
+            UpdateResult::Continue(state) => state,
+            _ => panic!("folder keys should not exit"),
+        };
+        let state = UIState {
+            folders: FolderState {
+                collapsed_by_default: true,
+                ..FolderState::default()
+            },
+            ..UIState::default()
+        };
+
+        let state = key(state, KeyCode::Right, Some(TreePosition::Collapsed("apps")));
+        assert!(!state.folders.is_collapsed("apps"));
+        let state = key(
+            state,
+            KeyCode::Enter,
+            Some(TreePosition::Collapsed("apps/web")),
+        );
+        assert!(!state.folders.is_collapsed("apps/web"));
+
+        // ← on a task collapses its folder, on a collapsed row its parent
+        let state = key(
+            state,
+            KeyCode::Left,
+            Some(TreePosition::Task {
+                id: 0,
+                folder: "apps/web",
+            }),
+        );
+        assert!(state.folders.is_collapsed("apps/web"));
+        let state = key(
+            state,
+            KeyCode::Left,
+            Some(TreePosition::Collapsed("apps/web")),
+        );
+        assert!(state.folders.is_collapsed("apps"));
+        assert!(state.folders.toggled.is_empty());
+
+        // The root never collapses
+        let state = key(
+            state,
+            KeyCode::Left,
+            Some(TreePosition::Task { id: 0, folder: "." }),
+        );
+        assert!(!state.folders.is_collapsed("."));
+    }
+
+    #[test]
+    fn test_mouse_input() {
+        let mouse = |kind, row| MouseEvent {
+            kind,
+            column: 10,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        let click = |row| mouse(MouseEventKind::Down(MouseButton::Left), row);
+        let key = |code| Some(Input::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+        // Header rows, a folder header, then two tasks
+        let rows = [None, None, None, None, None, None, Some(3), Some(4)];
+        let start = Instant::now();
+        let mut last_click = None;
+
+        assert_eq!(
+            mouse_input(
+                mouse(MouseEventKind::ScrollDown, 0),
+                &rows,
+                &mut last_click,
+                start
+            ),
+            key(KeyCode::Down)
+        );
+        assert_eq!(mouse_input(click(2), &rows, &mut last_click, start), None);
+        assert_eq!(mouse_input(click(20), &rows, &mut last_click, start), None);
+
+        assert_eq!(
+            mouse_input(click(6), &rows, &mut last_click, start),
+            Some(Input::Select(3))
+        );
+        // A second click on another row is a new single click
+        assert_eq!(
+            mouse_input(click(7), &rows, &mut last_click, start),
+            Some(Input::Select(4))
+        );
+        assert_eq!(
+            mouse_input(click(7), &rows, &mut last_click, start + DOUBLE_CLICK / 2),
+            key(KeyCode::Enter)
+        );
+        // Too slow for a double-click
+        let later = start + DOUBLE_CLICK * 4;
+        mouse_input(click(7), &rows, &mut last_click, later);
+        assert_eq!(
+            mouse_input(click(7), &rows, &mut last_click, later + DOUBLE_CLICK * 2),
+            Some(Input::Select(4))
+        );
+    }
+
     #[test]
     fn test_move_selection_wrap() {
         assert_eq!(move_selection(0, 5, -1), 4);
@@ -0,0 +1,112 @@
+//! Opt-in package manager versions for JSON output (`--package-manager-version`)
+
+use std::collections::HashMap;
+use std::fs;
+use std::process::{Command, Stdio};
+
+use serde_json::Value;
+use task_runner_detector::{RunnerType, TaskRunner};
+
+/// Resolves which npm/yarn/pnpm/bun version a runner's tasks will run with
+#[derive(Default)]
+pub struct PackageManagerVersions {
+    /// Fall back to `<binary> --version` when package.json doesn't pin one
+    probe: bool,
+    /// Installed versions by binary (None if it couldn't be run)
+    installed: HashMap<&'static str, Option<String>>,
+}
+
+impl PackageManagerVersions {
+    pub fn new(probe: bool) -> Self {
+        Self {
+            probe,
+            ..Self::default()
+        }
+    }
+
+    /// The version a runner resolves to, if it's a Node package manager
+    pub fn resolve(&mut self, runner: &TaskRunner) -> Option<String> {
+        if !matches!(
+            runner.runner_type,
+            RunnerType::Npm | RunnerType::Yarn | RunnerType::Pnpm | RunnerType::Bun
+        ) {
+            return None;
+        }
+
+        let binary = runner.runner_type.binary();
+        pinned_version(runner, binary).or_else(|| {
+            if !self.probe {
+                return None;
+            }
+            self.installed
+                .entry(binary)
+                .or_insert_with(|| installed_version(binary))
+                .clone()
+        })
+    }
+
+    /// Add `package_manager_version` to a serialized runner when it resolves
+    pub fn annotate(&mut self, runner: &TaskRunner, value: &mut Value) {
+        if let (Some(version), Some(object)) = (self.resolve(runner), value.as_object_mut()) {
+            object.insert("package_manager_version".to_string(), version.into());
+        }
+    }
+}
+
+/// Version pinned by package.json's `packageManager` field (`pnpm@8.15.4+sha512...`),
+/// if it names this runner's binary
+fn pinned_version(runner: &TaskRunner, binary: &str) -> Option<String> {
+    let content = fs::read_to_string(&runner.config_path).ok()?;
+    let package: Value = serde_json::from_str(&content).ok()?;
+    let (name, version) = package.get("packageManager")?.as_str()?.split_once('@')?;
+    let version = version.split('+').next().unwrap_or(version);
+    (name == binary && !version.is_empty()).then(|| version.to_string())
+}
+
+/// Ask the installed binary for its version
+fn installed_version(binary: &str) -> Option<String> {
+    let output = Command::new(binary)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !version.is_empty()).then_some(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pinned_package_manager_version() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("package.json");
+        let runner = |runner_type| TaskRunner {
+            config_path: path.clone(),
+            runner_type,
+            tasks: Vec::new(),
+            ..Default::default()
+        };
+        fs::write(
+            &path,
+            r#"{"packageManager": "pnpm@8.15.4+sha512.0bd3a9be"}"#,
+        )
+        .unwrap();
+
+        let mut versions = PackageManagerVersions::default();
+        assert_eq!(
+            versions.resolve(&runner(RunnerType::Pnpm)).as_deref(),
+            Some("8.15.4")
+        );
+        // Pinned for another package manager, and not probing
+        assert_eq!(versions.resolve(&runner(RunnerType::Npm)), None);
+        assert_eq!(versions.resolve(&runner(RunnerType::Make)), None);
+
+        let mut value = serde_json::to_value(runner(RunnerType::Pnpm)).unwrap();
+        versions.annotate(&runner(RunnerType::Pnpm), &mut value);
+        assert_eq!(value["package_manager_version"], "8.15.4");
+    }
+}
@@ -0,0 +1,368 @@
+//! Re-run a task whenever files change (`--watch-run`)
+//!
+//! The task runs in its own process group with stdin detached, so a restart
+//! can stop everything it started (a `sh -c` wrapper and the dev server
+//! under it alike). Changes to gitignored paths and common build output
+//! folders are ignored, so a task writing its own output doesn't restart
+//! itself in a loop.
+
+use std::path::{Path, PathBuf};
+use std::process::{Child, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use console::style;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::WalkBuilder;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+use crate::messages::SelectedTask;
+use crate::render::Theme;
+use crate::{interrupt, print_task_header, report_status, task_command, work_dir};
+
+/// Paths that are never worth restarting for: VCS data, dependencies and
+/// the usual build output, cache and coverage folders
+const DEFAULT_IGNORES: &[&str] = &[
+    "**/.git/**",
+    "**/node_modules/**",
+    "**/target/**",
+    "**/dist/**",
+    "**/build/**",
+    "**/out/**",
+    "**/.next/**",
+    "**/.nuxt/**",
+    "**/.svelte-kit/**",
+    "**/.turbo/**",
+    "**/.cache/**",
+    "**/.gradle/**",
+    "**/coverage/**",
+    "**/__pycache__/**",
+    "**/.venv/**",
+];
+
+/// How long a stopped task gets to exit after SIGTERM before it's killed
+const STOP_GRACE: Duration = Duration::from_secs(2);
+
+/// How long the tree must be quiet before restarting, so a burst of saves
+/// (or a formatter touching many files) triggers a single run
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// What to watch for `--watch-run`
+pub struct WatchOptions {
+    /// Paths to watch recursively (empty = the task's folder)
+    pub paths: Vec<PathBuf>,
+    /// Globs for paths whose changes are ignored, on top of DEFAULT_IGNORES
+    pub ignore: Vec<String>,
+}
+
+/// Paths whose changes don't restart the task
+struct Ignore {
+    globs: GlobSet,
+    gitignores: Vec<Gitignore>,
+}
+
+impl Ignore {
+    fn is_match(&self, path: &Path) -> bool {
+        self.globs.is_match(path)
+            || self.gitignores.iter().any(|gitignore| {
+                path.starts_with(gitignore.path())
+                    && gitignore
+                        .matched_path_or_any_parents(path, path.is_dir())
+                        .is_ignore()
+            })
+    }
+}
+
+impl WatchOptions {
+    /// Build the ignore glob set. Invalid patterns are skipped.
+    fn ignore_globs(&self) -> GlobSet {
+        let mut builder = GlobSetBuilder::new();
+        let patterns = DEFAULT_IGNORES
+            .iter()
+            .copied()
+            .chain(self.ignore.iter().map(String::as_str));
+        for pattern in patterns {
+            if let Ok(glob) = Glob::new(pattern) {
+                builder.add(glob);
+            }
+        }
+        builder.build().unwrap_or_else(|_| GlobSet::empty())
+    }
+}
+
+/// The `.gitignore` files that apply to the watched paths: those inside
+/// them (outside ignored folders) and those above them up to `root`
+fn gitignores(paths: &[PathBuf], root: &Path) -> Vec<Gitignore> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        for dir in path.ancestors().skip(1) {
+            if !dir.starts_with(root) {
+                break;
+            }
+            files.push(dir.join(".gitignore"));
+        }
+        let walker = WalkBuilder::new(path)
+            .hidden(false)
+            .filter_entry(|entry| entry.file_name() != ".git")
+            .build();
+        files.extend(
+            walker
+                .flatten()
+                .filter(|entry| entry.file_name() == ".gitignore")
+                .map(|entry| entry.into_path()),
+        );
+    }
+    files.sort();
+    files.dedup();
+
+    files
+        .iter()
+        .filter(|file| file.is_file())
+        .filter_map(|file| {
+            let mut builder = GitignoreBuilder::new(file.parent()?);
+            builder.add(file);
+            builder.build().ok()
+        })
+        .collect()
+}
+
+/// Whether a file event should restart the task
+fn is_relevant(event: &Event, ignore: &Ignore) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) && event.paths.iter().any(|path| !ignore.is_match(path))
+}
+
+/// Start the task in a process group of its own, without stdin: it no
+/// longer owns the terminal, and reading it would suspend the task
+fn spawn(task: &SelectedTask, command: &str, root: &Path) -> Option<std::io::Result<Child>> {
+    let mut command = task_command(task, command, root)?;
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit());
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    Some(command.spawn())
+}
+
+/// Stop a running task before restarting it: SIGTERM to its process group,
+/// then SIGKILL to whatever is left after [`STOP_GRACE`]
+#[cfg(unix)]
+fn stop(child: &mut Option<Child>) {
+    if let Some(mut child) = child.take() {
+        if matches!(child.try_wait(), Ok(None)) {
+            let Ok(pid) = libc::pid_t::try_from(child.id()) else {
+                child.kill().ok();
+                child.wait().ok();
+                return;
+            };
+            // SAFETY: kill() has no memory-safety preconditions; a negative
+            // pid addresses the group the task leads
+            unsafe { libc::kill(-pid, libc::SIGTERM) };
+            let deadline = Instant::now() + STOP_GRACE;
+            while matches!(child.try_wait(), Ok(None)) && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            // The group outlives its leader while anything it started runs
+            unsafe { libc::kill(-pid, libc::SIGKILL) };
+        }
+        child.wait().ok();
+    }
+}
+
+/// Stop a running task before restarting it
+#[cfg(not(unix))]
+fn stop(child: &mut Option<Child>) {
+    if let Some(mut child) = child.take() {
+        if matches!(child.try_wait(), Ok(None)) {
+            child.kill().ok();
+        }
+        child.wait().ok();
+    }
+}
+
+/// Run a task, then restart it whenever a watched file changes. A still
+/// running task is killed before restarting. Returns 130 on Ctrl+C, or 1 if
+/// watching fails.
+pub fn watch_run(
+    task: &SelectedTask,
+    command: &str,
+    root: &Path,
+    theme: &Theme,
+    options: &WatchOptions,
+) -> i32 {
+    let work_dir = work_dir(&task.config_path, root);
+    let paths = if options.paths.is_empty() {
+        vec![work_dir.to_path_buf()]
+    } else {
+        options.paths.clone()
+    };
+    let ignore = Ignore {
+        globs: options.ignore_globs(),
+        gitignores: gitignores(&paths, root),
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            eprintln!("{} Failed to start watcher: {}", style("✗").red(), e);
+            return 1;
+        }
+    };
+    for path in &paths {
+        if let Err(e) = watcher.watch(path, RecursiveMode::Recursive) {
+            eprintln!(
+                "{} Failed to watch {}: {}",
+                style("✗").red(),
+                path.display(),
+                e
+            );
+            return 1;
+        }
+    }
+
+    let watched = paths
+        .iter()
+        .map(|path| match path.strip_prefix(root) {
+            Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+            Ok(relative) => relative.display().to_string(),
+            Err(_) => path.display().to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    loop {
+        println!(
+            "\n  {}",
+            theme.muted(format!("Watching {} for changes (Ctrl+C to stop)", watched))
+        );
+        print_task_header(task, command, root, theme);
+        let mut child = match spawn(task, command, root) {
+            Some(Ok(child)) => Some(child),
+            Some(Err(e)) => {
+                report_status(Err(e), theme);
+                None
+            }
+            None => {
+                eprintln!("{} Empty command", style("✗").red());
+                return 1;
+            }
+        };
+
+        // Wait for a relevant change, reporting the task's result if it ends first
+        loop {
+            if interrupt::interrupted() {
+                stop(&mut child);
+                println!("\n  {}\n", theme.muted("Stopped watching"));
+                return interrupt::EXIT_CODE;
+            }
+            match rx.recv_timeout(Duration::from_millis(100)) {
+                Ok(Ok(event)) if is_relevant(&event, &ignore) => break,
+                Ok(_) | Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return 1,
+            }
+            if let Some(status) = child.as_mut().and_then(|c| c.try_wait().ok().flatten()) {
+                child = None;
+                report_status(Ok(status), theme);
+            }
+        }
+
+        // Debounce: wait for the burst of events to settle
+        while rx.recv_timeout(DEBOUNCE).is_ok() {}
+
+        stop(&mut child);
+        println!(
+            "\n  {} {}",
+            style("↻").cyan().bold(),
+            style("Change detected, restarting").cyan()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, ModifyKind};
+    use std::fs;
+
+    #[test]
+    fn test_is_relevant() {
+        let options = WatchOptions {
+            paths: Vec::new(),
+            ignore: vec!["*.log".to_string()],
+        };
+        let ignore = Ignore {
+            globs: options.ignore_globs(),
+            gitignores: Vec::new(),
+        };
+        let event = |kind, path: &str| Event::new(kind).add_path(PathBuf::from(path));
+
+        assert!(is_relevant(
+            &event(EventKind::Create(CreateKind::File), "/repo/src/main.rs"),
+            &ignore
+        ));
+        assert!(!is_relevant(
+            &event(EventKind::Create(CreateKind::File), "/repo/debug.log"),
+            &ignore
+        ));
+        assert!(!is_relevant(
+            &event(EventKind::Create(CreateKind::File), "/repo/.git/index"),
+            &ignore
+        ));
+        assert!(!is_relevant(
+            &event(EventKind::Access(AccessKind::Any), "/repo/src/main.rs"),
+            &ignore
+        ));
+        assert!(!is_relevant(
+            &event(EventKind::Modify(ModifyKind::Any), "/repo/web/dist/app.js"),
+            &ignore
+        ));
+    }
+
+    #[test]
+    fn test_gitignored_changes_are_ignored() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(root.join(".gitignore"), "*.tmp\n").unwrap();
+        fs::create_dir_all(root.join("web/src")).unwrap();
+        fs::write(root.join("web/.gitignore"), "generated/\n").unwrap();
+
+        let paths = [root.join("web")];
+        let ignore = Ignore {
+            globs: GlobSet::empty(),
+            gitignores: gitignores(&paths, root),
+        };
+        let changed = |path: &str| {
+            is_relevant(
+                &Event::new(EventKind::Modify(ModifyKind::Any)).add_path(root.join(path)),
+                &ignore,
+            )
+        };
+
+        assert!(changed("web/src/main.ts"));
+        assert!(!changed("web/src/scratch.tmp"));
+        assert!(!changed("web/generated/api.ts"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stop_ends_the_whole_group() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let marker = dir.path().join("survived");
+        // The shell's background child would outlive a plain kill of the shell
+        let script = format!("(sleep 1; touch {}) & wait", marker.display());
+        let mut command = std::process::Command::new("sh");
+        command.args(["-c", &script]);
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        let mut child = Some(command.spawn().unwrap());
+
+        std::thread::sleep(Duration::from_millis(100));
+        stop(&mut child);
+        std::thread::sleep(Duration::from_millis(1500));
+        assert!(!marker.exists());
+    }
+}
@@ -0,0 +1,215 @@
+//! Integration tests for the non-interactive CLI modes
+//!
+//! These run the `task` binary against the fixtures directory and check its
+//! output and exit code.
+
+use std::process::{Command, Output};
+
+use serde_json::Value;
+
+/// Get path to fixtures directory
+fn fixtures_path() -> String {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    format!("{}/fixtures", manifest_dir)
+}
+
+/// Run `task` with `args`
+fn task(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_task"))
+        .args(args)
+        .output()
+        .expect("Failed to run task")
+}
+
+/// Run `task` with `args` and parse its JSON output
+fn task_json(args: &[&str]) -> Vec<Value> {
+    let output = task(args);
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).expect("Output should be a JSON array")
+}
+
+fn runner_types(runners: &[Value]) -> Vec<&str> {
+    runners
+        .iter()
+        .map(|r| r["runner_type"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_json_runner_filter() {
+    let fixtures = fixtures_path();
+    let runners = task_json(&["-j", "--runner", "cargo", &fixtures]);
+
+    assert!(!runners.is_empty());
+    assert!(runner_types(&runners).iter().all(|&t| t == "cargo"));
+}
+
+#[test]
+fn test_unknown_runner_names_are_rejected() {
+    let fixtures = fixtures_path();
+    for (flag, value) in [("--runner", "bogus"), ("--runner-priority", "cargo,bogus")] {
+        let output = task(&["-j", flag, value, &fixtures]);
+        assert_eq!(output.status.code(), Some(2));
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("unknown runner type: bogus"), "{}", stderr);
+    }
+}
+
+#[test]
+fn test_json_depth_zero_only_scans_root() {
+    let fixtures = fixtures_path();
+    let runners = task_json(&["-j", "--depth", "0", &fixtures]);
+
+    let mut types = runner_types(&runners);
+    types.sort();
+    assert_eq!(types, vec!["just", "make", "npm", "turbo"]);
+}
+
+#[test]
+fn test_only_config_path_glob() {
+    let fixtures = fixtures_path();
+    let runners = task_json(&["-j", "--only", "apps/*/package.json", &fixtures]);
+
+    assert_eq!(runner_types(&runners), ["npm"]);
+    let config_path = runners[0]["config_path"].as_str().unwrap();
+    assert!(config_path.ends_with("apps/web/package.json"));
+
+    // A broken pattern is an error, not a filter that matches nothing
+    let output = task(&["-j", "--only", "apps/[web/package.json", &fixtures]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("--only"), "{}", stderr);
+}
+
+#[test]
+fn test_json_report_errors() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("turbo.json"), "{ not json").unwrap();
+    std::fs::write(dir.path().join("Makefile"), "build:\n\techo hi\n").unwrap();
+    let dir = dir.path().to_str().unwrap();
+
+    // Errors are only mentioned when asked for
+    assert_eq!(runner_types(&task_json(&["-j", dir])), ["make"]);
+
+    let output = task(&["-j", "--report-errors", dir]);
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["runners"].as_array().unwrap().len(), 1);
+    let errors = report["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0]["path"].as_str().unwrap().ends_with("turbo.json"));
+}
+
+#[test]
+fn test_list_lines() {
+    let fixtures = fixtures_path();
+    let output = task(&["--list", "--runner", "make", &fixtures]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains('\x1b'));
+    assert!(stdout
+        .lines()
+        .any(|line| line == "make\t.\tbuild\tmake build"));
+}
+
+#[test]
+fn test_exec_exit_codes() {
+    let fixtures = fixtures_path();
+
+    // Several fixtures have a task named build; only those are listed
+    let output = task(&["--exec", "build", &fixtures]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("make build"));
+    assert!(stderr.contains("npm run build"));
+    assert!(!stderr.contains("build:android"));
+    assert!(!stderr.contains("publish"));
+
+    let output = task(&["--exec", "zzz-no-such-task", &fixtures]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_exec_runs_single_match() {
+    if Command::new("make").arg("--version").output().is_err() {
+        eprintln!("Skipping make test - make not installed");
+        return;
+    }
+
+    let fixtures = fixtures_path();
+    let output = task(&["--exec", "build", "--runner", "make", &fixtures]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Compiling source files..."));
+
+    // The runner and folder columns of --list narrow a name down too
+    let output = task(&["--exec", "make . build", &fixtures]);
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_plan_prints_dependencies_first() {
+    let fixtures = fixtures_path();
+    let output = task(&["--plan", "make all", &fixtures]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        ["make build", "make test", "make all"]
+    );
+}
+
+#[test]
+fn test_run_all_selects_by_name_and_confirms() {
+    let fixtures = fixtures_path();
+
+    // Without a terminal to confirm on, the matches are listed but not run
+    let output = task(&["run-all", "lint", &fixtures]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("apps/web npm run lint"));
+    assert!(!stdout.contains("dotnet"));
+    assert!(!stdout.contains("Running"));
+
+    let output = task(&["--runner", "make", "run-all", "buil*", "--yes", &fixtures]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Compiling source files..."));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_ctrl_c_during_task_exits_130() {
+    use std::os::unix::process::CommandExt;
+    use std::process::Stdio;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    if Command::new("make").arg("--version").output().is_err() {
+        eprintln!("Skipping make test - make not installed");
+        return;
+    }
+
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("Makefile"), "wait:\n\tsleep 30\n").unwrap();
+
+    // Its own process group stands in for the terminal's foreground group
+    let started = Instant::now();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_task"))
+        .args(["--exec", "wait"])
+        .arg(dir.path())
+        .process_group(0)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    thread::sleep(Duration::from_secs(1));
+    Command::new("kill")
+        .args(["-INT", "--", &format!("-{}", child.id())])
+        .status()
+        .unwrap();
+
+    let status = child.wait().unwrap();
+    assert_eq!(status.code(), Some(130));
+    assert!(started.elapsed() < Duration::from_secs(30));
+}
@@ -0,0 +1,47 @@
+//! Golden test for the full scan output over `fixtures/`
+//!
+//! Every parser that has a fixture contributes to one committed JSON file, so
+//! cross-parser regressions and ordering changes show up in a single diff.
+//! After an intended change, regenerate it with
+//! `UPDATE_FIXTURES=1 cargo test --test golden`.
+
+use std::path::{Path, PathBuf};
+
+use task_runner_detector::{scan_with_options, ScanOptions, TaskRunner};
+
+/// Scan results in a stable form: config paths relative to `root` (with `/`
+/// separators) and runners sorted by path, then runner type. Tasks keep the
+/// order their parser produced.
+fn normalized_scan(root: &Path) -> Vec<TaskRunner> {
+    let mut runners = scan_with_options(root, ScanOptions::default()).unwrap();
+    for runner in &mut runners {
+        let relative = runner.config_path.strip_prefix(root).unwrap();
+        runner.config_path = PathBuf::from(relative.to_string_lossy().replace('\\', "/"));
+    }
+    runners.sort_by_cached_key(|r| (r.config_path.clone(), r.runner_type.display_name()));
+    runners
+}
+
+#[test]
+fn test_scan_matches_golden_file() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let fixtures = manifest_dir.join("fixtures");
+    let golden_path = fixtures.join("golden_scan.json");
+
+    let mut output = serde_json::to_string_pretty(&normalized_scan(&fixtures)).unwrap();
+    output.push('\n');
+
+    // Allow updating the golden file with UPDATE_FIXTURES=1 cargo test
+    if std::env::var("UPDATE_FIXTURES").is_ok() {
+        std::fs::write(&golden_path, &output).expect("Failed to write golden file");
+        return;
+    }
+
+    let expected =
+        std::fs::read_to_string(&golden_path).expect("Failed to read fixtures/golden_scan.json");
+    assert_eq!(
+        output, expected,
+        "Scan output doesn't match fixtures/golden_scan.json \
+         (rerun with UPDATE_FIXTURES=1 if the change is intended)"
+    );
+}
@@ -6,6 +6,9 @@
 //! Note: These tests must run serially (--test-threads=1) because they
 //! spawn PTY sessions that can interfere with each other.
 
+// `spawn(&format!(..))` reads fine; newer clippy flags the borrow
+#![allow(clippy::needless_borrows_for_generic_args)]
+
 use std::process::Command;
 use std::time::Duration;
 
@@ -321,3 +324,65 @@ fn test_deno_task_execution() {
 
     session.expect(Eof).ok();
 }
+
+#[test]
+fn test_edit_flag_opens_edit_mode() {
+    ensure_binary_built();
+
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let fixture = format!("{}/fixtures/apps/web", manifest_dir);
+
+    let mut session = spawn(&format!("{} --edit build {}", binary_path(), fixture))
+        .expect("Failed to spawn task");
+
+    session.set_expect_timeout(Some(Duration::from_secs(10)));
+
+    // Picker should open directly in Edit mode on the best match
+    session
+        .expect("edit mode")
+        .expect("Picker should start in edit mode");
+
+    wait_for_filter();
+
+    // Enter runs the (unchanged) edit buffer
+    session.send("\r").expect("Failed to send Enter");
+
+    session
+        .expect("Bundling for production")
+        .expect("Should see npm build output");
+
+    session
+        .expect("Task completed")
+        .expect("Task should complete");
+
+    session.expect(Eof).ok();
+}
+
+#[test]
+fn test_help_overlay() {
+    ensure_binary_built();
+
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let fixture = format!("{}/fixtures/apps/web", manifest_dir);
+
+    let mut session =
+        spawn(&format!("{} {}", binary_path(), fixture)).expect("Failed to spawn task");
+
+    session.set_expect_timeout(Some(Duration::from_secs(10)));
+
+    session
+        .expect("? help")
+        .expect("Status line should hint at help");
+
+    session.send("?").expect("Failed to send ?");
+    session
+        .expect("Ctrl+W")
+        .expect("Help overlay should list editing shortcuts");
+
+    // Any key closes the overlay, then Esc exits
+    session.send("x").expect("Failed to send key");
+    wait_for_filter();
+    session.send("\x1b").expect("Failed to send Escape");
+
+    session.expect("Cancelled").expect("Should exit cleanly");
+}